@@ -0,0 +1,145 @@
+//! `trait_xml!`, but the XML lives in an actual `.xml` file. Now the people who review your
+//! interface specs never have to look at a single line of Rust, which is probably for the best.
+//!
+//! ```ignore
+//! trait_xml_include::include_trait_xml!("spec/foo.xml");
+//! ```
+//! reads `spec/foo.xml` (relative to `CARGO_MANIFEST_DIR`, same as `include_str!` would be relative
+//! to the file it's in) and hands whatever's in it to [`trait_xml!`]. You'll need `trait-xml` as a
+//! dependency too, since that's what does the actual work.
+//!
+//! Since it's a real XML file now, it gets treated like one:
+//! - The `<?xml ...?>` declaration, and any other `<?...?>` processing instructions, get skipped.
+//! - Comments get skipped, so they can have whatever they like in them, even unbalanced quotes.
+//! - `&lt;`, `&gt;`, `&amp;`, `&quot;`, `&apos;` and numeric character references like `&#60;`
+//!   get decoded everywhere outside CDATA sections. A bare `&` or `<` is an error, same as
+//!   anywhere else XML gets parsed, so `Vec<u8>` has to be written `Vec&lt;u8&gt;` and `&self`
+//!   `&amp;self`.
+//! - CDATA sections are left alone for [`trait_xml!`] to deal with.
+//! - Attribute values can be quoted like in any other XML, `<trait name="Foo">` and
+//!   `<param mut="mut">` included. The quotes get taken off, since [`trait_xml!`] can't do that
+//!   itself.
+//! - Start and end tags have to match up.
+//!
+//! That's all done by `trait_xml::build::to_tokens`, the same reading `trait_xml::build` does for
+//! `build.rs`, so a spec means the same thing to both.
+//!
+//! Anything wrong with the file gets reported as a compile error with the file, line and column,
+//! e.g. `spec/foo.xml:3:9: expected </name>, found </nmae>`.
+//!
+//! The file also gets `include_str!`ed, so cargo knows to rebuild when it changes.
+//!
+//! [`trait_xml!`]: https://docs.rs/trait-xml/latest/trait_xml/macro.trait_xml.html
+
+use proc_macro::{TokenStream, TokenTree};
+use std::path::Path;
+use trait_xml::build::to_tokens;
+
+/// Reads a trait definition from an XML file and expands it with `trait_xml!`. See the crate docs
+/// for what's accepted.
+#[proc_macro]
+pub fn include_trait_xml(input: TokenStream) -> TokenStream {
+    let expansion = match expand(input) {
+        Ok(expansion) => expansion,
+        Err(message) => format!("compile_error!({message:?});"),
+    };
+    expansion.parse().unwrap()
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let relative = path_literal(input)?;
+    let root = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| "CARGO_MANIFEST_DIR isn't set, so there's nothing to find the file from")?;
+    let path = Path::new(&root).join(&relative);
+    let source = std::fs::read_to_string(&path)
+        .map_err(|err| format!("couldn't read {}: {err}", path.display()))?;
+
+    let tokens = to_tokens(&source)
+        .map_err(|err| format!("{relative}:{}:{}: {}", err.line, err.column, err.message))?;
+    if tokens.parse::<TokenStream>().is_err() {
+        return Err(format!(
+            "{relative}: the file's contents can't be turned into Rust tokens - check for \
+             unclosed brackets or string literals",
+        ));
+    }
+
+    Ok(format!(
+        "const _: &str = include_str!({:?});\n::trait_xml::trait_xml! {{\n{tokens}\n}}",
+        path.display().to_string(),
+    ))
+}
+
+/// Gets the path out of `"spec/foo.xml"` or `r"spec/foo.xml"`.
+fn path_literal(input: TokenStream) -> Result<String, String> {
+    let mut tokens = input.into_iter();
+    let literal = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => literal.to_string(),
+        _ => return Err("expected a single string literal, e.g. \"spec/foo.xml\"".into()),
+    };
+
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return Ok(raw[hashes + 1..raw.len() - hashes - 1].into());
+    }
+
+    let quoted = literal
+        .strip_prefix('"')
+        .and_then(|literal| literal.strip_suffix('"'))
+        .ok_or("expected a string literal, e.g. \"spec/foo.xml\"")?;
+    unescape(quoted).ok_or_else(|| format!("{literal} isn't a valid string literal"))
+}
+
+/// Undoes the escapes in the inside of a string literal, the same way the compiler would.
+fn unescape(quoted: &str) -> Option<String> {
+    let mut path = String::new();
+    let mut chars = quoted.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            path.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => path.push('\n'),
+            'r' => path.push('\r'),
+            't' => path.push('\t'),
+            '0' => path.push('\0'),
+            c @ ('\\' | '\'' | '"') => path.push(c),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                path.push(char::from(
+                    u8::from_str_radix(&hex, 16).ok().filter(u8::is_ascii)?,
+                ));
+            }
+            'u' => {
+                let code = chars.by_ref().skip(1).take_while(|&c| c != '}');
+                let hex: String = code.filter(|&c| c != '_').collect();
+                path.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            // A line continuation, which skips the newline and any whitespace after it
+            '\n' => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+            _ => return None,
+        }
+    }
+    Some(path)
+}
+
+#[test]
+fn test() {
+    // trybuild builds tests/fail as a package of its own inside target/, so that's the
+    // `CARGO_MANIFEST_DIR` the specs they include get looked up in
+    let target = std::env::current_exe()
+        .unwrap()
+        .ancestors()
+        .find(|dir| dir.join("CACHEDIR.TAG").is_file())
+        .unwrap()
+        .to_path_buf();
+    let spec = target.join("tests/trybuild/trait-xml-include/tests/spec");
+    std::fs::create_dir_all(&spec).unwrap();
+    for file in std::fs::read_dir("tests/spec").unwrap() {
+        let file = file.unwrap();
+        std::fs::copy(file.path(), spec.join(file.file_name())).unwrap();
+    }
+
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/fail/*.rs");
+}
@@ -0,0 +1,3 @@
+trait_xml_include::include_trait_xml!("tests/spec/broken.xml");
+
+fn main() {}
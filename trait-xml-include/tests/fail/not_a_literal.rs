@@ -0,0 +1,3 @@
+trait_xml_include::include_trait_xml!(spec::foo);
+
+fn main() {}
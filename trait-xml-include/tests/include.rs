@@ -0,0 +1,66 @@
+trait_xml_include::include_trait_xml!("tests/spec/shape.xml");
+
+#[derive(Debug)]
+struct Square(f64);
+
+impl Shape for Square {
+    const SIDES: u32 = 4;
+
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+#[derive(Debug)]
+struct Line;
+
+impl Shape for Line {
+    const SIDES: u32 = 1;
+
+    fn area(&self) -> f64 {
+        0.0
+    }
+}
+
+#[test]
+fn include() {
+    assert_eq!(Square(2.0).area(), 4.0);
+    assert_eq!(Square(2.0).corners().len(), 4);
+    assert!(Line.corners().is_empty());
+    assert_eq!(Square::label(), "shape!");
+}
+
+// The same file again, with its path spelled with escapes
+mod escaped {
+    trait_xml_include::include_trait_xml!(
+        "tests/spec/\
+         sh\x61\u{70}e.xml"
+    );
+
+    impl Shape for super::Line {
+        const SIDES: u32 = 1;
+
+        fn area(&self) -> f64 {
+            0.0
+        }
+    }
+}
+
+#[test]
+fn escaped_path() {
+    assert_eq!(<Line as escaped::Shape>::label(), "shape!");
+}
+
+trait_xml_include::include_trait_xml!("tests/spec/quoted.xml");
+
+impl Scale for Line {
+    fn factor(&self) -> u8 {
+        3
+    }
+}
+
+#[test]
+fn quoted_attrs() {
+    assert_eq!(Line.factor(), 3);
+    assert_eq!(unsafe { <Line as Scale>::apply(2, 3) }, 6);
+}
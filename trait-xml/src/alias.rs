@@ -0,0 +1,122 @@
+/// Expands an `<alias/>` into a blanket implementation of the trait for everything that meets its
+/// supertraits and `where` clauses.
+///
+/// Expected initial call example:
+/// ```
+/// trait ReadWrite: std::io::Read + std::io::Write {}
+///
+/// trait_xml::trait_xml_expand_alias! {
+///     @expand {
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: ReadWrite,
+///             gparams: [],
+///             tpbs: [std::io::Write + std::io::Read +],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [],
+///         },
+///     }
+/// }
+///
+/// fn assert_read_write<T: ReadWrite>() {}
+/// assert_read_write::<std::fs::File>();
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_alias {
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: [],
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: [],
+                fns: [],
+            },
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_alias,
+                    rule: [@namescallback],
+                    args: [
+                        name: $name,
+                        gparams: [$($gpt)*],
+                        tpbs: $tpbt,
+                        wc: $wct,
+                    ],
+                ],
+            }
+        }
+    };
+    // A blanket impl can't uphold whatever an unsafe trait promises
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: [unsafe],
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding alias `",
+            stringify!($name),
+            "`: unsafe traits can't be aliases",
+        ));
+    };
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding alias `",
+            stringify!($name),
+            "`: aliases can't have associated types, constants or functions",
+        ));
+    };
+
+    (
+        @namescallback {
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            tpbs: [$($tpbt:tt)*],
+            wc: [$($wct:tt)*],
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        impl<$($gpt)* __Alias: $($tpbt)* ?Sized> $name<$($lt,)* $($ty),*> for __Alias
+        where
+            $($wct)*
+        {
+        }
+    };
+}
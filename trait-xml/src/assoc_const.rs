@@ -26,6 +26,24 @@
 /// ```
 #[macro_export]
 macro_rules! trait_xml_parse_assoc_const {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_const,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     // Empty input error
     (
         @parse {
@@ -65,7 +83,7 @@ macro_rules! trait_xml_parse_assoc_const {
             $($rule)+ {
                 input: [$($rest)*],
                 $($field: $fieldtokens,)+
-                assoc const: [ac [$name] [$type] [$($default)?]],
+                assoc const: [ac [$name] [$type] [$($default)?] []],
             }
         }
     };
@@ -292,6 +310,28 @@ macro_rules! trait_xml_parse_assoc_const {
             "`",
         ));
     };
+    // CDATA sections are passed through without looking inside
+    (
+        @parsedefault {
+            input: [<![CDATA[$($cdata:tt)*]]>$($rest:tt)*],
+            name: $($name:ident)?,
+            type: $($type:ty)?,
+            default: ,
+            tokens: [$($defaulttok:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_const! {
+            @parsedefault {
+                input: [$($rest)*],
+                name: $($name)?,
+                type: $($type)?,
+                default: ,
+                tokens: [$($defaulttok)* $($cdata)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
     (
         @parsedefault {
             input: [$first:tt$($rest:tt)*],
@@ -25,10 +25,12 @@
 ///         gparams: [],
 ///         params: [],
 ///         hasparams: [],
-///         ret: ,
+///         ret: [],
 ///         where: [],
 ///         def: [],
 ///         hasdef: [],
+///         examples: [],
+///         nstags: [],
 ///         callback: [
 ///             name: trait_xml::trait_xml_inner,
 ///             rule: [@afcallback],
@@ -41,6 +43,58 @@
 /// ```
 #[macro_export]
 macro_rules! trait_xml_parse_assoc_fn {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseparams {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseparams],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseparam {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseparam],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     // Empty input error
     (
         @parse {
@@ -50,10 +104,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -74,13 +130,15 @@ macro_rules! trait_xml_parse_assoc_fn {
             input: [</assocfn>$($rest:tt)*],
             name: $name:ident,
             qualifiers: $qt:tt,
-            gparams: $gpt:tt,
+            gparams: $(empty)? $([$($gpt:tt)*])?,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
-            where: $wt:tt,
+            ret: $rettoks:tt,
+            where: $(empty)? $([$($wt:tt)*])?,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: [$([$testtoks:tt [$($example:tt)+]])*],
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: [$($rule:tt)+],
@@ -96,14 +154,35 @@ macro_rules! trait_xml_parse_assoc_fn {
                     af
                     [$name]
                     $qt
-                    $gpt
+                    [$($($gpt)*)?]
                     $pt
-                    [$($rt)?]
-                    $wt
+                    $rettoks
+                    [$($($wt)*)?]
                     $dt
+                    [$([doc = "```"] [doc = stringify!($($example)+)] [doc = "```"])*]
+                    []
                 ],
+                examples: [$([$testtoks [$($example)+]])*],
+                nstags: $nst,
             }
         }
+
+        $crate::trait_xml_parse_assoc_fn! {
+            @checkmut [$name] $hdt $pt
+        }
+    };
+
+    // `mut` bindings only mean something when there's a body for them to be mutated in
+    (@checkmut [$name:ident] [[]] $paramstoks:tt) => {};
+    (
+        @checkmut [$name:ident] [] [$([param $argt:tt $typet:tt $recvt:tt [] $attrst:tt])*]
+    ) => {};
+    (@checkmut [$name:ident] [] $paramstoks:tt) => {
+        compile_error!(concat!(
+            "error parsing associated function `",
+            stringify!($name),
+            "`: `<mut/>` parameters need a `<rust></rust>` body",
+        ));
     };
 
     // Other parsing failures
@@ -115,10 +194,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -142,10 +223,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -169,10 +252,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -188,10 +273,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                         gparams: $gpt,
                         params: $pt,
                         hasparams: $hpt,
-                        ret: [$($rt)?],
+                        ret: $rettoks,
                         where: $wt,
                         def: $dt,
                         hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
                         callback: $callbacktoks,
                     ],
                 ],
@@ -212,10 +299,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -241,10 +330,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -259,10 +350,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -279,10 +372,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -310,10 +405,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -328,10 +425,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -347,10 +446,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -365,10 +466,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -384,10 +487,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -411,10 +516,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -426,10 +533,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -442,10 +551,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -470,10 +581,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -498,10 +611,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -519,19 +634,162 @@ macro_rules! trait_xml_parse_assoc_fn {
         ));
     };
 
+    // `<gparams/>` and `<where/>` are empty sections, which get recorded as `empty` rather than
+    // `[]` so there can't be another one after them. `<params/>` is the same as
+    // `<params></params>`
+    (
+        @parse {
+            input: [<gparams/>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: [],
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parse {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: empty,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<gparams/>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function: multiple `<gparams>` sections found. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<where/>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: [],
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parse {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: empty,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<where/>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function: encountered multiple `where` clauses. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<params/>$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parse {
+                input: [<params></params>$($rest)*],
+                $($state)*
+            }
+        }
+    };
+
     // Parse generic parameters
     (
         @parse {
             input: [<gparams>$($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
-            gparams: [$($gpt:tt)+],
+            gparams: $(empty)? $([$($gpt:tt)+])?,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -553,10 +811,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: [],
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -574,10 +834,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                         qualifiers: $qt,
                         params: $pt,
                         hasparams: $hpt,
-                        ret: [$($rt)?],
+                        ret: $rettoks,
                         where: $wt,
                         def: $dt,
                         hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
                         callback: $callbacktoks,
                     ],
                 ],
@@ -594,10 +856,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: [[]],
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -620,10 +884,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: [],
             hasparams: [],
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -635,10 +901,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: [],
                 hasparams: [[]],
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -651,10 +919,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -676,10 +946,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -691,10 +963,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -707,10 +981,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -722,13 +998,61 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
                 arg: [],
                 type: ,
+                recv: [],
+                mut: [],
+                attrs: [],
+            }
+        }
+    };
+    (
+        @parseparams {
+            input: [<param $($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: [param],
+                input: [$($rest)*],
+                children: [],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@paramattrscallback],
+                    args: [
+                        name: [$($name)?],
+                        qualifiers: $qt,
+                        gparams: $gpt,
+                        params: $pt,
+                        hasparams: $hpt,
+                        ret: $rettoks,
+                        where: $wt,
+                        def: $dt,
+                        hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
+                        callback: $callbacktoks,
+                    ],
+                ],
             }
         }
     };
@@ -740,10 +1064,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -767,10 +1093,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -794,10 +1122,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -823,10 +1153,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -834,6 +1166,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: $argtoks:tt,
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -850,10 +1185,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -861,6 +1198,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: [],
             type: ,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -878,10 +1218,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -889,6 +1231,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: [$($argtok:tt)+],
             type: ,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -905,10 +1250,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -916,6 +1263,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: [],
             type: $type:ty,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -932,13 +1282,18 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: [$($pt:tt)*],
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             arg: [$arg:tt],
             type: $type:ty,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         $crate::trait_xml_parse_assoc_fn! {
@@ -947,12 +1302,14 @@ macro_rules! trait_xml_parse_assoc_fn {
                 name: $($name)?,
                 qualifiers: $qt,
                 gparams: $gpt,
-                params: [$($pt)* [param [$arg] [$type]]],
+                params: [$($pt)* [param [$arg] [$type] $recvtoks $muttoks $pattrstoks]],
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -965,10 +1322,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -976,6 +1335,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: [$($ainv:tt)+],
             type: $type:ty,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -994,10 +1356,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1005,6 +1369,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: [$($arg:tt)+],
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -1023,13 +1390,18 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             arg: [],
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         $crate::trait_xml_parse_assoc_fn! {
@@ -1040,13 +1412,18 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
                 arg: [],
                 type: $($type)?,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: $pattrstoks,
             }
         }
     };
@@ -1058,10 +1435,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1069,6 +1448,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: [$($arg:tt)+],
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -1087,13 +1469,18 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             arg: [],
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         $crate::trait_xml_parse_name_ident! {
@@ -1109,12 +1496,17 @@ macro_rules! trait_xml_parse_assoc_fn {
                         gparams: $gpt,
                         params: $pt,
                         hasparams: $hpt,
-                        ret: [$($rt)?],
+                        ret: $rettoks,
                         where: $wt,
                         def: $dt,
                         hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
                         callback: $callbacktoks,
                         type: [$($type)?],
+                        recv: $recvtoks,
+                        mut: $muttoks,
+                        attrs: $pattrstoks,
                     ],
                 ],
             }
@@ -1128,10 +1520,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1139,6 +1533,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: $argtoks:tt,
             type: $type:ty,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -1149,70 +1546,389 @@ macro_rules! trait_xml_parse_assoc_fn {
             "`",
         ));
     };
+    // `Self`, `&Self` and `&mut Self` parameters are remembered so that generated impls know how
+    // A leading `&amp;` is decoded up front so `&amp;Self` and friends are still receivers
     (
         @parseparam {
-            input: [<type>$($rest:tt)*],
+            input: [<type>& amp ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
+                input: [<type>& $($rest)*],
+                $($state)*
+            }
+        }
+    };
+    // Same goes for a receiver wrapped in a CDATA section, which is unwrapped so the arms below see
+    // it. Anything else in CDATA is left alone since it's likely not safe to look inside
+    (
+        @parseparam {
+            input: [<type><![CDATA[$(& $($lt:lifetime)?)? Self]]></type>$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
+                input: [<type>$(& $($lt)?)? Self</type>$($rest)*],
+                $($state)*
+            }
+        }
+    };
+    (
+        @parseparam {
+            input: [<type><![CDATA[& $($lt:lifetime)? mut Self]]></type>$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
+                input: [<type>& $($lt)? mut Self</type>$($rest)*],
+                $($state)*
+            }
+        }
+    };
+    // `Self` is being taken
+    (
+        @parseparam {
+            input: [<type>Self</type>$($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             arg: $argtoks:tt,
             type: ,
+            recv: [],
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
-        $crate::trait_xml_parse_type_ty! {
-            @parse {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
                 input: [$($rest)*],
-                type: ,
-                tokens: [],
-                callback: [
-                    name: $crate::trait_xml_parse_assoc_fn,
-                    rule: [@paramtypecallback],
-                    args: [
-                        name: [$($name)?],
-                        qualifiers: $qt,
-                        gparams: $gpt,
-                        params: $pt,
-                        hasparams: $hpt,
-                        ret: [$($rt)?],
-                        where: $wt,
-                        def: $dt,
-                        hasdef: $hdt,
-                        callback: $callbacktoks,
-                        arg: $argtoks,
-                    ],
-                ],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: $argtoks,
+                type: Self,
+                recv: [value],
+                mut: $muttoks,
+                attrs: $pattrstoks,
             }
         }
     };
     (
         @parseparam {
-            input: [</$unk:tt$($rest:tt)*],
+            input: [<type>&$($lt:lifetime)? Self</type>$($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
-            callback: [
-                name: $callback:path,
-                rule: $ruletoks:tt,
-                args: $argstoks:tt,
-            ],
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
             arg: $argtoks:tt,
-            type: $($type:ty)?,
+            type: ,
+            recv: [],
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
-        compile_error!(concat!(
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: $argtoks,
+                type: &$($lt)? Self,
+                recv: [ref],
+                mut: $muttoks,
+                attrs: $pattrstoks,
+            }
+        }
+    };
+    (
+        @parseparam {
+            input: [<type>&$($lt:lifetime)? mut Self</type>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+            arg: $argtoks:tt,
+            type: ,
+            recv: [],
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: $argtoks,
+                type: &$($lt)? mut Self,
+                recv: [refmut],
+                mut: $muttoks,
+                attrs: $pattrstoks,
+            }
+        }
+    };
+    (
+        @parseparam {
+            input: [<type>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+            arg: $argtoks:tt,
+            type: ,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_type_ty! {
+            @parse {
+                input: [$($rest)*],
+                type: ,
+                tokens: [],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@paramtypecallback],
+                    args: [
+                        name: [$($name)?],
+                        qualifiers: $qt,
+                        gparams: $gpt,
+                        params: $pt,
+                        hasparams: $hpt,
+                        ret: $rettoks,
+                        where: $wt,
+                        def: $dt,
+                        hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
+                        callback: $callbacktoks,
+                        arg: $argtoks,
+                        recv: $recvtoks,
+                        mut: $muttoks,
+                        attrs: $pattrstoks,
+                    ],
+                ],
+            }
+        }
+    };
+    // `mut` bindings and attributes only affect the parameter in the trait's own declaration
+    (
+        @parseparam {
+            input: [<mut/>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: [mut],
+            attrs: $pattrstoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function parameter: multiple `<mut/>` tags encountered. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parseparam {
+            input: [<mut/>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: [],
+            attrs: $pattrstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: $argtoks,
+                type: $($type)?,
+                recv: $recvtoks,
+                mut: [mut],
+                attrs: $pattrstoks,
+            }
+        }
+    };
+    (
+        @parseparam {
+            input: [<attr>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparamattr {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: $argtoks,
+                type: $($type)?,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: $pattrstoks,
+                attr: [],
+            }
+        }
+    };
+    (
+        @parseparam {
+            input: [</$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
             "error parsing associated function parameter: encountered unknown end tag `",
             stringify!($unk),
             "`. caller: `",
@@ -1228,10 +1944,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1239,6 +1957,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: $argtoks:tt,
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -1257,10 +1978,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1268,6 +1991,9 @@ macro_rules! trait_xml_parse_assoc_fn {
             ],
             arg: $argtoks:tt,
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -1288,13 +2014,18 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             arg: [$($argtok:tt)+],
             type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
         }
     ) => {
         $crate::trait_xml_parse_assoc_fn! {
@@ -1305,92 +2036,630 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
                 arg: [$($argtok)+],
                 type: $($type)?,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: $pattrstoks,
+            }
+        }
+    };
+    (
+        @parseparamarg {
+            input: [$first:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+            arg: [$($argtok:tt)*],
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparamarg {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: [$($argtok)* $first],
+                type: $($type)?,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: $pattrstoks,
+            }
+        }
+    };
+
+    // Parse parameter attribute
+    (
+        @parseparamattr {
+            input: [</attr>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+            attr: [],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function parameter: empty `<attr></attr>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parseparamattr {
+            input: [</attr>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: [$($pattr:tt)*],
+            attr: [$($attrtok:tt)+],
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparam {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: $argtoks,
+                type: $($type)?,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: [$($pattr)* [$($attrtok)+]],
+            }
+        }
+    };
+    (
+        @parseparamattr {
+            input: [],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+            attr: $attrtoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function parameter: expected `</attr>`, found end of input. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parseparamattr {
+            input: [$first:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+            arg: $argtoks:tt,
+            type: $($type:ty)?,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
+            attr: [$($attrtok:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparamattr {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+                arg: $argtoks,
+                type: $($type)?,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: $pattrstoks,
+                attr: [$($attrtok)* $first],
+            }
+        }
+    };
+
+    // Parse return type
+    (
+        @parse {
+            input: [<ret>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: [$($rt:tt)+],
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function: return type already defined as `",
+            $(stringify!($rt),)+
+            "`, but encountered another `<ret>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<ret>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: [],
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseret {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: [],
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parseret {
+            input: [</ret>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: [],
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function return type: empty return type. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    // The return type is kept as raw tokens rather than a `ty` so that things like `Self` can still
+    // be matched on later, so it gets checked separately
+    (
+        @parseret {
+            input: [</ret>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: [$($rt:tt)+],
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @checkret {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: [$($rt)+],
+                raw: [$($rt)+],
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @checkret {
+            input: $inputtoks:tt,
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: [$rt:ty],
+            raw: $rawtoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parse {
+                input: $inputtoks,
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rawtoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @checkret {
+            input: $inputtoks:tt,
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: [$($rtinv:tt)+],
+            raw: $rawtoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing associated function return type: input forms invalid type `",
+            $(stringify!($rtinv),)+
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    // CDATA sections are passed through without looking inside
+    (
+        @parseret {
+            input: [<![CDATA[$($cdata:tt)*]]>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: [$($rt:tt)*],
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseret {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: [$($rt)* $($cdata)*],
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    // XML entities, which might be hiding in groups too
+    (
+        @parseret {
+            input: [& lt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& lt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseret],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseret {
+            input: [& gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseret],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseret {
+            input: [& amp ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& amp ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseret],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseret {
+            input: [- & gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [- & gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseret],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseret {
+            input: [($($group:tt)*) $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [($($group)*) $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseret],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseret {
+            input: [[$($group:tt)*] $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [[$($group)*] $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseret],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parseret {
+            input: [{$($group:tt)*} $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [{$($group)*} $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@parseret],
+                    args: [$($state)*],
+                ],
             }
         }
     };
     (
-        @parseparamarg {
-            input: [$first:tt$($rest:tt)*],
+        @parseret {
+            input: [@decoded $tok:tt $($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: [$($rt:tt)*],
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
-            arg: [$($argtok:tt)*],
-            type: $($type:ty)?,
         }
     ) => {
         $crate::trait_xml_parse_assoc_fn! {
-            @parseparamarg {
+            @parseret {
                 input: [$($rest)*],
                 name: $($name)?,
                 qualifiers: $qt,
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: [$($rt)* $tok],
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
-                arg: [$($argtok)* $first],
-                type: $($type)?,
             }
         }
     };
-
-    // Parse return type
-    (
-        @parse {
-            input: [<ret>$($rest:tt)*],
-            name: $($name:ident)?,
-            qualifiers: $qt:tt,
-            gparams: $gpt:tt,
-            params: $pt:tt,
-            hasparams: $hpt:tt,
-            ret: $rt:ty,
-            where: $wt:tt,
-            def: $dt:tt,
-            hasdef: $hdt:tt,
-            callback: [
-                name: $callback:path,
-                rule: $ruletoks:tt,
-                args: $argstoks:tt,
-            ],
-        }
-    ) => {
-        compile_error!(concat!(
-            "error parsing associated function: return type already defined as `",
-            stringify!($rt),
-            "`, but encountered another `<ret>` tag. caller: `",
-            stringify!($callback),
-            "`",
-        ));
-    };
     (
-        @parse {
-            input: [<ret>$($rest:tt)*],
+        @parseret {
+            input: [$first:tt$($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: ,
+            ret: [$($rt:tt)*],
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -1402,26 +2671,32 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: [],
+                ret: [$($rt)* $first],
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
     };
+
+    // Parse `where` clause
     (
-        @parseret {
-            input: [</ret>$($rest:tt)*],
+        @parse {
+            input: [<where>$($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [],
-            where: $wt:tt,
+            ret: $rettoks:tt,
+            where: $(empty)? $([$($wt:tt)+])?,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1430,164 +2705,215 @@ macro_rules! trait_xml_parse_assoc_fn {
         }
     ) => {
         compile_error!(concat!(
-            "error parsing associated function return type: empty return type. caller: `",
+            "error parsing associated function: encountered multiple `where` clauses. caller: `",
             stringify!($callback),
             "`",
         ));
     };
     (
-        @parseret {
-            input: [</ret>$($rest:tt)*],
+        @parse {
+            input: [<where>$($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$rt:ty],
-            where: $wt:tt,
+            ret: $rettoks:tt,
+            where: [],
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
-        $crate::trait_xml_parse_assoc_fn! {
+        $crate::trait_xml_parse_where! {
             @parse {
                 input: [$($rest)*],
-                name: $($name)?,
-                qualifiers: $qt,
-                gparams: $gpt,
-                params: $pt,
-                hasparams: $hpt,
-                ret: $rt,
-                where: $wt,
-                def: $dt,
-                hasdef: $hdt,
-                callback: $callbacktoks,
+                clauses: [],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@wherecallback],
+                    args: [
+                        name: [$($name)?],
+                        qualifiers: $qt,
+                        gparams: $gpt,
+                        params: $pt,
+                        hasparams: $hpt,
+                        ret: $rettoks,
+                        def: $dt,
+                        hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
+                        callback: $callbacktoks,
+                    ],
+                ],
             }
         }
     };
+
+    // Parse examples
     (
-        @parseret {
-            input: [</ret>$($rest:tt)*],
+        @parse {
+            input: [<example>$($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$($rtinv:tt)+],
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
-            callback: [
-                name: $callback:path,
-                rule: $ruletoks:tt,
-                args: $argstoks:tt,
-            ],
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
         }
     ) => {
-        compile_error!(concat!(
-            "error parsing associated function return type: input forms invalid type `",
-            $(stringify!($rtinv),)+
-            "`. caller: `",
-            stringify!($callback),
-            "`",
-        ));
+        $crate::trait_xml_parse_example! {
+            @parse {
+                input: [$($rest)*],
+                test: ,
+                code: [],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_fn,
+                    rule: [@examplecallback],
+                    args: [
+                        name: [$($name)?],
+                        qualifiers: $qt,
+                        gparams: $gpt,
+                        params: $pt,
+                        hasparams: $hpt,
+                        ret: $rettoks,
+                        where: $wt,
+                        def: $dt,
+                        hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
     };
     (
-        @parseret {
-            input: [$first:tt$($rest:tt)*],
-            name: $($name:ident)?,
+        @examplecallback {
+            input: $inputtoks:tt,
+            name: [$($name:ident)?],
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$($rt:tt)*],
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: [$($ext:tt)*],
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
+            example: $exampletoks:tt,
         }
     ) => {
         $crate::trait_xml_parse_assoc_fn! {
-            @parseret {
-                input: [$($rest)*],
+            @parse {
+                input: $inputtoks,
                 name: $($name)?,
                 qualifiers: $qt,
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: [$($rt)* $first],
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: [$($ext)* $exampletoks],
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
     };
 
-    // Parse `where` clause
-    (
-        @parse {
-            input: [<where>$($rest:tt)*],
-            name: $($name:ident)?,
-            qualifiers: $qt:tt,
-            gparams: $gpt:tt,
-            params: $pt:tt,
-            hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
-            where: [$($wt:tt)+],
-            def: $dt:tt,
-            hasdef: $hdt:tt,
-            callback: [
-                name: $callback:path,
-                rule: $ruletoks:tt,
-                args: $argstoks:tt,
-            ],
-        }
-    ) => {
-        compile_error!(concat!(
-            "error parsing associated function: encountered multiple `where` clauses. caller: `",
-            stringify!($callback),
-            "`",
-        ));
-    };
+    // Namespaced tags are kept for the trait to hand to its namespace handler
     (
         @parse {
-            input: [<where>$($rest:tt)*],
+            input: [<$ns:ident:$tag:ident $($rest:tt)*],
             name: $($name:ident)?,
             qualifiers: $qt:tt,
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
-            where: [],
+            ret: $rettoks:tt,
+            where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
-        $crate::trait_xml_parse_where! {
+        $crate::trait_xml_parse_ns_tag! {
             @parse {
+                ns: $ns,
+                tag: $tag,
                 input: [$($rest)*],
-                clauses: [],
+                attrs: [],
                 callback: [
                     name: $crate::trait_xml_parse_assoc_fn,
-                    rule: [@wherecallback],
+                    rule: [@nstagcallback],
                     args: [
                         name: [$($name)?],
                         qualifiers: $qt,
                         gparams: $gpt,
                         params: $pt,
                         hasparams: $hpt,
-                        ret: [$($rt)?],
+                        ret: $rettoks,
+                        where: $wt,
                         def: $dt,
                         hasdef: $hdt,
+                        examples: $ext,
+                        nstags: $nst,
                         callback: $callbacktoks,
                     ],
                 ],
             }
         }
     };
+    (
+        @nstagcallback {
+            input: $inputtoks:tt,
+            name: [$($name:ident)?],
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: [$($nst:tt)*],
+            callback: $callbacktoks:tt,
+            nstag: $nstagtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parse {
+                input: $inputtoks,
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: [$($nst)* $nstagtoks],
+                callback: $callbacktoks,
+            }
+        }
+    };
 
     // Parse default function defs
     (
@@ -1598,10 +2924,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: [[]],
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1623,10 +2951,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: [],
             hasdef: [],
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -1638,10 +2968,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: [],
                 hasdef: [[]],
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -1654,10 +2986,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -1669,10 +3003,48 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    // CDATA sections are passed through without looking inside
+    (
+        @parserust {
+            input: [<![CDATA[$($cdata:tt)*]]>$($rest:tt)*],
+            name: $($name:ident)?,
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: [$($dt:tt)*],
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parserust {
+                input: [$($rest)*],
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
+                where: $wt,
+                def: [$($dt)* $($cdata)*],
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -1685,10 +3057,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: [$($dt:tt)*],
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
         }
     ) => {
@@ -1700,10 +3074,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: [$($dt)* $first],
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -1716,10 +3092,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: $($rt:ty)?,
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: [
                 name: $callback:path,
                 rule: $ruletoks:tt,
@@ -1742,10 +3120,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$($rt:ty)?],
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             name: $name:ident,
         }
@@ -1758,10 +3138,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -1773,10 +3155,12 @@ macro_rules! trait_xml_parse_assoc_fn {
             qualifiers: $qt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$($rt:ty)?],
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             gparams: $gpt:tt,
         }
@@ -1789,10 +3173,47 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
+                where: $wt,
+                def: $dt,
+                hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @paramattrscallback {
+            input: $inputtoks:tt,
+            name: [$($name:ident)?],
+            qualifiers: $qt:tt,
+            gparams: $gpt:tt,
+            params: $pt:tt,
+            hasparams: $hpt:tt,
+            ret: $rettoks:tt,
+            where: $wt:tt,
+            def: $dt:tt,
+            hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_assoc_fn! {
+            @parseparams {
+                input: $inputtoks,
+                name: $($name)?,
+                qualifiers: $qt,
+                gparams: $gpt,
+                params: $pt,
+                hasparams: $hpt,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
@@ -1805,12 +3226,17 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$($rt:ty)?],
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             type: [$($type:ty)?],
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
             name: $paramname:ident,
         }
     ) => {
@@ -1822,13 +3248,18 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
                 arg: [$paramname],
                 type: $($type)?,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: $pattrstoks,
             }
         }
     };
@@ -1840,12 +3271,17 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$($rt:ty)?],
+            ret: $rettoks:tt,
             where: $wt:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             arg: $argtoks:tt,
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            attrs: $pattrstoks:tt,
             type: $type:ty,
         }
     ) => {
@@ -1857,13 +3293,18 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wt,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
                 arg: $argtoks,
                 type: $type,
+                recv: $recvtoks,
+                mut: $muttoks,
+                attrs: $pattrstoks,
             }
         }
     };
@@ -1875,9 +3316,11 @@ macro_rules! trait_xml_parse_assoc_fn {
             gparams: $gpt:tt,
             params: $pt:tt,
             hasparams: $hpt:tt,
-            ret: [$($rt:ty)?],
+            ret: $rettoks:tt,
             def: $dt:tt,
             hasdef: $hdt:tt,
+            examples: $ext:tt,
+            nstags: $nst:tt,
             callback: $callbacktoks:tt,
             where: $wheretoks:tt,
         }
@@ -1890,10 +3333,12 @@ macro_rules! trait_xml_parse_assoc_fn {
                 gparams: $gpt,
                 params: $pt,
                 hasparams: $hpt,
-                ret: $($rt)?,
+                ret: $rettoks,
                 where: $wheretoks,
                 def: $dt,
                 hasdef: $hdt,
+                examples: $ext,
+                nstags: $nst,
                 callback: $callbacktoks,
             }
         }
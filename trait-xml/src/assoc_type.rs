@@ -40,6 +40,24 @@
 /// ```
 #[macro_export]
 macro_rules! trait_xml_parse_assoc_type {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_assoc_type,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     // Empty input error
     (
         @parse {
@@ -91,6 +109,7 @@ macro_rules! trait_xml_parse_assoc_type {
                     $gpt
                     $boundstoks
                     $bwtoks
+                    []
                 ],
             }
         }
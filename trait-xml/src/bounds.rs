@@ -25,6 +25,24 @@
 /// }
 /// ```
 macro_rules! trait_xml_parse_bounds {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_bounds,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     // Empty input error
     (
         @parse {
@@ -0,0 +1,1314 @@
+//! `trait_xml!` for `build.rs`. Point [`generate`] at a directory of `.xml` specs and it writes
+//! the traits out as plain Rust, ready to be `include!`d:
+//! ```no_run
+//! // build.rs's main
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("traits.rs");
+//! trait_xml::build::generate("spec", out).unwrap();
+//! ```
+//! ```ignore
+//! // src/lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/traits.rs"));
+//! ```
+//! The spec files are real XML, so they get handled the same way `trait-xml-include` handles them:
+//! the `<?xml ...?>` declaration and comments get skipped, entities get decoded, and a bare `&` or
+//! `<` is an error. Errors come with the file, line and column.
+//!
+//! The generated code is what `trait_xml!` would have expanded to, which makes it a lot nicer to
+//! read, and it doesn't cost thousands of macro steps every time the crate builds. This covers:
+//! - `<name>`, `<vis>`, `<unsafe/>`, `<bounds>` and `<where>`
+//! - `<assoctype>`, `<assocconst>` and `<assocfn>`, with everything that can go in them
+//! - `<rust>` items
+//! - `<example>`s, on the trait or an `<assocfn>`, as doc comments plus a `#[cfg(test)]` module
+//!   for the ones with a `<test>`
+//! - `<sealed/>` and `<sealed-for>`
+//! - Attribute shorthand on `<trait>`, `<assocfn>` and `<param>`, like `<assocfn name=bar unsafe>`,
+//!   and since these are real XML files, the quoted `<assocfn name="bar" unsafe="unsafe">` too
+//!
+//! The tags that generate more than the trait itself aren't covered: `<cfg>`, `<extension>`,
+//! `<delegate>`, `<descriptor>`, `<mock>`, `<dyn-safe/>`, `<dyn-companion>`, `<enum-dispatch>`,
+//! `<ffi-vtable>`, `<alias/>`, `<closure-impl/>`, `<namespace/>` and namespaced tags like
+//! `<acme:metrics/>`. A spec with one of those in it is an error naming the tag, and that trait is
+//! one for `trait_xml!` or `trait-xml-include` instead.
+
+use std::fmt::{self, Display, Write as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Something wrong with a spec, or with reading or writing one.
+#[derive(Debug)]
+pub struct Error {
+    /// The spec it's in, if it came from a file.
+    pub path: Option<PathBuf>,
+    /// The line it's on, starting from 1. 0 if it isn't about any particular spot.
+    pub line: usize,
+    /// The column it's at, starting from 1. 0 if it isn't about any particular spot.
+    pub column: usize,
+    /// What's wrong.
+    pub message: String,
+}
+
+impl Error {
+    fn io(path: &Path, err: std::io::Error) -> Self {
+        Error {
+            path: Some(path.to_owned()),
+            line: 0,
+            column: 0,
+            message: err.to_string(),
+        }
+    }
+
+    fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error {
+            path: None,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.path, self.line) {
+            (Some(path), 0) => write!(f, "{}: {}", path.display(), self.message),
+            (Some(path), line) => {
+                write!(
+                    f,
+                    "{}:{line}:{}: {}",
+                    path.display(),
+                    self.column,
+                    self.message
+                )
+            }
+            (None, 0) => f.write_str(&self.message),
+            (None, line) => write!(f, "{line}:{}: {}", self.column, self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Writes every `*.xml` spec in `input_dir` out to `out_file` as Rust, in file name order, and
+/// prints the `cargo:rerun-if-changed` lines for the directory and each spec.
+pub fn generate(input_dir: impl AsRef<Path>, out_file: impl AsRef<Path>) -> Result<(), Error> {
+    let input_dir = input_dir.as_ref();
+    let mut paths = fs::read_dir(input_dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|err| Error::io(input_dir, err))?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "xml"));
+    paths.sort();
+
+    println!("cargo:rerun-if-changed={}", input_dir.display());
+    let mut output = String::new();
+    for path in &paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let source = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+        let generated = to_rust(&source).map_err(|err| Error {
+            path: Some(path.clone()),
+            ..err
+        })?;
+
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        let _ = writeln!(
+            output,
+            "// Generated from {} by trait_xml::build - edit that instead.",
+            path.display(),
+        );
+        output.push_str(&generated);
+    }
+
+    let out_file = out_file.as_ref();
+    fs::write(out_file, output).map_err(|err| Error::io(out_file, err))
+}
+
+/// Turns one spec into Rust source. See the [module docs](self) for what comes out.
+pub fn to_rust(source: &str) -> Result<String, Error> {
+    trait_def(&tree(scan(source)?)?)
+}
+
+/// Turns a spec into the tokens `trait_xml!` takes. That's most of the way there already, so this
+/// just gets rid of the parts of the file that aren't tokens, like the declaration and comments,
+/// decodes entities, and takes the quotes off attribute values `trait_xml!` can't take quoted.
+/// CDATA sections are left for `trait_xml!`.
+pub fn to_tokens(source: &str) -> Result<String, Error> {
+    let mut output = String::with_capacity(source.len());
+    for event in scan(source)? {
+        match event {
+            Event::Start {
+                name, attrs, empty, ..
+            } if MACRO_ATTR_TAGS.contains(&name) => {
+                output.push('<');
+                output.push_str(name);
+                for (attr, value) in split_attrs(&attrs) {
+                    let _ = match value.as_deref() {
+                        Some(quoted) if quoted.starts_with('"') && attr != "extern" => {
+                            match unquote(&attr, quoted) {
+                                Some(value) => write!(output, " {attr}={{{value}}}"),
+                                None => write!(output, " {attr}"),
+                            }
+                        }
+                        Some(value) => write!(output, " {attr}={value}"),
+                        None => write!(output, " {attr}"),
+                    };
+                }
+                output.push_str(if empty { "/>" } else { ">" });
+            }
+            Event::Start { raw, .. } | Event::End { raw } | Event::Text(raw) => {
+                output.push_str(&raw)
+            }
+            Event::Cdata(cdata) => output.push_str(cdata),
+            Event::Skipped => output.push(' '),
+        }
+    }
+    Ok(output)
+}
+
+/*
+    Reading the XML
+*/
+
+enum Event<'a> {
+    Start {
+        name: &'a str,
+        attrs: String,
+        empty: bool,
+        raw: String,
+        line: usize,
+        column: usize,
+    },
+    End {
+        raw: String,
+    },
+    Text(String),
+    /// The whole section, `<![CDATA[` and `]]>` included.
+    Cdata(&'a str),
+    /// A comment or `<?...?>`.
+    Skipped,
+}
+
+/// Walks the file a character at a time, keeping track of where it is for errors.
+struct Reader<'a> {
+    rest: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn advance(&mut self, bytes: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(bytes);
+        for c in taken.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.rest = rest;
+        taken
+    }
+
+    /// Takes everything up to and including `end`, or errors with `message` at where it started.
+    fn take_until(&mut self, end: &str, message: &str) -> Result<&'a str, Error> {
+        match self.rest.find(end) {
+            Some(at) => Ok(self.advance(at + end.len())),
+            None => Err(self.error(message)),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::at(self.line, self.column, message)
+    }
+}
+
+/// Splits the file up, checking that tags match up and decoding entities on the way.
+fn scan(source: &str) -> Result<Vec<Event<'_>>, Error> {
+    let mut reader = Reader {
+        rest: source.strip_prefix('\u{feff}').unwrap_or(source),
+        line: 1,
+        column: 1,
+    };
+    let mut events = Vec::new();
+    let mut open: Vec<(&str, usize, usize)> = Vec::new();
+
+    while !reader.rest.is_empty() {
+        let (line, column) = (reader.line, reader.column);
+        if reader.rest.starts_with("<?") {
+            reader.take_until("?>", "expected `?>` to close this `<?`")?;
+            events.push(Event::Skipped);
+        } else if reader.rest.starts_with("<!--") {
+            reader.take_until("-->", "expected `-->` to close this comment")?;
+            events.push(Event::Skipped);
+        } else if reader.rest.starts_with("<![CDATA[") {
+            let cdata = reader.take_until("]]>", "expected `]]>` to close this CDATA section")?;
+            events.push(Event::Cdata(cdata));
+        } else if reader.rest.starts_with("<!") {
+            return Err(reader.error("DTDs and other `<!` declarations aren't supported"));
+        } else if reader.rest.starts_with('<') {
+            let tag = take_tag(&mut reader)?;
+            let inside = tag[1..tag.len() - 1].trim_start_matches('/');
+            let name_len = inside.find(|c| !is_name_char(c)).unwrap_or(inside.len());
+            let (name, attrs) = inside.split_at(name_len);
+            let raw = decode_entities(tag, line, column)?;
+
+            if tag.starts_with("</") {
+                match open.pop() {
+                    Some((open_name, _, _)) if open_name == name => {}
+                    Some((open_name, open_line, open_column)) => {
+                        return Err(Error::at(
+                            line,
+                            column,
+                            format!(
+                                "expected </{open_name}> (opened at {open_line}:{open_column}), \
+                                 found </{name}>",
+                            ),
+                        ));
+                    }
+                    None => {
+                        return Err(Error::at(
+                            line,
+                            column,
+                            format!("found </{name}> without a matching <{name}>"),
+                        ));
+                    }
+                }
+                events.push(Event::End { raw });
+            } else {
+                let empty = attrs.ends_with('/');
+                if !empty {
+                    open.push((name, line, column));
+                }
+                events.push(Event::Start {
+                    name,
+                    attrs: decode_entities(attrs.trim_end_matches('/'), line, column)?,
+                    empty,
+                    raw,
+                    line,
+                    column,
+                });
+            }
+        } else {
+            let len = reader.rest.find('<').unwrap_or(reader.rest.len());
+            let text = reader.advance(len);
+            events.push(Event::Text(decode_entities(text, line, column)?));
+        }
+    }
+
+    match open.pop() {
+        Some((name, line, column)) => Err(Error::at(
+            line,
+            column,
+            format!(
+                "<{name}> is never closed - if it's not meant to be a tag, write `&lt;` instead \
+                 of `<`",
+            ),
+        )),
+        None => Ok(events),
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')
+}
+
+/// Takes a start or end tag, attributes and all.
+fn take_tag<'a>(reader: &mut Reader<'a>) -> Result<&'a str, Error> {
+    let after = reader.rest.trim_start_matches(['<', '/']);
+    if !after.starts_with(|c: char| is_name_char(c) && !c.is_ascii_digit()) {
+        return Err(
+            reader.error("expected a tag name after `<` - write `&lt;` for a less-than sign")
+        );
+    }
+
+    // `>`s inside quoted attribute values don't end the tag
+    let mut quoted = false;
+    for (at, c) in reader.rest.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '>' if !quoted => return Ok(reader.advance(at + 1)),
+            _ => {}
+        }
+    }
+    Err(reader.error("expected `>` to close this tag"))
+}
+
+/// Decodes any entities in `text`. `line` and `column` are where `text` starts, so errors can
+/// point at the entity itself.
+fn decode_entities(text: &str, line: usize, column: usize) -> Result<String, Error> {
+    let mut reader = Reader {
+        rest: text,
+        line,
+        column,
+    };
+    let mut output = String::with_capacity(text.len());
+
+    while let Some(at) = reader.rest.find('&') {
+        output.push_str(reader.advance(at));
+        let Some(end) = reader.rest.find(';').filter(|&end| {
+            reader.rest[1..end]
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '#')
+        }) else {
+            return Err(reader.error("bare `&` - write `&amp;` instead"));
+        };
+
+        let name = &reader.rest[1..end];
+        let decoded = match name {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => name
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| name.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => output.push(c),
+            None => return Err(reader.error(format!("unknown entity `&{name};`"))),
+        }
+        reader.advance(end + 1);
+    }
+    output.push_str(reader.rest);
+    Ok(output)
+}
+
+struct Element<'a> {
+    name: &'a str,
+    /// Attributes that couldn't be turned into child elements, which only `trait_xml!` knows what
+    /// to do with.
+    attrs: Vec<(String, Option<String>)>,
+    /// Whether it was written `<name/>`.
+    empty: bool,
+    children: Vec<Node<'a>>,
+    line: usize,
+    column: usize,
+}
+
+enum Node<'a> {
+    Element(Element<'a>),
+    Text(String),
+}
+
+fn tree(events: Vec<Event<'_>>) -> Result<Element<'_>, Error> {
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+
+    for event in events {
+        let finished = match event {
+            Event::Start {
+                name,
+                attrs,
+                empty,
+                line,
+                column,
+                ..
+            } => {
+                let (children, attrs) = match name {
+                    "trait" | "assocfn" | "param" => attr_children(&attrs, line, column),
+                    _ => (Vec::new(), split_attrs(&attrs)),
+                };
+                let element = Element {
+                    name,
+                    attrs,
+                    empty,
+                    children,
+                    line,
+                    column,
+                };
+                if !empty {
+                    stack.push(element);
+                    continue;
+                }
+                element
+            }
+            Event::End { .. } => stack.pop().unwrap(),
+            Event::Text(text) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(Node::Text(text));
+                }
+                continue;
+            }
+            Event::Cdata(cdata) => {
+                if let Some(parent) = stack.last_mut() {
+                    let text = &cdata["<![CDATA[".len()..cdata.len() - "]]>".len()];
+                    parent.children.push(Node::Text(text.into()));
+                }
+                continue;
+            }
+            Event::Skipped => continue,
+        };
+
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(Node::Element(finished)),
+            None if root.is_none() => root = Some(finished),
+            None => {
+                return Err(Error::at(
+                    finished.line,
+                    finished.column,
+                    "only one trait per spec",
+                ))
+            }
+        }
+    }
+
+    match root {
+        Some(root) if root.name == "trait" => Ok(root),
+        Some(root) => Err(Error::at(
+            root.line,
+            root.column,
+            format!("expected <trait>, found <{}>", root.name),
+        )),
+        None => Err(Error::at(1, 1, "expected a <trait>")),
+    }
+}
+
+/// Bare attributes have no value. Values keep their quotes, since `extern="C"` wants them.
+fn split_attrs(attrs: &str) -> Vec<(String, Option<String>)> {
+    let mut split = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut depth = 0usize;
+    for c in attrs.chars().chain([' ']) {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            '(' | '[' | '{' if !quoted => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' | ']' | '}' if !quoted => {
+                depth = depth.saturating_sub(1);
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted && depth == 0 => {
+                if !current.is_empty() {
+                    split.push(match current.split_once('=') {
+                        Some((name, value)) => (name.into(), Some(value.into())),
+                        None => (current.clone(), None),
+                    });
+                    current.clear();
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    split
+}
+
+/// Tags that can be given as attributes instead, on the tags `trait_xml!` takes attributes on.
+const ATTR_TAGS: &[&str] = &[
+    "name", "vis", "unsafe", "sealed", "extern", "ret", "pat", "type", "mut",
+];
+
+/// The ones of those that are written as bare attributes, or `flag="flag"`.
+const FLAG_TAGS: &[&str] = &["unsafe", "sealed", "mut"];
+
+/// Every tag `trait_xml!` takes attributes on.
+const MACRO_ATTR_TAGS: &[&str] = &[
+    "trait",
+    "assocfn",
+    "param",
+    "mock",
+    "dyn-companion",
+    "enum-dispatch",
+    "ffi-vtable",
+    "namespace",
+];
+
+/// The value inside a quoted attribute other than `extern`, or `None` for a `flag="flag"`.
+fn unquote<'a>(attr: &str, quoted: &'a str) -> Option<&'a str> {
+    match quoted.trim_matches('"') {
+        flag if flag == attr && FLAG_TAGS.contains(&flag) => None,
+        value => Some(value),
+    }
+}
+
+/// Turns attributes back into the child elements they're short for, same as `trait_xml!` does:
+/// `flag` is `<flag/>`, `attr=value` and `attr={value}` are `<attr>value</attr>`, and
+/// `extern="ABI"` is `<extern>"ABI"</extern>`. Being real XML, values can have quotes too, which
+/// `trait_xml!` can't take out: `attr="value"` is `<attr>value</attr>`, and `flag="flag"` is
+/// `<flag/>`. Anything else is left as an attribute.
+fn attr_children<'a>(
+    attrs: &str,
+    line: usize,
+    column: usize,
+) -> (Vec<Node<'a>>, Vec<(String, Option<String>)>) {
+    let mut children = Vec::new();
+    let mut left = Vec::new();
+    for (attr, value) in split_attrs(attrs) {
+        let Some(name) = ATTR_TAGS.iter().find(|tag| **tag == attr) else {
+            left.push((attr, value));
+            continue;
+        };
+        let text = match value.as_deref() {
+            None => None,
+            Some(quoted) if quoted.starts_with('"') && attr != "extern" => {
+                unquote(&attr, quoted).map(str::to_owned)
+            }
+            Some(value) => Some(
+                value
+                    .strip_prefix('{')
+                    .and_then(|value| value.strip_suffix('}'))
+                    .unwrap_or(value)
+                    .to_owned(),
+            ),
+        };
+        children.push(Node::Element(Element {
+            name,
+            attrs: Vec::new(),
+            empty: text.is_none(),
+            children: text.into_iter().map(Node::Text).collect(),
+            line,
+            column,
+        }));
+    }
+    (children, left)
+}
+
+/*
+    Writing the Rust
+*/
+
+/// Tags only `trait_xml!` knows how to expand.
+const MACRO_ONLY_TAGS: &[&str] = &[
+    "cfg",
+    "extension",
+    "delegate",
+    "descriptor",
+    "mock",
+    "dyn-safe",
+    "dyn-companion",
+    "enum-dispatch",
+    "ffi-vtable",
+    "alias",
+    "closure-impl",
+    "namespace",
+];
+
+impl Element<'_> {
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::at(self.line, self.column, message)
+    }
+
+    /// For a child that doesn't belong in `parent`.
+    fn unexpected_in(&self, parent: &Element) -> Error {
+        match MACRO_ONLY_TAGS.contains(&self.name) || self.name.contains(':') {
+            true => self.error(format!(
+                "<{}> isn't supported by trait_xml::build - use trait_xml! for this trait",
+                self.name,
+            )),
+            false => self.error(format!("<{}> doesn't go in <{}>", self.name, parent.name)),
+        }
+    }
+
+    /// Attributes that are still attributes aren't allowed anywhere.
+    fn no_attrs(&self) -> Result<(), Error> {
+        match self.attrs.first() {
+            None => Ok(()),
+            Some((attr, _)) => {
+                Err(self.error(format!("unknown attribute `{attr}` on <{}>", self.name)))
+            }
+        }
+    }
+
+    /// Child elements. Text between them is only allowed to be whitespace, and the elements
+    /// aren't allowed any attributes that are still attributes.
+    fn elements(&self) -> Result<impl Iterator<Item = &Element<'_>>, Error> {
+        for child in &self.children {
+            match child {
+                Node::Text(text) if !text.trim().is_empty() => {
+                    return Err(self.error(format!(
+                        "expected tags inside <{}>, found `{}`",
+                        self.name,
+                        text.trim(),
+                    )))
+                }
+                Node::Element(element) if !element.attrs.is_empty() => {
+                    return Err(match MACRO_ONLY_TAGS.contains(&element.name) {
+                        true => element.unexpected_in(self),
+                        false => element.no_attrs().unwrap_err(),
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        }))
+    }
+
+    /// The element's text, as written.
+    fn text(&self) -> Result<String, Error> {
+        let mut text = String::new();
+        for child in &self.children {
+            match child {
+                Node::Text(part) => text.push_str(part),
+                Node::Element(child) => {
+                    return Err(child.error(format!(
+                        "expected text inside <{}>, found <{}>",
+                        self.name, child.name,
+                    )))
+                }
+            }
+        }
+        Ok(text)
+    }
+
+    /// The element's text on one line, for types, names and the like.
+    fn line_text(&self) -> Result<String, Error> {
+        let text = self
+            .text()?
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if text.is_empty() {
+            return Err(self.error(format!("<{}></{0}> can't be empty", self.name)));
+        }
+        Ok(text)
+    }
+
+    fn ident(&self) -> Result<String, Error> {
+        let text = self.line_text()?;
+        let is_ident = text
+            .strip_prefix("r#")
+            .unwrap_or(&text)
+            .chars()
+            .enumerate()
+            .all(|(i, c)| c == '_' || c.is_alphabetic() || (i > 0 && c.is_alphanumeric()));
+        match is_ident {
+            true => Ok(text),
+            false => Err(self.error(format!("expected an identifier, found `{text}`"))),
+        }
+    }
+
+    fn lifetime(&self) -> Result<String, Error> {
+        let text = self.line_text()?;
+        match text.starts_with('\'') {
+            true => Ok(text),
+            false => Err(self.error(format!("expected a lifetime, found `{text}`"))),
+        }
+    }
+
+    /// Checks the element has no children, for flags like `<unsafe/>`.
+    fn flag(&self) -> Result<(), Error> {
+        match self
+            .children
+            .iter()
+            .all(|child| matches!(child, Node::Text(text) if text.trim().is_empty()))
+        {
+            true => Ok(()),
+            false => Err(self.error(format!("<{}/> can't have anything inside it", self.name))),
+        }
+    }
+}
+
+/// A `<vis>`, which has to be one `trait_xml!` would take as a `vis`: `pub`, or `pub` with
+/// `crate`, `self`, `super` or `in path` in brackets.
+fn visibility(element: &Element) -> Result<String, Error> {
+    let text = element.line_text()?;
+    let restriction = text.strip_prefix("pub").map(str::trim_start).map(|rest| {
+        rest.strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+    });
+    let valid = match restriction {
+        Some(None) => text == "pub",
+        Some(Some(inside)) => match inside.trim() {
+            "crate" | "self" | "super" => true,
+            inside => inside
+                .strip_prefix("in ")
+                .is_some_and(|path| !path.trim().is_empty()),
+        },
+        None => false,
+    };
+    match valid {
+        true => Ok(text),
+        false => Err(element.error(format!(
+            "expected a visibility like `pub` or `pub(crate)`, found `{text}`"
+        ))),
+    }
+}
+
+/// Stores `value` in `slot`, unless something's already there.
+fn once<T>(slot: &mut Option<T>, element: &Element, value: T) -> Result<(), Error> {
+    match slot.replace(value) {
+        None => Ok(()),
+        Some(_) => Err(element.error(format!("only one <{}> allowed here", element.name))),
+    }
+}
+
+fn trait_def(root: &Element) -> Result<String, Error> {
+    let mut is_unsafe = None;
+    let mut name = None;
+    let mut vis = None;
+    let mut generics = None;
+    let mut clauses = None;
+    let mut sealed = None;
+    let mut sealed_for = Vec::new();
+    let mut docs = String::new();
+    let mut types = Vec::new();
+    let mut consts = Vec::new();
+    let mut fns = Vec::new();
+    let mut items = Vec::new();
+    let mut tests = Vec::new();
+
+    root.no_attrs()?;
+    for child in root.elements()? {
+        match child.name {
+            "name" => once(&mut name, child, child.ident()?)?,
+            "vis" => once(&mut vis, child, visibility(child)?)?,
+            "unsafe" => {
+                child.flag()?;
+                once(&mut is_unsafe, child, ())?;
+            }
+            "sealed" => {
+                child.flag()?;
+                once(&mut sealed, child, ())?;
+            }
+            "sealed-for" => sealed_for.extend(sealed_types(child)?),
+            "example" => example(child, "", &mut docs, &mut tests)?,
+            "bounds" if child.empty => once(&mut generics, child, Default::default())?,
+            "bounds" => once(&mut generics, child, gparams(child, true)?)?,
+            "where" if child.empty => once(&mut clauses, child, Vec::new())?,
+            "where" => once(&mut clauses, child, where_clauses(child)?)?,
+            "assoctype" => types.push(assoc_type(child)?),
+            "assocconst" => consts.push(assoc_const(child)?),
+            "assocfn" => fns.push(assoc_fn(child, &mut tests)?),
+            "rust" => items.push(reindent(&child.text()?, "    ")),
+            _ => return Err(child.unexpected_in(root)),
+        }
+    }
+
+    let name = name.ok_or_else(|| root.error("a <trait> needs a <name>"))?;
+    let (params, mut reqs) = generics.unwrap_or_default();
+    if sealed.is_some() {
+        reqs.insert(0, "__sealed::Sealed".into());
+    }
+
+    let mut output = docs;
+    if let Some(vis) = vis {
+        output.push_str(&vis);
+        output.push(' ');
+    }
+    if is_unsafe.is_some() {
+        output.push_str("unsafe ");
+    }
+    output.push_str("trait ");
+    output.push_str(&name);
+    push_gparams(&mut output, &params);
+    if !reqs.is_empty() {
+        output.push_str(": ");
+        output.push_str(&reqs.join(" + "));
+    }
+    push_where(&mut output, &clauses.unwrap_or_default(), "", ",\n");
+
+    let body: Vec<String> = types
+        .into_iter()
+        .chain(consts)
+        .chain(fns)
+        .chain(items)
+        .collect();
+    match (body.is_empty(), output.ends_with('\n')) {
+        (true, true) => output.push_str("{}\n"),
+        (true, false) => output.push_str(" {}\n"),
+        (false, ends_with_where) => {
+            output.push_str(if ends_with_where { "{\n" } else { " {\n" });
+            output.push_str(&body.join("\n"));
+            output.push_str("}\n");
+        }
+    }
+
+    if sealed.is_some() {
+        output.push_str("\nmod __sealed {\n    pub trait Sealed {}\n}\n");
+    }
+    if !sealed_for.is_empty() {
+        output.push('\n');
+        for ty in sealed_for {
+            let _ = writeln!(output, "impl __sealed::Sealed for {ty} {{}}");
+        }
+    }
+    for test in tests {
+        output.push('\n');
+        output.push_str(&test);
+    }
+    Ok(output)
+}
+
+/// The types in a `<sealed-for>`.
+fn sealed_types(element: &Element) -> Result<Vec<String>, Error> {
+    let mut types = Vec::new();
+    for child in element.elements()? {
+        match child.name {
+            "type" => types.push(child.line_text()?),
+            _ => return Err(child.unexpected_in(element)),
+        }
+    }
+    if types.is_empty() {
+        return Err(element.error("a <sealed-for> needs at least one <type>"));
+    }
+    Ok(types)
+}
+
+/// Adds an `<example>` to `docs` as a code block, and to `tests` as a `#[cfg(test)]` module if it
+/// has a `<test>` name.
+fn example(
+    element: &Element,
+    indent: &str,
+    docs: &mut String,
+    tests: &mut Vec<String>,
+) -> Result<(), Error> {
+    let mut test = None;
+    let mut code = String::new();
+    for child in &element.children {
+        match child {
+            Node::Element(child)
+                if child.name == "test" && child.attrs.is_empty() && code.trim().is_empty() =>
+            {
+                once(&mut test, child, child.ident()?)?
+            }
+            Node::Element(child) => {
+                return Err(child.error(format!(
+                    "<{}> doesn't go in <example> - only a <test>, before the code",
+                    child.name,
+                )))
+            }
+            Node::Text(text) => code.push_str(text),
+        }
+    }
+    if code.trim().is_empty() {
+        return Err(element.error("an <example> needs some code"));
+    }
+
+    let _ = writeln!(docs, "{indent}/// ```");
+    for line in reindent(&code, "").lines() {
+        let _ = match line.is_empty() {
+            true => writeln!(docs, "{indent}///"),
+            false => writeln!(docs, "{indent}/// {line}"),
+        };
+    }
+    let _ = writeln!(docs, "{indent}/// ```");
+
+    if let Some(test) = test {
+        tests.push(format!(
+            "#[cfg(test)]\nmod {test} {{\n    #[allow(unused_imports)]\n    use super::*;\n\n    \
+             #[test]\n    fn example() {{\n{}    }}\n}}\n",
+            reindent(&code, "        "),
+        ));
+    }
+    Ok(())
+}
+
+/// Generic parameters and supertraits (or bounds, for associated types) from a `<bounds>` or
+/// `<gparams>`. Lifetimes come first, then types, then consts, same as `trait_xml!` puts them.
+fn gparams(element: &Element, allow_reqs: bool) -> Result<(Vec<String>, Vec<String>), Error> {
+    let mut lifetimes = Vec::new();
+    let mut types = Vec::new();
+    let mut consts = Vec::new();
+    let mut reqs = Vec::new();
+
+    for child in element.elements()? {
+        match child.name {
+            "lifetime" => {
+                let mut name = None;
+                let mut bounds = Vec::new();
+                for grandchild in child.elements()? {
+                    match grandchild.name {
+                        "name" => once(&mut name, grandchild, grandchild.lifetime()?)?,
+                        "lifetime-bound" => bounds.push(grandchild.lifetime()?),
+                        _ => return Err(grandchild.unexpected_in(child)),
+                    }
+                }
+                let name = name.ok_or_else(|| child.error("a <lifetime> needs a <name>"))?;
+                lifetimes.push(bounded(name, &bounds));
+            }
+            "type" => {
+                let mut name = None;
+                let mut bounds = Vec::new();
+                for grandchild in child.elements()? {
+                    match grandchild.name {
+                        "name" => once(&mut name, grandchild, grandchild.ident()?)?,
+                        _ => bounds.push(bound(grandchild)?),
+                    }
+                }
+                let name = name.ok_or_else(|| child.error("a <type> needs a <name>"))?;
+                types.push(bounded(name, &bounds));
+            }
+            "const" => {
+                let mut name = None;
+                let mut ty = None;
+                for grandchild in child.elements()? {
+                    match grandchild.name {
+                        "name" => once(&mut name, grandchild, grandchild.ident()?)?,
+                        "type" => once(&mut ty, grandchild, grandchild.line_text()?)?,
+                        _ => return Err(grandchild.unexpected_in(child)),
+                    }
+                }
+                let name = name.ok_or_else(|| child.error("a <const> needs a <name>"))?;
+                let ty = ty.ok_or_else(|| child.error("a <const> needs a <type>"))?;
+                consts.push(format!("const {name}: {ty}"));
+            }
+            "req" if allow_reqs => reqs.push(child.line_text()?),
+            _ => return Err(child.unexpected_in(element)),
+        }
+    }
+
+    Ok((
+        lifetimes.into_iter().chain(types).chain(consts).collect(),
+        reqs,
+    ))
+}
+
+/// A `<type-bound>`, `<lifetime-bound>` or `<for-bound>`.
+fn bound(element: &Element) -> Result<String, Error> {
+    match element.name {
+        "type-bound" => element.line_text(),
+        "lifetime-bound" => element.lifetime(),
+        "for-bound" => {
+            let mut lifetimes = Vec::new();
+            let mut bound = None;
+            for child in element.elements()? {
+                match child.name {
+                    "lifetime" => lifetimes.push(child.lifetime()?),
+                    "type-bound" => once(&mut bound, child, child.line_text()?)?,
+                    _ => return Err(child.unexpected_in(element)),
+                }
+            }
+            let bound = bound.ok_or_else(|| element.error("a <for-bound> needs a <type-bound>"))?;
+            if lifetimes.is_empty() {
+                return Err(element.error("a <for-bound> needs at least one <lifetime>"));
+            }
+            Ok(format!("for<{}> {bound}", lifetimes.join(", ")))
+        }
+        _ => Err(element.error(format!(
+            "expected a <type-bound>, <lifetime-bound> or <for-bound>, found <{}>",
+            element.name,
+        ))),
+    }
+}
+
+/// An `<assocfn>`'s `<gparams>`, which has to have something in it, unlike `<bounds>`.
+fn non_empty_gparams(element: &Element) -> Result<Vec<String>, Error> {
+    let params = gparams(element, false)?.0;
+    match params.is_empty() {
+        true => Err(element.error(
+            "<gparams></gparams> can't be empty - use <gparams/> for no generic parameters",
+        )),
+        false => Ok(params),
+    }
+}
+
+fn bounded(name: String, bounds: &[String]) -> String {
+    match bounds.is_empty() {
+        true => name,
+        false => format!("{name}: {}", bounds.join(" + ")),
+    }
+}
+
+fn where_clauses(element: &Element) -> Result<Vec<String>, Error> {
+    let clauses = element
+        .elements()?
+        .map(where_clause)
+        .collect::<Result<Vec<_>, _>>()?;
+    match clauses.is_empty() {
+        true => Err(element.error("<where></where> can't be empty - use <where/> for no clauses")),
+        false => Ok(clauses),
+    }
+}
+
+fn where_clause(element: &Element) -> Result<String, Error> {
+    match element.name {
+        "type-clause" => {
+            let mut ty = None;
+            let mut bounds = Vec::new();
+            for child in element.elements()? {
+                match child.name {
+                    "type" => once(&mut ty, child, child.line_text()?)?,
+                    _ => bounds.push(bound(child)?),
+                }
+            }
+            let ty = ty.ok_or_else(|| element.error("a <type-clause> needs a <type>"))?;
+            if bounds.is_empty() {
+                return Err(element.error(format!("`{ty}` in a <type-clause> needs bounds")));
+            }
+            Ok(format!("{ty}: {}", bounds.join(" + ")))
+        }
+        "lifetime-clause" => {
+            let mut lifetime = None;
+            let mut bounds = Vec::new();
+            for child in element.elements()? {
+                match child.name {
+                    "lifetime" => once(&mut lifetime, child, child.lifetime()?)?,
+                    "lifetime-bound" => bounds.push(child.lifetime()?),
+                    _ => return Err(child.unexpected_in(element)),
+                }
+            }
+            let lifetime =
+                lifetime.ok_or_else(|| element.error("a <lifetime-clause> needs a <lifetime>"))?;
+            if bounds.is_empty() {
+                return Err(
+                    element.error(format!("`{lifetime}` in a <lifetime-clause> needs bounds"))
+                );
+            }
+            Ok(format!("{lifetime}: {}", bounds.join(" + ")))
+        }
+        "for-clause" => {
+            let mut lifetimes = Vec::new();
+            let mut clause = None;
+            for child in element.elements()? {
+                match child.name {
+                    "lifetime" => lifetimes.push(child.lifetime()?),
+                    "type-clause" => once(&mut clause, child, where_clause(child)?)?,
+                    _ => return Err(child.unexpected_in(element)),
+                }
+            }
+            let clause =
+                clause.ok_or_else(|| element.error("a <for-clause> needs a <type-clause>"))?;
+            if lifetimes.is_empty() {
+                return Err(element.error("a <for-clause> needs at least one <lifetime>"));
+            }
+            Ok(format!("for<{}> {clause}", lifetimes.join(", ")))
+        }
+        _ => Err(element.error(format!(
+            "expected a <type-clause>, <lifetime-clause> or <for-clause>, found <{}>",
+            element.name,
+        ))),
+    }
+}
+
+fn assoc_type(element: &Element) -> Result<String, Error> {
+    let mut name = None;
+    let mut generics = None;
+    let mut clauses = None;
+    for child in element.elements()? {
+        match child.name {
+            "name" => once(&mut name, child, child.ident()?)?,
+            "bounds" | "where" if child.empty => {
+                return Err(child.error(format!("<{}/> doesn't go in <assoctype>", child.name)))
+            }
+            "bounds" => once(&mut generics, child, gparams(child, true)?)?,
+            "where" => once(&mut clauses, child, where_clauses(child)?)?,
+            _ => return Err(child.unexpected_in(element)),
+        }
+    }
+
+    let name = name.ok_or_else(|| element.error("an <assoctype> needs a <name>"))?;
+    let (params, bounds) = generics.unwrap_or_default();
+    let mut output = format!("    type {name}");
+    push_gparams(&mut output, &params);
+    if !bounds.is_empty() {
+        output.push_str(": ");
+        output.push_str(&bounds.join(" + "));
+    }
+    push_where(&mut output, &clauses.unwrap_or_default(), "    ", ",\n");
+    trim_last_comma(&mut output);
+    output.push_str(";\n");
+    Ok(output)
+}
+
+fn assoc_const(element: &Element) -> Result<String, Error> {
+    let mut name = None;
+    let mut ty = None;
+    let mut default = None;
+    for child in element.elements()? {
+        match child.name {
+            "name" => once(&mut name, child, child.ident()?)?,
+            "type" => once(&mut ty, child, child.line_text()?)?,
+            "default-value" => once(&mut default, child, child.line_text()?)?,
+            _ => return Err(child.unexpected_in(element)),
+        }
+    }
+
+    let name = name.ok_or_else(|| element.error("an <assocconst> needs a <name>"))?;
+    let ty = ty.ok_or_else(|| element.error("an <assocconst> needs a <type>"))?;
+    Ok(match default {
+        Some(default) => format!("    const {name}: {ty} = {default};\n"),
+        None => format!("    const {name}: {ty};\n"),
+    })
+}
+
+/// Any `<example>`s with a `<test>` name get their test modules added to `tests`.
+fn assoc_fn(element: &Element, tests: &mut Vec<String>) -> Result<String, Error> {
+    let mut docs = String::new();
+    let mut name = None;
+    let mut is_unsafe = None;
+    let mut abi = None;
+    let mut generics = None;
+    let mut params = None;
+    let mut ret = None;
+    let mut clauses = None;
+    let mut body = None;
+
+    for child in element.elements()? {
+        match child.name {
+            "name" => once(&mut name, child, child.ident()?)?,
+            "unsafe" => {
+                child.flag()?;
+                once(&mut is_unsafe, child, ())?;
+            }
+            "extern" => once(&mut abi, child, child.line_text()?)?,
+            "gparams" if child.empty => once(&mut generics, child, Vec::new())?,
+            "gparams" => once(&mut generics, child, non_empty_gparams(child)?)?,
+            "params" if child.empty => once(&mut params, child, Vec::new())?,
+            "params" => once(&mut params, child, fn_params(child)?)?,
+            "ret" => once(&mut ret, child, child.line_text()?)?,
+            "where" if child.empty => once(&mut clauses, child, Vec::new())?,
+            "where" => once(&mut clauses, child, where_clauses(child)?)?,
+            "rust" => once(&mut body, child, child.text()?)?,
+            "example" => example(child, "    ", &mut docs, tests)?,
+            _ => return Err(child.unexpected_in(element)),
+        }
+    }
+
+    let name = name.ok_or_else(|| element.error("an <assocfn> needs a <name>"))?;
+    let params = params.unwrap_or_default();
+    if body.is_none() && params.iter().any(|(_, is_mut)| *is_mut) {
+        return Err(element.error(format!(
+            "`{name}` has <mut/> parameters, which need a <rust></rust> body",
+        )));
+    }
+
+    let mut output = docs + "    ";
+    if is_unsafe.is_some() {
+        output.push_str("unsafe ");
+    }
+    if let Some(abi) = abi {
+        output.push_str("extern ");
+        output.push_str(&abi);
+        output.push(' ');
+    }
+    output.push_str("fn ");
+    output.push_str(&name);
+    push_gparams(&mut output, &generics.unwrap_or_default());
+    output.push('(');
+    let params: Vec<_> = params.into_iter().map(|(param, _)| param).collect();
+    output.push_str(&params.join(", "));
+    output.push(')');
+    if let Some(ret) = ret {
+        output.push_str(" -> ");
+        output.push_str(&ret);
+    }
+    let clauses = clauses.unwrap_or_default();
+    push_where(&mut output, &clauses, "    ", ",\n");
+
+    match body {
+        Some(body) => {
+            output.push_str(if clauses.is_empty() {
+                " {\n"
+            } else {
+                "    {\n"
+            });
+            output.push_str(&reindent(&body, "        "));
+            output.push_str("    }\n");
+        }
+        None => {
+            trim_last_comma(&mut output);
+            output.push_str(";\n");
+        }
+    }
+    Ok(output)
+}
+
+/// Each parameter, and whether it's `mut`.
+fn fn_params(element: &Element) -> Result<Vec<(String, bool)>, Error> {
+    let mut params = Vec::new();
+    for param in element.elements()? {
+        if param.name != "param" {
+            return Err(param.unexpected_in(element));
+        }
+
+        let mut name = None;
+        let mut ty = None;
+        let mut is_mut = None;
+        let mut attrs = Vec::new();
+        for child in param.elements()? {
+            match child.name {
+                "name" => once(&mut name, child, child.ident()?)?,
+                "pat" => once(&mut name, child, child.line_text()?)?,
+                "type" => once(&mut ty, child, child.line_text()?)?,
+                "mut" => {
+                    child.flag()?;
+                    once(&mut is_mut, child, ())?;
+                }
+                "attr" => attrs.push(format!("#[{}] ", child.line_text()?)),
+                _ => return Err(child.unexpected_in(param)),
+            }
+        }
+
+        let name = name.ok_or_else(|| param.error("a <param> needs a <name> or <pat>"))?;
+        let ty = ty.ok_or_else(|| param.error("a <param> needs a <type>"))?;
+        let mutability = if is_mut.is_some() { "mut " } else { "" };
+        let param_text = match (name.as_str(), ty.as_str()) {
+            ("self", "Self") => format!("{mutability}self"),
+            ("self", "&Self" | "& Self") => format!("{mutability}&self"),
+            ("self", "&mut Self" | "& mut Self") => format!("{mutability}&mut self"),
+            _ => format!("{mutability}{name}: {ty}"),
+        };
+        params.push((attrs.concat() + &param_text, is_mut.is_some()));
+    }
+    Ok(params)
+}
+
+fn push_gparams(output: &mut String, params: &[String]) {
+    if !params.is_empty() {
+        output.push('<');
+        output.push_str(&params.join(", "));
+        output.push('>');
+    }
+}
+
+/// Adds a `where` clause on its own lines, each clause followed by `end`.
+fn push_where(output: &mut String, clauses: &[String], indent: &str, end: &str) {
+    if !clauses.is_empty() {
+        let _ = write!(output, "\n{indent}where\n");
+        for clause in clauses {
+            let _ = write!(output, "{indent}    {clause}{end}");
+        }
+    }
+}
+
+/// Items without bodies end their `where` clause with a `;` rather than a `,`.
+fn trim_last_comma(output: &mut String) {
+    if output.ends_with(",\n") {
+        output.truncate(output.len() - 2);
+    }
+}
+
+/// Strips the indentation the XML gave `text` and indents it with `indent` instead. Only spaces and
+/// tabs count as indentation, so slicing it off never lands inside a character.
+fn reindent(text: &str, indent: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    let lines = &lines[first.unwrap_or(lines.len())..];
+    let common = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    let mut output = String::new();
+    for line in lines {
+        match line.trim().is_empty() {
+            true => output.push('\n'),
+            false => {
+                let _ = writeln!(output, "{indent}{}", line[common..].trim_end());
+            }
+        }
+    }
+    output
+}
@@ -0,0 +1,145 @@
+/// Parses the `<when></when>` at the start of a `<cfg></cfg>` section. The items inside the section
+/// are left for the caller.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_cfg! {
+///     @parse {
+///         input: [
+///                     <when>debug_assertions</when>
+///                     <assocfn>
+///                         <name>bar</name>
+///                     </assocfn>
+///                 </cfg>
+///             </trait>
+///         ],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@cfgcallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_cfg {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_cfg,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<when>$($rest:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_cfg! {
+            @parsewhen {
+                input: [$($rest)*],
+                pred: [],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: $inputtoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing cfg: expected `<when></when>` right after `<cfg>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    (
+        @parsewhen {
+            input: [</when>$($rest:tt)*],
+            pred: [$($pred:tt)+],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                cfg: [$($pred)+],
+            }
+        }
+    };
+    (
+        @parsewhen {
+            input: [</when>$($rest:tt)*],
+            pred: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing cfg: `<when></when>` needs a predicate. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsewhen {
+            input: [],
+            pred: $predtoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing cfg: expected `</when>`, found end of input. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsewhen {
+            input: [$first:tt$($rest:tt)*],
+            pred: [$($pred:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_cfg! {
+            @parsewhen {
+                input: [$($rest)*],
+                pred: [$($pred)* $first],
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
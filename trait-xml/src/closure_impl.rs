@@ -0,0 +1,317 @@
+/// Expands a `<closure-impl/>` into an implementation of a single-function trait for closures with
+/// the same signature.
+///
+/// Expected initial call example:
+/// ```
+/// trait OnEvent {
+///     fn on_event(&mut self, id: u32) -> bool;
+/// }
+///
+/// trait_xml::trait_xml_expand_closure_impl! {
+///     @expand {
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: OnEvent,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [on_event]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [
+///                         [param [self] [&mut Self] [refmut] [] []]
+///                         [param [id] [u32] [] [] []]
+///                     ]
+///                     [bool]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+///
+/// let mut seen = Vec::new();
+/// let mut handler = |id: u32| {
+///     seen.push(id);
+///     id % 2 == 0
+/// };
+/// assert!(handler.on_event(2));
+/// assert!(!handler.on_event(3));
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_closure_impl {
+    // Trait shape errors
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: [unsafe],
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding closure impl of `",
+            stringify!($name),
+            "`: unsafe traits can't be implemented for closures",
+        ));
+    };
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: [],
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [$($att:tt)+],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding closure impl of `",
+            stringify!($name),
+            "`: traits with associated types can't be implemented for closures",
+        ));
+    };
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: [],
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: [$($act:tt)*],
+                fns: [
+                    [
+                        af
+                        [$fname:ident]
+                        $qualstoks:tt
+                        []
+                        [$([param [$arg:tt] [$type:ty] [$($recv:tt)?] $muttoks:tt $pattrstoks:tt])*]
+                        $rettoks:tt
+                        $wheretoks:tt
+                        $deftoks:tt
+                        $docstoks:tt
+                        $attrstoks:tt
+                    ]
+                ],
+            },
+        }
+    ) => {
+        $(
+            $crate::trait_xml_expand_closure_impl! {
+                @checkac $name $act
+            }
+        )*
+
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_closure_impl,
+                    rule: [@namescallback],
+                    args: [
+                        name: $name,
+                        gparams: [$($gpt)*],
+                        wc: $wct,
+                        fn: $fname,
+                        qualifiers: $qualstoks,
+                        // The first copy of the arg is for matching `self`, the second one is the
+                        // one that actually gets used
+                        params: [$([param [$arg] [$arg] [$type] [$($recv)?]])*],
+                        ret: $rettoks,
+                        where: $wheretoks,
+                        attrs: $attrstoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: [],
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: $act:tt,
+                fns: [[af [$fname:ident] $qualstoks:tt [$($fgpt:tt)+] $($rest:tt)*]],
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding closure impl of `",
+            stringify!($name),
+            "`: associated function `",
+            stringify!($fname),
+            "` has generic parameters, so it can't be implemented for closures",
+        ));
+    };
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: [],
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding closure impl of `",
+            stringify!($name),
+            "`: only traits with exactly one associated function can be implemented for closures",
+        ));
+    };
+
+    // Constants can't come from a closure, so they have to have a default
+    (
+        @checkac $trait:ident [[$name:ident] [$type:ty] [$default:expr] $attrstoks:tt]
+    ) => {};
+    (
+        @checkac $trait:ident [[$name:ident] [$type:ty] [] $attrstoks:tt]
+    ) => {
+        compile_error!(concat!(
+            "error expanding closure impl of `",
+            stringify!($trait),
+            "`: associated constant `",
+            stringify!($name),
+            "` needs a `<default-value></default-value>`",
+        ));
+    };
+
+    // The receiver decides which closure trait is needed, and nothing else can be `Self`
+    (
+        @namescallback {
+            name: $name:ident,
+            gparams: $gpt:tt,
+            wc: $wct:tt,
+            fn: $fname:ident,
+            qualifiers: $qualstoks:tt,
+            params: [
+                [param [self] [$recvarg:tt] [$recvtype:ty] [$recv:ident]]
+                $([param $matchtoks:tt [$arg:tt] [$type:ty] []])*
+            ],
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            attrs: $attrstoks:tt,
+            lifetimes: $lttoks:tt,
+            types: $tytoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_closure_impl! {
+            @fntrait [$recv] {
+                name: $name,
+                gparams: $gpt,
+                wc: $wct,
+                fn: $fname,
+                qualifiers: $qualstoks,
+                recv: [$recvarg: $recvtype],
+                params: [$([$arg] [$type])*],
+                ret: $rettoks,
+                where: $wheretoks,
+                attrs: $attrstoks,
+                lifetimes: $lttoks,
+                types: $tytoks,
+            }
+        }
+    };
+    (
+        @namescallback {
+            name: $name:ident,
+            gparams: $gpt:tt,
+            wc: $wct:tt,
+            fn: $fname:ident,
+            $($rest:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding closure impl of `",
+            stringify!($name),
+            "`: associated function `",
+            stringify!($fname),
+            "` has to take `self`, `&self` or `&mut self` first and can't take `Self` anywhere else",
+        ));
+    };
+
+    (@fntrait [ref] $implttoks:tt) => {
+        $crate::trait_xml_expand_closure_impl! { @emit [Fn] $implttoks }
+    };
+    (@fntrait [refmut] $implttoks:tt) => {
+        $crate::trait_xml_expand_closure_impl! { @emit [FnMut] $implttoks }
+    };
+    (@fntrait [value] $implttoks:tt) => {
+        $crate::trait_xml_expand_closure_impl! { @emit [FnOnce] $implttoks }
+    };
+
+    (
+        @emit [$fntrait:ident] {
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            fn: $fname:ident,
+            qualifiers: [
+                unsafe: [$($unsafe:tt)?],
+                extern: [$($extern:literal)?],
+            ],
+            recv: [$recvarg:tt: $recvtype:ty],
+            params: [$([$arg:tt] [$type:ty])*],
+            ret: [$($rt:ty)?],
+            where: [$($([$($wt:tt)+])+)?],
+            attrs: [$([$($attr:tt)*])*],
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        impl<$($gpt)* __F> $name<$($lt,)* $($ty),*> for __F
+        where
+            __F: $fntrait($($type),*) $(-> $rt)?,
+            $($wct)*
+        {
+            $(#[$($attr)*])*
+            $($unsafe)? $(extern $extern)? fn $fname(
+                $recvarg: $recvtype,
+                $($arg: $type),*
+            )$( -> $rt)?
+            $(where $($($wt)+,)+)?
+            {
+                $recvarg($($arg),*)
+            }
+        }
+    };
+}
@@ -0,0 +1,210 @@
+/// Skips an XML comment. The caller matches the `<!--` and hands over the rest of its input along
+/// with its whole parsing state, which gets passed back untouched once the `-->` turns up.
+///
+/// Comments still have to be made of valid Rust tokens, so no unbalanced brackets or lone quotes.
+/// They're skipped 8 tokens at a time where possible, since every step here counts towards the
+/// recursion limit of the whole `trait_xml!` expansion.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_skip_comment! {
+///     @skip {
+///         input: [
+///                     this is a comment -->
+///                     <name>Foo</name>
+///                 </trait>
+///         ],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@parsetrait],
+///             args: [
+///                 output: [],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_skip_comment {
+    // `-->` within the next 8 tokens
+    (
+        @skip {
+            input: [-->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$a:tt -->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$a:tt $b:tt -->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$a:tt $b:tt $c:tt -->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$a:tt $b:tt $c:tt $d:tt -->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$a:tt $b:tt $c:tt $d:tt $e:tt -->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$a:tt $b:tt $c:tt $d:tt $e:tt $f:tt -->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt -->$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($args)*
+            }
+        }
+    };
+    // No `-->` in sight, skip all 8
+    (
+        @skip {
+            input: [$a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt $($rest:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @skip {
+            input: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing comment: expected `-->`, found end of input. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    // Fewer than 8 tokens left
+    (
+        @skip {
+            input: [$first:tt$($rest:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
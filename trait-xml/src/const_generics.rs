@@ -34,6 +34,24 @@
 /// }
 #[macro_export]
 macro_rules! trait_xml_parse_const_generic {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_const_generic,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     // Empty input error
     (
         @parse {
@@ -0,0 +1,961 @@
+/// Parses a `<delegate></delegate>` section.
+///
+/// Expected initial call example:
+/// ```
+/// # struct Meters(f64);
+/// trait_xml::trait_xml_parse_delegate! {
+///     @parse {
+///         input: [
+///                     <for>Meters</for>
+///                     <field>0</field>
+///                 </delegate>
+///             </trait>
+///         ],
+///         for: ,
+///         field: [],
+///         type: ,
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@delegatecallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_delegate {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_delegate,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed delegate
+    (
+        @parse {
+            input: [</delegate>$($rest:tt)*],
+            for: $for:ty,
+            field: [$($fieldtok:tt)+],
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                delegate: [delegate [$for] [$($fieldtok)+] [$($type)?]],
+            }
+        }
+    };
+
+    // Missing field(s) errors
+    (
+        @parse {
+            input: [</delegate>$($rest:tt)*],
+            for: ,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: no wrapper type given with `<for></for>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [</delegate>$($rest:tt)*],
+            for: $for:ty,
+            field: [],
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate for `",
+            stringify!($for),
+            "`: no field given with `<field></field>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse wrapper type
+    (
+        @parse {
+            input: [<for>$($rest:tt)*],
+            for: $for:ty,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: wrapper type already defined as `",
+            stringify!($for),
+            "`, but encountered a second `<for>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<for>$($rest:tt)*],
+            for: ,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_delegate! {
+            @parsefor {
+                input: [$($rest)*],
+                for: [],
+                field: $fieldtoks,
+                type: $($type)?,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parsefor {
+            input: [],
+            for: $fortoks:tt,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate wrapper type: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsefor {
+            input: [</for>$($rest:tt)*],
+            for: [],
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate wrapper type: empty type. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsefor {
+            input: [</for>$($rest:tt)*],
+            for: [$for:ty],
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_delegate! {
+            @parse {
+                input: [$($rest)*],
+                for: $for,
+                field: $fieldtoks,
+                type: $($type)?,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parsefor {
+            input: [</for>$($rest:tt)*],
+            for: [$($forinv:tt)+],
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate wrapper type: input forms invalid type `",
+            $(stringify!($forinv),)+
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsefor {
+            input: [$first:tt$($rest:tt)*],
+            for: [$($fortok:tt)*],
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_delegate! {
+            @parsefor {
+                input: [$($rest)*],
+                for: [$($fortok)* $first],
+                field: $fieldtoks,
+                type: $($type)?,
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    // Parse field path
+    (
+        @parse {
+            input: [<field>$($rest:tt)*],
+            for: $($for:ty)?,
+            field: [$($field:tt)+],
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: field already defined as `",
+            $(stringify!($field),)+
+            "`, but encountered a second `<field>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<field>$($rest:tt)*],
+            for: $($for:ty)?,
+            field: [],
+            type: $($type:ty)?,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_delegate! {
+            @parsefield {
+                input: [$($rest)*],
+                for: $($for)?,
+                field: [],
+                type: $($type)?,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parsefield {
+            input: [],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate field: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsefield {
+            input: [</field>$($rest:tt)*],
+            for: $($for:ty)?,
+            field: [],
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate field: empty field path. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsefield {
+            input: [</field>$($rest:tt)*],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_delegate! {
+            @parse {
+                input: [$($rest)*],
+                for: $($for)?,
+                field: $fieldtoks,
+                type: $($type)?,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parsefield {
+            input: [$first:tt$($rest:tt)*],
+            for: $($for:ty)?,
+            field: [$($field:tt)*],
+            type: $($type:ty)?,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_delegate! {
+            @parsefield {
+                input: [$($rest)*],
+                for: $($for)?,
+                field: [$($field)* $first],
+                type: $($type)?,
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    // Parse field type
+    (
+        @parse {
+            input: [<type>$($rest:tt)*],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: $type:ty,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: field type already defined as `",
+            stringify!($type),
+            "`, but encountered a second `<type>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<type>$($rest:tt)*],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: ,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_type_ty! {
+            @parse {
+                input: [$($rest)*],
+                type: ,
+                tokens: [],
+                callback: [
+                    name: $crate::trait_xml_parse_delegate,
+                    rule: [@typecallback],
+                    args: [
+                        for: [$($for)?],
+                        field: $fieldtoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            for: $($for:ty)?,
+            field: $fieldtoks:tt,
+            type: $($type:ty)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing delegate: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Catch callbacks
+    (
+        @typecallback {
+            input: $inputtoks:tt,
+            for: [$($for:ty)?],
+            field: $fieldtoks:tt,
+            callback: $callbacktoks:tt,
+            type: $type:ty,
+        }
+    ) => {
+        $crate::trait_xml_parse_delegate! {
+            @parse {
+                input: $inputtoks,
+                for: $($for)?,
+                field: $fieldtoks,
+                type: $type,
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
+
+/// Expands a parsed `<delegate>` into an implementation of the trait for the wrapper type that
+/// forwards everything to the wrapped field.
+///
+/// Expected initial call example:
+/// ```
+/// trait Foo {
+///     fn bar(self: &Self) -> u8;
+/// }
+///
+/// impl Foo for u8 {
+///     fn bar(self: &Self) -> u8 {
+///         *self
+///     }
+/// }
+///
+/// struct Baz(u8);
+///
+/// trait_xml::trait_xml_expand_delegate! {
+///     @expand {
+///         delegate: [[Baz] [0] []],
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: Foo,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [bar]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [[param [self] [&Self] [ref] [] []]]
+///                     [u8]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_delegate {
+    // Associated types and consts can only be forwarded if we know what we're forwarding to
+    (
+        @expand {
+            delegate: [[$for:ty] $fieldtoks:tt []],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [$($att:tt)+],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding delegate of `",
+            stringify!($name),
+            "` for `",
+            stringify!($for),
+            "`: the trait has associated types, so the field's type must be given with ",
+            "`<type></type>`",
+        ));
+    };
+    (
+        @expand {
+            delegate: [[$for:ty] $fieldtoks:tt []],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: [$($act:tt)+],
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding delegate of `",
+            stringify!($name),
+            "` for `",
+            stringify!($for),
+            "`: the trait has associated constants, so the field's type must be given with ",
+            "`<type></type>`",
+        ));
+    };
+    (
+        @expand {
+            delegate: [$fortoks:tt $fieldtoks:tt $typetoks:tt],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_delegate,
+                    rule: [@namescallback],
+                    args: [
+                        for: $fortoks,
+                        field: $fieldtoks,
+                        type: $typetoks,
+                        unsafe: $unsafetoks,
+                        name: $name,
+                        gparams: [$($gpt)*],
+                        wc: $wct,
+                        assoctypes: $att,
+                        assocconsts: $act,
+                        fns: $fnt,
+                    ],
+                ],
+            }
+        }
+    };
+
+    (
+        @namescallback {
+            for: [$for:ty],
+            field: $fieldtoks:tt,
+            type: [$type:ty],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_delegate! {
+            @emit {
+                names: [[$name] [$for]],
+                header: [
+                    $($unsafe)? impl<$($gpt)*> $name<$($lt,)* $($ty),*> for $for
+                    where
+                        $type: $name<$($lt,)* $($ty),*>,
+                        $($wct)*
+                ],
+                qself: [<$type as $name<$($lt,)* $($ty),*>>],
+                field: $fieldtoks,
+                assoc types: $att,
+                assoc consts: $act,
+                fns: $fnt,
+            }
+        }
+    };
+    // Without a field type, leave it up to inference - this only happens when there are just
+    // functions to forward
+    (
+        @namescallback {
+            for: [$for:ty],
+            field: $fieldtoks:tt,
+            type: [],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_delegate! {
+            @emit {
+                names: [[$name] [$for]],
+                header: [
+                    $($unsafe)? impl<$($gpt)*> $name<$($lt,)* $($ty),*> for $for
+                    where
+                        $($wct)*
+                ],
+                qself: [<_ as $name<$($lt,)* $($ty),*>>],
+                field: $fieldtoks,
+                assoc types: $att,
+                assoc consts: $act,
+                fns: $fnt,
+            }
+        }
+    };
+
+    (
+        @emit {
+            names: $namestoks:tt,
+            header: [$($header:tt)*],
+            qself: $qselftoks:tt,
+            field: $fieldtoks:tt,
+            assoc types: [$($att:tt)*],
+            assoc consts: [$($act:tt)*],
+            fns: [$($fnt:tt)*],
+        }
+    ) => {
+        $($header)* {
+            $(
+                $crate::trait_xml_expand_delegate! {
+                    @expandat $att $qselftoks
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_delegate! {
+                    @expandac $act $qselftoks
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_delegate! {
+                    @expandfn $fnt $qselftoks $fieldtoks $namestoks
+                }
+            )*
+        }
+    };
+
+    (
+        @expandac [[$name:ident] [$type:ty] $defaulttoks:tt [$([$($attr:tt)*])*]] [$($qself:tt)*]
+    ) => {
+        $(#[$($attr)*])*
+        const $name: $type = $($qself)*::$name;
+    };
+
+    (
+        @expandat
+        [[$name:ident] [] $boundstoks:tt $wheretoks:tt [$([$($attr:tt)*])*]]
+        [$($qself:tt)*]
+    ) => {
+        $(#[$($attr)*])*
+        type $name = $($qself)*::$name;
+    };
+    (
+        @expandat
+        [[$name:ident] [$($gpt:tt)+] $boundstoks:tt $wheretoks:tt $attrstoks:tt]
+        $qselftoks:tt
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)+],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_delegate,
+                    rule: [@atnamescallback],
+                    args: [
+                        name: $name,
+                        gparams: [$($gpt)+],
+                        where: $wheretoks,
+                        attrs: $attrstoks,
+                        qself: $qselftoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @atnamescallback {
+            name: $name:ident,
+            gparams: [$($gpt:tt)+],
+            where: [$($([$($bw:tt)+])+)?],
+            attrs: [$([$($attr:tt)*])*],
+            qself: [$($qself:tt)*],
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        type $name<$($gpt)+> = $($qself)*::$name<$($lt,)* $($ty),*>
+        $(where $($($bw)+,)+)?;
+    };
+
+    // There's no getting the field out of a `Box<Self>` and the like. The function still gets its
+    // signature so that's the only error.
+    (
+        @expandfn
+        [
+            af [$fname:ident] $qualstoks:tt $gpttoks:tt
+            [[param [self] [$($type:tt)+] [] $muttoks:tt $pattrstoks:tt] $($params:tt)*]
+            $($frest:tt)*
+        ]
+        $qselftoks:tt
+        $fieldtoks:tt
+        [[$name:ident] [$for:ty]]
+    ) => {
+        $crate::trait_xml_expand_delegate! {
+            @expandfn
+            [
+                af [$fname] $qualstoks $gpttoks
+                [[param [self] [$($type)+] [] $muttoks $pattrstoks] $($params)*]
+                $($frest)*
+            ]
+            $qselftoks
+            $fieldtoks
+            [error
+                "error expanding delegate of `",
+                stringify!($name),
+                "` for `",
+                stringify!($for),
+                "`: associated function `",
+                stringify!($fname),
+                "` takes `self: ",
+                stringify!($($type)+),
+                "`, which can't be forwarded to the field - only `self`, `&self` and `&mut self` ",
+                "can",
+            ]
+        }
+    };
+    (
+        @expandfn [
+            af
+            [$name:ident]
+            [
+                unsafe: [$($unsafe:tt)?],
+                extern: [$($extern:literal)?],
+            ]
+            [$($($gpt:tt)+)?]
+            [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+            [$($($rt:tt)+)?]
+            [$($([$($wt:tt)+])+)?]
+            $deftoks:tt
+            $docstoks:tt
+            [$([$($attr:tt)*])*]
+        ]
+        [$($qself:tt)*]
+        $fieldtoks:tt
+        $namestoks:tt
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+            $($arg: $type),*
+        )$( -> $($rt)+)?
+        $(where $($($wt)+,)+)?
+        {
+            $crate::trait_xml_expand_delegate! {
+                @body $namestoks [$name] [$($($rt)+)?] [$($($rt)+)?] {
+                    $($unsafe)? {
+                        $($qself)*::$name($(
+                            $crate::trait_xml_expand_delegate!(@arg [$arg] $recvtoks $fieldtoks)
+                        ),*)
+                    }
+                }
+            }
+        }
+    };
+
+    // The field's `Self` isn't the newtype, so there'd be no way of turning a returned one back -
+    // not on its own, and not inside an `Option<Self>` or `(Self, u8)` either. The return type
+    // gets looked through for a `Self` that isn't the start of a path like `Self::Item`, since
+    // those are the same for the newtype and the field. The second copy of it is for the error.
+    (@body [error $($message:tt)*] $fntoks:tt $rettoks:tt $fullrettoks:tt $bodytoks:tt) => {
+        compile_error!(concat!($($message)*))
+    };
+    (@body $namestoks:tt $fntoks:tt [] $fullrettoks:tt {$($body:tt)*}) => { $($body)* };
+    (@body $namestoks:tt $fntoks:tt [Self :: $($rest:tt)*] $fullrettoks:tt $bodytoks:tt) => {
+        $crate::trait_xml_expand_delegate! {
+            @body $namestoks $fntoks [$($rest)*] $fullrettoks $bodytoks
+        }
+    };
+    (@body $namestoks:tt $fntoks:tt [Self as $($rest:tt)*] $fullrettoks:tt $bodytoks:tt) => {
+        $crate::trait_xml_expand_delegate! {
+            @body $namestoks $fntoks [$($rest)*] $fullrettoks $bodytoks
+        }
+    };
+    (
+        @body [[$name:ident] [$for:ty]] [$fname:ident] [Self $($rest:tt)*] [$($rt:tt)+]
+        $bodytoks:tt
+    ) => {
+        compile_error!(concat!(
+            "error expanding delegate of `",
+            stringify!($name),
+            "` for `",
+            stringify!($for),
+            "`: associated function `",
+            stringify!($fname),
+            "` returns `",
+            stringify!($($rt)+),
+            "`, and the field's `Self` can't be turned back into `",
+            stringify!($for),
+            "`",
+        ))
+    };
+    (
+        @body $namestoks:tt $fntoks:tt [($($inner:tt)*) $($rest:tt)*] $fullrettoks:tt
+        $bodytoks:tt
+    ) => {
+        $crate::trait_xml_expand_delegate! {
+            @body $namestoks $fntoks [$($inner)* $($rest)*] $fullrettoks $bodytoks
+        }
+    };
+    (
+        @body $namestoks:tt $fntoks:tt [[$($inner:tt)*] $($rest:tt)*] $fullrettoks:tt
+        $bodytoks:tt
+    ) => {
+        $crate::trait_xml_expand_delegate! {
+            @body $namestoks $fntoks [$($inner)* $($rest)*] $fullrettoks $bodytoks
+        }
+    };
+    (@body $namestoks:tt $fntoks:tt [$other:tt $($rest:tt)*] $fullrettoks:tt $bodytoks:tt) => {
+        $crate::trait_xml_expand_delegate! {
+            @body $namestoks $fntoks [$($rest)*] $fullrettoks $bodytoks
+        }
+    };
+
+    // Anything taking `Self` gets the field instead
+    (@arg [$arg:tt] [ref] [$($field:tt)+]) => { &$arg.$($field)+ };
+    (@arg [$arg:tt] [refmut] [$($field:tt)+]) => { &mut $arg.$($field)+ };
+    (@arg [$arg:tt] [value] [$($field:tt)+]) => { $arg.$($field)+ };
+    (@arg [$arg:tt] [] $fieldtoks:tt) => { $arg };
+}
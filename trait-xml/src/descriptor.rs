@@ -0,0 +1,675 @@
+/// Describes a trait defined with `<descriptor></descriptor>`. Types and paths all come out the
+/// same way however they were spaced in the XML: no spaces except between words, after `,` and `;`
+/// and around `=`, `+` and `->`, e.g. `&'a mut Self` or `Box<dyn Iterator<Item = u8> + Send>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraitDescriptor {
+    pub name: &'static str,
+    /// Empty for private traits.
+    pub vis: &'static str,
+    pub is_unsafe: bool,
+    /// Names of the generic parameters, lifetimes first.
+    pub generic_params: &'static [&'static str],
+    pub supertraits: &'static [&'static str],
+    pub assoc_types: &'static [&'static str],
+    pub assoc_consts: &'static [ConstDescriptor],
+    pub fns: &'static [FnDescriptor],
+}
+
+/// Describes an associated constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstDescriptor {
+    pub name: &'static str,
+    pub ty: &'static str,
+    pub has_default: bool,
+}
+
+/// Describes an associated function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FnDescriptor {
+    pub name: &'static str,
+    /// Includes the receiver, if there is one.
+    pub params: &'static [ParamDescriptor],
+    /// `None` for functions returning `()` without saying so.
+    pub ret: Option<&'static str>,
+    /// Whether there's a `<rust></rust>` body.
+    pub has_default: bool,
+}
+
+/// Describes an associated function parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamDescriptor {
+    pub name: &'static str,
+    pub ty: &'static str,
+}
+
+/// Parses a `<descriptor></descriptor>` section.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_descriptor! {
+///     @parse {
+///         input: [
+///                     <name>FOO</name>
+///                 </descriptor>
+///             </trait>
+///         ],
+///         name: ,
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@descriptorcallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_descriptor {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_descriptor,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing descriptor: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed descriptor
+    (
+        @parse {
+            input: [</descriptor>$($rest:tt)*],
+            name: $name:ident,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                descriptor: [descriptor [$name]],
+            }
+        }
+    };
+
+    // Missing field errors
+    (
+        @parse {
+            input: [</descriptor>$($rest:tt)*],
+            name: ,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing descriptor: no name given with `<name></name>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse name
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: $name:ident,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing descriptor: name already defined as `",
+            stringify!($name),
+            "`, but encountered a second `<name>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<name>$new:ident</name>$($rest:tt)*],
+            name: ,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_descriptor! {
+            @parse {
+                input: [$($rest)*],
+                name: $new,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: ,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing descriptor: `<name></name>` has to contain a valid identifier. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Unexpected input errors
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing descriptor: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing descriptor: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing descriptor: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+}
+
+/// Expands a `<descriptor>` into a [`TraitDescriptor`](crate::TraitDescriptor) constant.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_expand_descriptor! {
+///     @expand {
+///         descriptor: [FOO_DESCRIPTOR],
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: Foo,
+///             gparams: [],
+///             tpbs: [Clone + std::fmt::Debug +],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [bar]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [[param [self] [&Self] [ref] [] []]]
+///                     [u8]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+///
+/// assert_eq!(FOO_DESCRIPTOR.supertraits, ["std::fmt::Debug", "Clone"]);
+/// assert_eq!(FOO_DESCRIPTOR.fns[0].params[0].ty, "&Self");
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_descriptor {
+    (
+        @expand {
+            descriptor: [$descriptor:ident],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: [$($tpbt:tt)*],
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_descriptor,
+                    rule: [@namescallback],
+                    args: [
+                        descriptor: $descriptor,
+                        vis: $vistoks,
+                        unsafe: $unsafetoks,
+                        name: $name,
+                        // Supertraits get added to the front as they're parsed, so they have to be
+                        // split up and put back in order
+                        tpbs: [$($tpbt)*],
+                        assoctypes: $att,
+                        assocconsts: $act,
+                        fns: $fnt,
+                    ],
+                ],
+            }
+        }
+    };
+
+    (
+        @namescallback {
+            descriptor: $descriptor:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            tpbs: $tpbt:tt,
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_descriptor! {
+            @supertraits {
+                descriptor: $descriptor,
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                name: $name,
+                gparams: [$(stringify!($lt),)* $(stringify!($ty),)*],
+                tpbs: $tpbt,
+                current: [],
+                supertraits: [],
+                assoctypes: $att,
+                assocconsts: $act,
+                fns: $fnt,
+            }
+        }
+    };
+
+    // Split supertraits on `+`s, leaving out `<sealed/>`'s marker since it's an implementation
+    // detail
+    (
+        @supertraits {
+            descriptor: $descriptor:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: [+ $($rest:tt)*],
+            current: [__sealed::Sealed],
+            supertraits: $supertraitstoks:tt,
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_descriptor! {
+            @supertraits {
+                descriptor: $descriptor,
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                name: $name,
+                gparams: $gpt,
+                tpbs: [$($rest)*],
+                current: [],
+                supertraits: $supertraitstoks,
+                assoctypes: $att,
+                assocconsts: $act,
+                fns: $fnt,
+            }
+        }
+    };
+    (
+        @supertraits {
+            descriptor: $descriptor:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: [+ $($rest:tt)*],
+            current: [$($current:tt)+],
+            supertraits: [$($supertrait:tt)*],
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_descriptor! {
+            @supertraits {
+                descriptor: $descriptor,
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                name: $name,
+                gparams: $gpt,
+                tpbs: [$($rest)*],
+                current: [],
+                supertraits: [
+                    $crate::trait_xml_expand_descriptor!(@str $($current)+),
+                    $($supertrait)*
+                ],
+                assoctypes: $att,
+                assocconsts: $act,
+                fns: $fnt,
+            }
+        }
+    };
+    (
+        @supertraits {
+            descriptor: $descriptor:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: [$first:tt $($rest:tt)*],
+            current: [$($current:tt)*],
+            supertraits: $supertraitstoks:tt,
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_descriptor! {
+            @supertraits {
+                descriptor: $descriptor,
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                name: $name,
+                gparams: $gpt,
+                tpbs: [$($rest)*],
+                current: [$($current)* $first],
+                supertraits: $supertraitstoks,
+                assoctypes: $att,
+                assocconsts: $act,
+                fns: $fnt,
+            }
+        }
+    };
+    (
+        @supertraits {
+            descriptor: $descriptor:ident,
+            vis: [$($vistok:tt)*],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gp:expr,)*],
+            tpbs: [],
+            current: [],
+            supertraits: [$($supertrait:expr,)*],
+            assoctypes: [$([[$atname:ident] $($atrest:tt)*])*],
+            assocconsts: [$($act:tt)*],
+            fns: [$($fnt:tt)*],
+        }
+    ) => {
+        $($vistok)* const $descriptor: $crate::TraitDescriptor = $crate::TraitDescriptor {
+            name: stringify!($name),
+            vis: $crate::trait_xml_expand_descriptor!(@str $($vistok)*),
+            is_unsafe: $crate::trait_xml_expand_descriptor!(@present [$($unsafe)?]),
+            generic_params: &[$($gp),*],
+            supertraits: &[$($supertrait),*],
+            assoc_types: &[$(stringify!($atname)),*],
+            assoc_consts: &[$($crate::trait_xml_expand_descriptor!(@ac $act)),*],
+            fns: &[$($crate::trait_xml_expand_descriptor!(@fn $fnt)),*],
+        };
+    };
+
+    (@present []) => { false };
+    (@present [$($tok:tt)+]) => { true };
+
+    (
+        @ac [[$name:ident] [$type:ty] [$($default:expr)?] $attrstoks:tt]
+    ) => {
+        $crate::ConstDescriptor {
+            name: stringify!($name),
+            ty: $crate::trait_xml_expand_descriptor!(@str $type),
+            has_default: $crate::trait_xml_expand_descriptor!(@present [$($default)?]),
+        }
+    };
+
+    (
+        @fn [
+            af
+            [$name:ident]
+            $qualstoks:tt
+            $gptoks:tt
+            [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+            [$($rt:ty)?]
+            $wheretoks:tt
+            [$($dt:tt)*]
+            $docstoks:tt
+            $attrstoks:tt
+        ]
+    ) => {
+        $crate::FnDescriptor {
+            name: stringify!($name),
+            params: &[
+                $(
+                    $crate::ParamDescriptor {
+                        name: stringify!($arg),
+                        ty: $crate::trait_xml_expand_descriptor!(@str $type),
+                    }
+                ),*
+            ],
+            ret: $crate::trait_xml_expand_descriptor!(@ret [$($rt)?]),
+            has_default: $crate::trait_xml_expand_descriptor!(@present [$($dt)*]),
+        }
+    };
+
+    (@ret []) => { ::std::option::Option::None };
+    (@ret [$rt:ty]) => {
+        ::std::option::Option::Some($crate::trait_xml_expand_descriptor!(@str $rt))
+    };
+
+    (@str $($tok:tt)*) => {{
+        const TEXT: &str = stringify!($($tok)*);
+        const NORMALIZED: ([u8; 2 * TEXT.len()], usize) = $crate::__normalize_type(TEXT);
+        $crate::__normalized_str(&NORMALIZED)
+    }};
+}
+
+// `stringify!` spaces tokens differently depending on where they came from - `&Self` straight from
+// the XML but `& Self` when it went through one of `<param>`'s receiver rules - so descriptors
+// respace everything themselves. It has to happen in a `const`, hence doing it byte by byte into a
+// buffer that's twice as long as the input to make room for any added spaces.
+
+/// Whether `byte` can be part of an identifier, keyword or number.
+const fn is_word(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+/// Whether a token starting with `byte` is a word, lifetime or string literal.
+const fn is_wordy(byte: u8) -> bool {
+    is_word(byte) || byte == b'\'' || byte == b'"'
+}
+
+/// Where the token starting at `start` ends. Words, lifetimes, string literals, `::` and `->` are
+/// one token each, any other punctuation is one token per character.
+const fn token_end(text: &[u8], start: usize) -> usize {
+    let mut end = start + 1;
+    if is_word(text[start]) || text[start] == b'\'' {
+        while end < text.len() && is_word(text[end]) {
+            end += 1;
+        }
+    } else if text[start] == b'"' {
+        while end < text.len() && text[end] != b'"' {
+            if text[end] == b'\\' {
+                end += 1;
+            }
+            end += 1;
+        }
+        end += 1;
+    } else if end < text.len()
+        && (text[start] == b':' && text[end] == b':' || text[start] == b'-' && text[end] == b'>')
+    {
+        end += 1;
+    }
+    end
+}
+
+/// Whether the token at `text[token.0..token.1]` is one of `options`.
+const fn is_any(text: &[u8], token: (usize, usize), options: &[&str]) -> bool {
+    let mut option = 0;
+    'options: while option < options.len() {
+        let candidate = options[option].as_bytes();
+        option += 1;
+        if token.1 - token.0 != candidate.len() {
+            continue;
+        }
+        let mut i = 0;
+        while i < candidate.len() {
+            if text[token.0 + i] != candidate[i] {
+                continue 'options;
+            }
+            i += 1;
+        }
+        return true;
+    }
+    false
+}
+
+/// Keywords that are always followed by a space.
+const KEYWORDS: &[&str] = &[
+    "mut", "const", "dyn", "impl", "unsafe", "extern", "as", "where",
+];
+
+const fn space_between(text: &[u8], prev: (usize, usize), next: (usize, usize)) -> bool {
+    if is_any(text, next, &[",", ";", ")", "]", "}", ">"]) {
+        false
+    } else if is_any(text, prev, &[",", ";", "=", "+", "->"])
+        || is_any(text, next, &["=", "+", "->"])
+    {
+        true
+    } else if is_any(text, prev, KEYWORDS) {
+        // `*mut [u8]`, `&mut (A, B)`
+        true
+    } else {
+        // `for<'a> Fn(&'a u8)`
+        (is_wordy(text[prev.0]) || is_any(text, prev, &[">"])) && is_wordy(text[next.0])
+    }
+}
+
+/// Respaces `text` for a descriptor. `N` has to be at least twice `text`'s length.
+#[doc(hidden)]
+pub const fn __normalize_type<const N: usize>(text: &str) -> ([u8; N], usize) {
+    let text = text.as_bytes();
+    let mut out = [0; N];
+    let mut len = 0;
+    let mut prev = None;
+    let mut start = 0;
+    while start < text.len() {
+        if text[start].is_ascii_whitespace() {
+            start += 1;
+            continue;
+        }
+        let end = token_end(text, start);
+        if let Some(prev) = prev {
+            if space_between(text, prev, (start, end)) {
+                out[len] = b' ';
+                len += 1;
+            }
+        }
+        let mut i = start;
+        while i < end {
+            out[len] = text[i];
+            len += 1;
+            i += 1;
+        }
+        prev = Some((start, end));
+        start = end;
+    }
+    (out, len)
+}
+
+/// Gets the `&str` back out of [`__normalize_type`]'s buffer.
+#[doc(hidden)]
+pub const fn __normalized_str<const N: usize>(
+    normalized: &'static ([u8; N], usize),
+) -> &'static str {
+    match core::str::from_utf8(normalized.0.split_at(normalized.1).0) {
+        Ok(text) => text,
+        Err(_) => panic!("respacing a descriptor type produced invalid UTF-8"),
+    }
+}
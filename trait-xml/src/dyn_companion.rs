@@ -0,0 +1,1170 @@
+/// Parses a `<dyn-companion></dyn-companion>` section.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_dyn_companion! {
+///     @parse {
+///         input: [
+///                     <name>DynFoo</name>
+///                     <boxed/>
+///                 </dyn-companion>
+///             </trait>
+///         ],
+///         name: ,
+///         boxed: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@dyncompanioncallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_dyn_companion {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_dyn_companion,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            name: $($name:ident)?,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed dyn companion
+    (
+        @parse {
+            input: [</dyn-companion>$($rest:tt)*],
+            name: $name:ident,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                dyncompanion: [dyncompanion [[$name] [$($boxed)?]]],
+            }
+        }
+    };
+
+    // Missing field errors
+    (
+        @parse {
+            input: [</dyn-companion>$($rest:tt)*],
+            name: ,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: no name given with `<name></name>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse name
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: $name:ident,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: name already defined as `",
+            stringify!($name),
+            "`, but encountered a second `<name>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<name>$new:ident</name>$($rest:tt)*],
+            name: ,
+            boxed: [$($boxed:ident)?],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_dyn_companion! {
+            @parse {
+                input: [$($rest)*],
+                name: $new,
+                boxed: [$($boxed)?],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: ,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: `<name></name>` has to contain a valid identifier. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse boxed flag
+    (
+        @parse {
+            input: [<boxed/>$($rest:tt)*],
+            name: $($name:ident)?,
+            boxed: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_dyn_companion! {
+            @parse {
+                input: [$($rest)*],
+                name: $($name)?,
+                boxed: [boxed],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<boxed/>$($rest:tt)*],
+            name: $($name:ident)?,
+            boxed: [boxed],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: encountered a second `<boxed/>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Unexpected input errors
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            boxed: [$($boxed:ident)?],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing dyn companion: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+}
+
+/// Expands a parsed `<dyn-companion>` into a dyn-compatible version of the trait, a blanket
+/// implementation of it for everything implementing the original trait, and optionally an
+/// implementation of the original trait for `Box<dyn Companion>`.
+///
+/// Associated constants and generic associated types get left out of the companion, and any
+/// associated function that couldn't be called on a `dyn Companion` gets a `Self: Sized` bound so
+/// that it doesn't get in the way.
+///
+/// Expected initial call example:
+/// ```
+/// trait Foo {
+///     fn bar(&self) -> u8;
+/// }
+///
+/// impl Foo for u8 {
+///     fn bar(&self) -> u8 {
+///         *self
+///     }
+/// }
+///
+/// trait_xml::trait_xml_expand_dyn_companion! {
+///     @expand {
+///         companion: [[DynFoo] []],
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: Foo,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [bar]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [[param [self] [&Self] [ref] [] []]]
+///                     [u8]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+///
+/// let foo: &dyn DynFoo = &1u8;
+/// assert_eq!(foo.bar(), 1);
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_dyn_companion {
+    (
+        @expand {
+            companion: [[$dyn:ident] $boxedtoks:tt],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_dyn_companion,
+                    rule: [@namescallback],
+                    args: [
+                        dyn: $dyn,
+                        boxed: $boxedtoks,
+                        vis: $vistoks,
+                        unsafe: $unsafetoks,
+                        name: $name,
+                        gparams: [$($gpt)*],
+                        tpbs: $tpbt,
+                        wc: $wct,
+                        assoctypes: $att,
+                        assocconsts: $act,
+                        fns: $fnt,
+                    ],
+                ],
+            }
+        }
+    };
+
+    (
+        @namescallback {
+            dyn: $dyn:ident,
+            boxed: [$($boxed:ident)?],
+            vis: [$($vistok:tt)*],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            tpbs: $tpbt:tt,
+            wc: [$($wct:tt)*],
+            assoctypes: [$($att:tt)*],
+            assocconsts: $act:tt,
+            fns: [$($fnt:tt)*],
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @emit {
+                dyn: $dyn,
+                vis: [$($vistok)*],
+                unsafe: [$($unsafe)?],
+                name: $name,
+                gparams: [$($gpt)*],
+                wc: [$($wct)*],
+                assoctypes: [$($att)*],
+                fns: [$($fnt)*],
+                args: [$($lt,)* $($ty),*],
+                qself: [<__Impl as $name<$($lt,)* $($ty),*>>],
+            }
+        }
+
+        $crate::trait_xml_expand_dyn_companion! {
+            @boxed {
+                boxed: [$($boxed)?],
+                dyn: $dyn,
+                unsafe: [$($unsafe)?],
+                name: $name,
+                gparams: [$($gpt)*],
+                tpbs: $tpbt,
+                wc: [$($wct)*],
+                assoctypes: [$($att)*],
+                assocconsts: $act,
+                fns: [$($fnt)*],
+                args: [$($lt,)* $($ty),*],
+            }
+        }
+    };
+
+    (
+        @emit {
+            dyn: $dyn:ident,
+            vis: [$($vistok:tt)*],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            assoctypes: [$($att:tt)*],
+            fns: [$($fnt:tt)*],
+            args: [$($args:tt)*],
+            qself: $qselftoks:tt,
+        }
+    ) => {
+        $($vistok)* $($unsafe)? trait $dyn<$($gpt)*>
+        where
+            $($wct)*
+        {
+            $(
+                $crate::trait_xml_expand_dyn_companion! {
+                    @traitat $att
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_dyn_companion! {
+                    @fn [trait] $fnt
+                }
+            )*
+        }
+
+        $($unsafe)? impl<$($gpt)* __Impl: $name<$($args)*> + ?Sized> $dyn<$($args)*> for __Impl
+        where
+            $($wct)*
+        {
+            $(
+                $crate::trait_xml_expand_dyn_companion! {
+                    @implat $att $qselftoks
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_dyn_companion! {
+                    @fn [impl $qselftoks] $fnt
+                }
+            )*
+        }
+
+    };
+
+    // `Box<dyn Companion>` can only implement the original trait if everything in it can be
+    // forwarded through the companion
+    (
+        @boxed {
+            boxed: [],
+            dyn: $dyn:ident,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: $tpbt:tt,
+            wc: $wct:tt,
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+            args: $argstoks:tt,
+        }
+    ) => {};
+    (
+        @boxed {
+            boxed: [boxed],
+            dyn: $dyn:ident,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: [$($tpbt:tt)+],
+            wc: $wct:tt,
+            assoctypes: $att:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+            args: $argstoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding dyn companion `",
+            stringify!($dyn),
+            "` of `",
+            stringify!($name),
+            "`: `Box<dyn ",
+            stringify!($dyn),
+            ">` can't implement `",
+            stringify!($name),
+            "`'s supertraits",
+        ));
+    };
+    (
+        @boxed {
+            boxed: [boxed],
+            dyn: $dyn:ident,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: [],
+            wc: $wct:tt,
+            assoctypes: [$($att:tt)+],
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+            args: $argstoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding dyn companion `",
+            stringify!($dyn),
+            "` of `",
+            stringify!($name),
+            "`: `Box<dyn ",
+            stringify!($dyn),
+            ">` can't implement `",
+            stringify!($name),
+            "` since it has associated types",
+        ));
+    };
+    (
+        @boxed {
+            boxed: [boxed],
+            dyn: $dyn:ident,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: [],
+            wc: $wct:tt,
+            assoctypes: [],
+            assocconsts: [$($act:tt)+],
+            fns: $fnt:tt,
+            args: $argstoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding dyn companion `",
+            stringify!($dyn),
+            "` of `",
+            stringify!($name),
+            "`: `Box<dyn ",
+            stringify!($dyn),
+            ">` can't implement `",
+            stringify!($name),
+            "` since it has associated constants",
+        ));
+    };
+    (
+        @boxed {
+            boxed: [boxed],
+            dyn: $dyn:ident,
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            tpbs: [],
+            wc: [$($wct:tt)*],
+            assoctypes: [],
+            assocconsts: [],
+            fns: [$($fnt:tt)*],
+            args: [$($args:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @boxedemit {
+                dyn: $dyn,
+                unsafe: [$($unsafe)?],
+                name: $name,
+                gparams: [$($gpt)*],
+                wc: [$($wct)*],
+                fns: [$($fnt)*],
+                args: [$($args)*],
+                qself: [<dyn $dyn<$($args)*> as $dyn<$($args)*>>],
+            }
+        }
+    };
+    (
+        @boxedemit {
+            dyn: $dyn:ident,
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            fns: [$($fnt:tt)*],
+            args: [$($args:tt)*],
+            qself: $qselftoks:tt,
+        }
+    ) => {
+        $($unsafe)? impl<$($gpt)*> $name<$($args)*> for ::std::boxed::Box<dyn $dyn<$($args)*>>
+        where
+            $($wct)*
+        {
+            $(
+                $crate::trait_xml_expand_dyn_companion! {
+                    @fn [boxed $dyn $qselftoks] $fnt
+                }
+            )*
+        }
+    };
+
+    // Associated types
+    (
+        @traitat [
+            [$name:ident]
+            []
+            [$($($bt:tt)+)?]
+            [$($([$($bw:tt)+])+)?]
+            [$([$($attr:tt)*])*]
+        ]
+    ) => {
+        $(#[$($attr)*])*
+        type $name$(: $($bt)+)?
+        $(where $($($bw)+,)+)?;
+    };
+    (
+        @traitat [[$name:ident] [$($gpt:tt)+] $boundstoks:tt $wheretoks:tt $attrstoks:tt]
+    ) => {};
+    (
+        @implat [[$name:ident] [] $boundstoks:tt $wheretoks:tt [$([$($attr:tt)*])*]] [$($qself:tt)*]
+    ) => {
+        $(#[$($attr)*])*
+        type $name = $($qself)*::$name;
+    };
+    (
+        @implat [[$name:ident] [$($gpt:tt)+] $boundstoks:tt $wheretoks:tt $attrstoks:tt] $qselftoks:tt
+    ) => {};
+
+    // Work out whether an associated function can be called on a `dyn Companion`, keeping track
+    // of why it can't be
+    (
+        @fn $modetoks:tt [af $nametoks:tt $qualstoks:tt [$($gpt:tt)*] $($rest:tt)*]
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_dyn_companion,
+                    rule: [@fngpcallback],
+                    args: [
+                        mode: $modetoks,
+                        fn: [af $nametoks $qualstoks [$($gpt)*] $($rest)*],
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @fngpcallback {
+            mode: $modetoks:tt,
+            fn: [af $nametoks:tt $qualstoks:tt $gpt:tt $paramstoks:tt $($rest:tt)*],
+            lifetimes: $lttoks:tt,
+            types: [],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnrecv {
+                mode: $modetoks,
+                fn: [af $nametoks $qualstoks $gpt $paramstoks $($rest)*],
+                params: $paramstoks,
+                reasons: [],
+            }
+        }
+    };
+    (
+        @fngpcallback {
+            mode: $modetoks:tt,
+            fn: [af $nametoks:tt $qualstoks:tt $gpt:tt $paramstoks:tt $($rest:tt)*],
+            lifetimes: $lttoks:tt,
+            types: [$($ty:ident)+],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnrecv {
+                mode: $modetoks,
+                fn: [af $nametoks $qualstoks $gpt $paramstoks $($rest)*],
+                params: $paramstoks,
+                reasons: ["has generic parameters"],
+            }
+        }
+    };
+    (
+        @fnrecv {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            params: [
+                [param $argtoks:tt $typetoks:tt [value] $muttoks:tt $pattrstoks:tt]
+                $($rest:tt)*
+            ],
+            reasons: [$($reason:literal)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnparams {
+                mode: $modetoks,
+                fn: $fntoks,
+                params: [$($rest)*],
+                reasons: [$($reason)* "takes `self` by value"],
+            }
+        }
+    };
+    (
+        @fnrecv {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            params: [
+                [param $argtoks:tt $typetoks:tt [$recv:tt] $muttoks:tt $pattrstoks:tt]
+                $($rest:tt)*
+            ],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnparams {
+                mode: $modetoks,
+                fn: $fntoks,
+                params: [$($rest)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    (
+        @fnrecv {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            params: [[param [self] $typetoks:tt [] $muttoks:tt $pattrstoks:tt] $($rest:tt)*],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnparams {
+                mode: $modetoks,
+                fn: $fntoks,
+                params: [$($rest)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    (
+        @fnrecv {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            params: $paramstoks:tt,
+            reasons: [$($reason:literal)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnparams {
+                mode: $modetoks,
+                fn: $fntoks,
+                params: [],
+                reasons: [$($reason)* "doesn't take `self`"],
+            }
+        }
+    };
+    (
+        @fnparams {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            params: [
+                [param $argtoks:tt $typetoks:tt [$recv:tt] $muttoks:tt $pattrstoks:tt]
+                $($rest:tt)*
+            ],
+            reasons: [$($reason:literal)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnparams {
+                mode: $modetoks,
+                fn: $fntoks,
+                params: [],
+                reasons: [$($reason)* "takes `Self` as a parameter"],
+            }
+        }
+    };
+    (
+        @fnparams {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            params: [[param $argtoks:tt $typetoks:tt [] $muttoks:tt $pattrstoks:tt] $($rest:tt)*],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnparams {
+                mode: $modetoks,
+                fn: $fntoks,
+                params: [$($rest)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    (
+        @fnparams {
+            mode: $modetoks:tt,
+            fn: [af $nametoks:tt $qualstoks:tt $gpt:tt $paramstoks:tt [$($rt:tt)*] $($rest:tt)*],
+            params: [],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnret {
+                mode: $modetoks,
+                fn: [af $nametoks $qualstoks $gpt $paramstoks [$($rt)*] $($rest)*],
+                ret: [$($rt)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    // `Self::Foo` is fine, it's only `Self` by itself that isn't
+    (
+        @fnret {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            ret: [Self :: $($rest:tt)*],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnret {
+                mode: $modetoks,
+                fn: $fntoks,
+                ret: [$($rest)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    (
+        @fnret {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            ret: [Self $($rest:tt)*],
+            reasons: [$($reason:literal)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnemit {
+                mode: $modetoks,
+                fn: $fntoks,
+                reasons: [$($reason)* "returns `Self`"],
+            }
+        }
+    };
+    (
+        @fnret {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            ret: [($($inner:tt)*) $($rest:tt)*],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnret {
+                mode: $modetoks,
+                fn: $fntoks,
+                ret: [$($inner)* $($rest)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    (
+        @fnret {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            ret: [[$($inner:tt)*] $($rest:tt)*],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnret {
+                mode: $modetoks,
+                fn: $fntoks,
+                ret: [$($inner)* $($rest)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    (
+        @fnret {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            ret: [$first:tt $($rest:tt)*],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnret {
+                mode: $modetoks,
+                fn: $fntoks,
+                ret: [$($rest)*],
+                reasons: $reasonstoks,
+            }
+        }
+    };
+    (
+        @fnret {
+            mode: $modetoks:tt,
+            fn: $fntoks:tt,
+            ret: [],
+            reasons: $reasonstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @fnemit {
+                mode: $modetoks,
+                fn: $fntoks,
+                reasons: $reasonstoks,
+            }
+        }
+    };
+
+    // Anything that can't be called on a `dyn Companion` gets `Self: Sized`
+    (
+        @fnemit {
+            mode: [trait],
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                [$($($gpt:tt)+)?]
+                [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+                [$($rt:ty)?]
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+            reasons: [],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+            $($arg: $type),*
+        )$( -> $rt)?
+        $(where $($($wt)+,)+)?;
+    };
+    (
+        @fnemit {
+            mode: [trait],
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                [$($($gpt:tt)+)?]
+                [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+                [$($rt:ty)?]
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+            reasons: [$($reason:literal)+],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+            $($arg: $type),*
+        )$( -> $rt)?
+        where
+            Self: Sized,
+            $($($($wt)+,)+)?;
+    };
+    (
+        @fnemit {
+            mode: [impl [$($qself:tt)*]],
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                [$($($gpt:tt)+)?]
+                [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+                [$($rt:ty)?]
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+            reasons: [],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+            $($arg: $type),*
+        )$( -> $rt)?
+        $(where $($($wt)+,)+)?
+        {
+            $($unsafe)? {
+                $($qself)*::$name($($arg),*)
+            }
+        }
+    };
+    (
+        @fnemit {
+            mode: [impl [$($qself:tt)*]],
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                [$($($gpt:tt)+)?]
+                [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+                [$($rt:ty)?]
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+            reasons: [$($reason:literal)+],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+            $($arg: $type),*
+        )$( -> $rt)?
+        where
+            Self: Sized,
+            $($($($wt)+,)+)?
+        {
+            $($unsafe)? {
+                $($qself)*::$name($($arg),*)
+            }
+        }
+    };
+    // Errors go in the function bodies so that the impl is otherwise complete
+    (
+        @fnemit {
+            mode: [boxed $dyn:ident $qselftoks:tt],
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                [$($($gpt:tt)+)?]
+                [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+                [$($rt:ty)?]
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+            reasons: [$reason:literal $($reasons:literal)*],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+            $($arg: $type),*
+        )$( -> $rt)?
+        $(where $($($wt)+,)+)?
+        {
+            compile_error!(concat!(
+                "error expanding dyn companion `",
+                stringify!($dyn),
+                "`: associated function `",
+                stringify!($name),
+                "` ",
+                $reason,
+                ", so it can't be called through `Box<dyn ",
+                stringify!($dyn),
+                ">`",
+            ))
+        }
+    };
+    (
+        @fnemit {
+            mode: [boxed $dyn:ident [$($qself:tt)*]],
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                []
+                [
+                    [param [$recvarg:tt] [$recvtype:ty] [$recv:ident] $recvmut:tt $recvattrs:tt]
+                    $([param [$arg:tt] [$type:ty] [] $muttoks:tt $pattrstoks:tt])*
+                ]
+                [$($rt:ty)?]
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+            reasons: [],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name(
+            $recvarg: $recvtype,
+            $($arg: $type),*
+        )$( -> $rt)?
+        $(where $($($wt)+,)+)?
+        {
+            $($unsafe)? {
+                $($qself)*::$name(
+                    $crate::trait_xml_expand_dyn_companion!(@unbox [$recv] $recvarg),
+                    $($arg),*
+                )
+            }
+        }
+    };
+    (
+        @fnemit {
+            mode: [boxed $dyn:ident $qselftoks:tt],
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                [$($($gpt:tt)+)?]
+                [$([param [$arg:tt] [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt])*]
+                [$($rt:ty)?]
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+            reasons: [],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+            $($arg: $type),*
+        )$( -> $rt)?
+        $(where $($($wt)+,)+)?
+        {
+            compile_error!(concat!(
+                "error expanding dyn companion `",
+                stringify!($dyn),
+                "`: associated function `",
+                stringify!($name),
+                "` has to take `&self` or `&mut self` to be called through `Box<dyn ",
+                stringify!($dyn),
+                ">`",
+            ))
+        }
+    };
+
+    (@unbox [ref] $arg:tt) => { &**$arg };
+    (@unbox [refmut] $arg:tt) => { &mut **$arg };
+}
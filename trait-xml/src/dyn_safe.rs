@@ -0,0 +1,332 @@
+// `where` clauses have already been captured as `ty`s and `path`s by the time `<dyn-safe/>` gets
+// expanded, so macros can't look inside them anymore. Their `stringify!`ed forms can still be
+// checked in a `const` though.
+
+/// Whether the `,`-separated `where` clauses in `clauses` include `Self: Sized`.
+#[doc(hidden)]
+pub const fn __where_self_sized(clauses: &str) -> bool {
+    let s = clauses.as_bytes();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut i = 0;
+    while i <= s.len() {
+        if i == s.len() || (depth == 0 && s[i] == b',') {
+            if clause_is_self_sized(s, start, i) {
+                return true;
+            }
+            start = i + 1;
+        } else {
+            depth = nest(s, i, depth);
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn clause_is_self_sized(s: &[u8], start: usize, end: usize) -> bool {
+    // Find the `:` separating the bounded type from its bounds, skipping over any `::`s
+    let mut depth = 0usize;
+    let mut colon = start;
+    while colon < end {
+        if depth == 0 && s[colon] == b':' {
+            if colon + 1 < end && s[colon + 1] == b':' {
+                colon += 2;
+                continue;
+            }
+            break;
+        }
+        depth = nest(s, colon, depth);
+        colon += 1;
+    }
+    if colon == end || !eq_ignoring_ws(s, start, colon, b"Self") {
+        return false;
+    }
+    let mut bound = colon + 1;
+    let mut i = bound;
+    while i <= end {
+        if i == end || (depth == 0 && s[i] == b'+') {
+            if is_sized(s, bound, i) {
+                return true;
+            }
+            bound = i + 1;
+        } else {
+            depth = nest(s, i, depth);
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Whether `s[start..end]` is `Sized` or a path ending in `::Sized`.
+const fn is_sized(s: &[u8], start: usize, end: usize) -> bool {
+    let word = b"Sized";
+    let mut i = end;
+    let mut matched = 0;
+    while i > start && matched < word.len() {
+        i -= 1;
+        if !s[i].is_ascii_whitespace() {
+            if s[i] != word[word.len() - 1 - matched] {
+                return false;
+            }
+            matched += 1;
+        }
+    }
+    if matched < word.len() {
+        return false;
+    }
+    while i > start && s[i - 1].is_ascii_whitespace() {
+        i -= 1;
+    }
+    i == start || (i - start >= 2 && s[i - 1] == b':' && s[i - 2] == b':')
+}
+
+const fn eq_ignoring_ws(s: &[u8], mut start: usize, end: usize, word: &[u8]) -> bool {
+    let mut matched = 0;
+    while start < end {
+        if !s[start].is_ascii_whitespace() {
+            if matched == word.len() || s[start] != word[matched] {
+                return false;
+            }
+            matched += 1;
+        }
+        start += 1;
+    }
+    matched == word.len()
+}
+
+/// Tracks how deeply nested in brackets `s[i]` is, so that commas and `+`s in generic arguments
+/// don't get mistaken for separators.
+const fn nest(s: &[u8], i: usize, depth: usize) -> usize {
+    match s[i] {
+        b'<' | b'(' | b'[' => depth + 1,
+        // `->` in `Fn() -> T` bounds
+        b'>' if i > 0 && s[i - 1] == b'-' => depth,
+        b'>' | b')' | b']' => depth.saturating_sub(1),
+        _ => depth,
+    }
+}
+
+/// Expands a `<dyn-safe/>` into checks that the trait can be used as `dyn Trait`.
+///
+/// Expected initial call example:
+/// ```
+/// trait Foo {
+///     fn bar(&self) -> u8;
+/// }
+///
+/// trait_xml::trait_xml_expand_dyn_safe! {
+///     @expand {
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: Foo,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [bar]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [[param [self] [&Self] [ref] [] []]]
+///                     [u8]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_dyn_safe {
+    (
+        @expand {
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [$([[$atname:ident] $($atrest:tt)*])*],
+                assoc consts: [$($act:tt)*],
+                fns: [$($fnt:tt)*],
+            },
+        }
+    ) => {
+        $(
+            $crate::trait_xml_expand_dyn_safe! {
+                @checkac $name $act
+            }
+        )*
+
+        $(
+            $crate::trait_xml_expand_dyn_safe! {
+                @checkfn $name $fnt
+            }
+        )*
+
+        // Catches anything that the checks above don't
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_dyn_safe,
+                    rule: [@namescallback],
+                    args: [
+                        name: $name,
+                        gparams: [$($gpt)*],
+                        wc: $wct,
+                        assoctypes: [$($atname)*],
+                    ],
+                ],
+            }
+        }
+    };
+
+    (
+        @namescallback {
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            assoctypes: [$($at:ident)*],
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        const _: () = {
+            // Associated types have to be given for `dyn Trait` to be a type, so they get stand-ins
+            // with the same names
+            #[allow(dead_code)]
+            fn assert_dyn_compatible<$($gpt)* $($at),*>(
+                _: &dyn $name<$($lt,)* $($ty,)* $($at = $at),*>,
+            )
+            where
+                $($wct)*
+            {
+            }
+        };
+    };
+
+    (
+        @checkac $name:ident [[$acname:ident] $typetoks:tt $defaulttoks:tt $attrstoks:tt]
+    ) => {
+        compile_error!(concat!(
+            "error checking that `",
+            stringify!($name),
+            "` is dyn-compatible: it has an associated constant, `",
+            stringify!($acname),
+            "`",
+        ));
+    };
+
+    (
+        @checkfn $name:ident [
+            af
+            [$fname:ident]
+            $qualstoks:tt
+            [$($gpt:tt)*]
+            $paramstoks:tt
+            $rettoks:tt
+            [$($([$($wt:tt)+])+)?]
+            $deftoks:tt
+            $docstoks:tt
+            $attrstoks:tt
+        ]
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_dyn_safe,
+                    rule: [@fncallback],
+                    args: [
+                        name: $name,
+                        fn: $fname,
+                        ret: $rettoks,
+                        where: [$($($($wt)+,)+)?],
+                    ],
+                ],
+            }
+        }
+    };
+
+    // Generic lifetimes are fine, but generic types and consts aren't
+    (
+        @fncallback {
+            name: $name:ident,
+            fn: $fname:ident,
+            ret: $rettoks:tt,
+            where: [$($wt:tt)*],
+            lifetimes: $lttoks:tt,
+            types: [$($ty:ident)+],
+        }
+    ) => {
+        const _: () = assert!(
+            $crate::__where_self_sized(stringify!($($wt)*)),
+            concat!(
+                "error checking that `",
+                stringify!($name),
+                "` is dyn-compatible: associated function `",
+                stringify!($fname),
+                "` has generic parameters, so it needs `where Self: Sized`",
+            ),
+        );
+
+        $crate::trait_xml_expand_dyn_safe! {
+            @fncallback {
+                name: $name,
+                fn: $fname,
+                ret: $rettoks,
+                where: [$($wt)*],
+                lifetimes: $lttoks,
+                types: [],
+            }
+        }
+    };
+    // The return type is still the tokens from `<ret></ret>`, so `Self` can be matched as is
+    (
+        @fncallback {
+            name: $name:ident,
+            fn: $fname:ident,
+            ret: [Self],
+            where: [$($wt:tt)*],
+            lifetimes: $lttoks:tt,
+            types: [],
+        }
+    ) => {
+        const _: () = assert!(
+            $crate::__where_self_sized(stringify!($($wt)*)),
+            concat!(
+                "error checking that `",
+                stringify!($name),
+                "` is dyn-compatible: associated function `",
+                stringify!($fname),
+                "` returns `Self`, so it needs `where Self: Sized`",
+            ),
+        );
+    };
+    (
+        @fncallback {
+            name: $name:ident,
+            fn: $fname:ident,
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            lifetimes: $lttoks:tt,
+            types: [],
+        }
+    ) => {};
+}
@@ -0,0 +1,387 @@
+/// Decodes the XML entities `&lt;`, `&gt;` and `&amp;` so types and paths can be written as valid
+/// XML. Rust tokenizes `&lt;` as `&` `lt` `;`, which is what gets matched here.
+///
+/// The caller hands over its input whenever it starts with an entity or a group, and gets it back
+/// with the decoded token (or the whole group, decoded all the way down) at the front behind an
+/// `@decoded` marker, so it knows to take that token as-is. `-&gt;` comes back as a single `->`,
+/// since a separate `-` and `>` wouldn't parse as one. Plain `&`s never come through here, since
+/// re-emitting them would lose their spacing and change what `stringify!` makes of the type.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_decode_entities! {
+///     @step {
+///         input: [
+///                     &lt;u8&gt;</type>
+///                 </assocconst>
+///             </trait>
+///         ],
+///         callback: [
+///             name: trait_xml::trait_xml_parse_type_ty,
+///             rule: [@parse],
+///             args: [
+///                 type: ,
+///                 tokens: [Vec],
+///                 callback: [
+///                     name: trait_xml::trait_xml_parse_assoc_const,
+///                     rule: [@typecallback],
+///                     args: [
+///                         name: [Foo],
+///                         default: [],
+///                         callback: [
+///                             name: trait_xml::trait_xml_inner,
+///                             rule: [@accallback],
+///                             args: [
+///                                 output: [[name Foo]],
+///                             ],
+///                         ],
+///                     ],
+///                 ],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_decode_entities {
+    // Entities
+    (
+        @step {
+            input: [& lt ; $($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [@decoded < $($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @step {
+            input: [& gt ; $($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [@decoded > $($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @step {
+            input: [& amp ; $($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [@decoded & $($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @step {
+            input: [- & gt ; $($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [@decoded -> $($rest)*],
+                $($args)*
+            }
+        }
+    };
+
+    // Groups get decoded on their own and put back together afterwards
+    (
+        @step {
+            input: [($($group:tt)*) $($rest:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @decode {
+                input: [$($group)*],
+                output: [],
+                delim: (),
+                rest: [$($rest)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @step {
+            input: [[$($group:tt)*] $($rest:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @decode {
+                input: [$($group)*],
+                output: [],
+                delim: [],
+                rest: [$($rest)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @step {
+            input: [{$($group:tt)*} $($rest:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @decode {
+                input: [$($group)*],
+                output: [],
+                delim: {},
+                rest: [$($rest)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    // Finished a group
+    (
+        @decode {
+            input: [],
+            output: [$($out:tt)*],
+            delim: (),
+            rest: [$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [@decoded ($($out)*) $($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @decode {
+            input: [],
+            output: [$($out:tt)*],
+            delim: [],
+            rest: [$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [@decoded [$($out)*] $($rest)*],
+                $($args)*
+            }
+        }
+    };
+    (
+        @decode {
+            input: [],
+            output: [$($out:tt)*],
+            delim: {},
+            rest: [$($rest:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [@decoded {$($out)*} $($rest)*],
+                $($args)*
+            }
+        }
+    };
+
+    // Inside a group
+    (
+        @decode {
+            input: [@decoded $tok:tt $($rest:tt)*],
+            output: [$($out:tt)*],
+            delim: $delimtoks:tt,
+            rest: $resttoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @decode {
+                input: [$($rest)*],
+                output: [$($out)* $tok],
+                delim: $delimtoks,
+                rest: $resttoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @decode {
+            input: [& lt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& lt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_decode_entities,
+                    rule: [@decode],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @decode {
+            input: [& gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_decode_entities,
+                    rule: [@decode],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @decode {
+            input: [& amp ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& amp ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_decode_entities,
+                    rule: [@decode],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @decode {
+            input: [- & gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [- & gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_decode_entities,
+                    rule: [@decode],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @decode {
+            input: [($($group:tt)*) $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [($($group)*) $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_decode_entities,
+                    rule: [@decode],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @decode {
+            input: [[$($group:tt)*] $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [[$($group)*] $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_decode_entities,
+                    rule: [@decode],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @decode {
+            input: [{$($group:tt)*} $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [{$($group)*} $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_decode_entities,
+                    rule: [@decode],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @decode {
+            input: [$first:tt $($rest:tt)*],
+            output: [$($out:tt)*],
+            delim: $delimtoks:tt,
+            rest: $resttoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @decode {
+                input: [$($rest)*],
+                output: [$($out)* $first],
+                delim: $delimtoks,
+                rest: $resttoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
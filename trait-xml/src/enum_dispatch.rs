@@ -0,0 +1,633 @@
+/// Parses an `<enum-dispatch></enum-dispatch>` section.
+///
+/// Expected initial call example:
+/// ```
+/// # struct Circle;
+/// # struct Square;
+/// # impl Shape for Circle {}
+/// # impl Shape for Square {}
+/// trait_xml::trait_xml_parse_enum_dispatch! {
+///     @parse {
+///         input: [
+///                     <name>AnyShape</name>
+///                     <type>Circle</type>
+///                     <type>Square</type>
+///                 </enum-dispatch>
+///             </trait>
+///         ],
+///         name: ,
+///         types: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@enumdispatchcallback],
+///             args: [
+///                 output: [[name Shape]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_enum_dispatch {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_enum_dispatch,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            name: $($name:ident)?,
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed enum dispatch
+    (
+        @parse {
+            input: [</enum-dispatch>$($rest:tt)*],
+            name: $name:ident,
+            types: [$($type:ident)+],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                enumdispatch: [enumdispatch [$name] [$($type)+]],
+            }
+        }
+    };
+
+    // Missing field(s) errors
+    (
+        @parse {
+            input: [</enum-dispatch>$($rest:tt)*],
+            name: ,
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch: no enum name given with `<name></name>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [</enum-dispatch>$($rest:tt)*],
+            name: $name:ident,
+            types: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch `",
+            stringify!($name),
+            "`: no types given with `<type></type>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse enum name
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: $name:ident,
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch: name already defined as `",
+            stringify!($name),
+            "`, but encountered a second `<name>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<name>$name:ident</name>$($rest:tt)*],
+            name: ,
+            types: $typestoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_enum_dispatch! {
+            @parse {
+                input: [$($rest)*],
+                name: $name,
+                types: $typestoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    // Parse dispatched type - it doubles as the variant's name, so it has to be an identifier
+    (
+        @parse {
+            input: [<type>$type:ident</type>$($rest:tt)*],
+            name: $($name:ident)?,
+            types: [$($types:ident)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_enum_dispatch! {
+            @parse {
+                input: [$($rest)*],
+                name: $($name)?,
+                types: [$($types)* $type],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<type>$($rest:tt)*],
+            name: $($name:ident)?,
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch: types have to be single identifiers, since they're also ",
+            "used as variant names. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Unexpected input errors
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing enum dispatch: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+}
+
+/// Expands a parsed `<enum-dispatch>` into an enum with a variant per type, `From` impls for each
+/// of them, and an implementation of the trait that forwards to whichever variant is present.
+///
+/// Expected initial call example:
+/// ```
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// struct Square(f64);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// trait_xml::trait_xml_expand_enum_dispatch! {
+///     @expand {
+///         dispatch: [[AnyShape] [Square]],
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: Shape,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [area]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [[param [self] [&Self] [ref] [] []]]
+///                     [f64]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+///
+/// assert_eq!(AnyShape::from(Square(2.0)).area(), 4.0);
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_enum_dispatch {
+    // The enum would need one set of associated types for every variant at once
+    (
+        @expand {
+            dispatch: [[$enum:ident] $typestoks:tt],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [$($att:tt)+],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding enum dispatch `",
+            stringify!($enum),
+            "` of `",
+            stringify!($name),
+            "`: traits with associated types can't be dispatched",
+        ));
+    };
+    (
+        @expand {
+            dispatch: [[$enum:ident] $typestoks:tt],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_enum_dispatch,
+                    rule: [@namescallback],
+                    args: [
+                        enum: $enum,
+                        variants: $typestoks,
+                        vis: $vistoks,
+                        unsafe: $unsafetoks,
+                        name: $name,
+                        gparams: [$($gpt)*],
+                        wc: $wct,
+                        assocconsts: $act,
+                        fns: $fnt,
+                    ],
+                ],
+            }
+        }
+    };
+
+    (
+        @namescallback {
+            enum: $enum:ident,
+            variants: $variantstoks:tt,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            wc: $wct:tt,
+            assocconsts: $act:tt,
+            fns: $fnt:tt,
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_enum_dispatch! {
+            @emit {
+                enum: $enum,
+                variants: $variantstoks,
+                // Passed along as a whole so that it can be repeated per associated function
+                dispatched: $variantstoks,
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                trait: [$name<$($lt,)* $($ty),*>],
+                gparams: $gpt,
+                wc: $wct,
+                assocconsts: $act,
+                fns: $fnt,
+            }
+        }
+    };
+
+    (
+        @emit {
+            enum: $enum:ident,
+            variants: [$($variant:ident)+],
+            dispatched: $variantstoks:tt,
+            vis: [$($vistok:tt)*],
+            unsafe: [$($unsafe:ident)?],
+            trait: [$trait:path],
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            assocconsts: [$($act:tt)*],
+            fns: [$($fnt:tt)*],
+        }
+    ) => {
+        $($vistok)* enum $enum {
+            $(
+                $variant($variant),
+            )+
+        }
+
+        $(
+            impl ::std::convert::From<$variant> for $enum {
+                fn from(value: $variant) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )+
+
+        $($unsafe)? impl<$($gpt)*> $trait for $enum
+        where
+            $($variant: $trait,)+
+            $($wct)*
+        {
+            $(
+                $crate::trait_xml_expand_enum_dispatch! {
+                    @expandac $enum $act
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_enum_dispatch! {
+                    @expandfn {
+                        enum: $enum,
+                        variants: $variantstoks,
+                        trait: [$trait],
+                        fn: $fnt,
+                    }
+                }
+            )*
+        }
+    };
+
+    // Every variant could have a different value, so constants have to stick to their default
+    (
+        @expandac $enum:ident [[$name:ident] [$type:ty] [$default:expr] $attrstoks:tt]
+    ) => {};
+    (
+        @expandac $enum:ident [[$name:ident] [$type:ty] [] $attrstoks:tt]
+    ) => {
+        compile_error!(concat!(
+            "error expanding enum dispatch `",
+            stringify!($enum),
+            "`: associated constant `",
+            stringify!($name),
+            "` needs a `<default-value></default-value>`",
+        ));
+    };
+
+    (
+        @expandfn {
+            enum: $enum:ident,
+            variants: $variantstoks:tt,
+            trait: $traittoks:tt,
+            fn: [
+                af
+                [$name:ident]
+                [
+                    unsafe: [$($unsafe:tt)?],
+                    extern: [$($extern:literal)?],
+                ]
+                [$($($gpt:tt)+)?]
+                [$([param [$arg:tt] [$type:ty] [$($recv:tt)?] $muttoks:tt $pattrstoks:tt])*]
+                $rettoks:tt
+                [$($([$($wt:tt)+])+)?]
+                $deftoks:tt
+                $docstoks:tt
+                [$([$($attr:tt)*])*]
+            ],
+        }
+    ) => {
+        $crate::trait_xml_expand_enum_dispatch! {
+            @fnret {
+                enum: $enum,
+                name: $name,
+                ret: $rettoks,
+                sig: [
+                    $(#[$($attr)*])*
+                    $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
+                        $($arg: $type),*
+                    )
+                ],
+                where: [$(where $($($wt)+,)+)?],
+                body: {
+                    enum: $enum,
+                    variants: $variantstoks,
+                    trait: $traittoks,
+                    fn: $name,
+                    // The first copy of the arg is for matching `self`, the second one is the one
+                    // that actually gets used
+                    params: [$([param [$arg] [$arg] [$($recv)?]])*],
+                },
+            }
+        }
+    };
+
+    // The variant's return value can't be turned back into the enum without knowing its type
+    (
+        @fnret {
+            enum: $enum:ident,
+            name: $name:ident,
+            ret: [Self],
+            sig: $sigtoks:tt,
+            where: $wheretoks:tt,
+            body: $bodytoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding enum dispatch `",
+            stringify!($enum),
+            "`: associated function `",
+            stringify!($name),
+            "` returns `Self`, so it can't be dispatched",
+        ));
+    };
+    (
+        @fnret {
+            enum: $enum:ident,
+            name: $name:ident,
+            ret: [$($rt:ty)?],
+            sig: [$($sig:tt)*],
+            where: [$($wheretok:tt)*],
+            body: $bodytoks:tt,
+        }
+    ) => {
+        $($sig)* $(-> $rt)?
+        $($wheretok)*
+        {
+            $crate::trait_xml_expand_enum_dispatch! {
+                @body $bodytoks
+            }
+        }
+    };
+
+    // Function bodies - the receiver has to come first, and nothing else can be `Self`
+    (
+        @body {
+            enum: $enum:ident,
+            variants: $variantstoks:tt,
+            trait: $traittoks:tt,
+            fn: $name:ident,
+            params: [
+                [param [self] [$recv:tt] [$recvkind:ident]]
+                $([param $matchtoks:tt [$arg:tt] []])*
+            ],
+        }
+    ) => {
+        $crate::trait_xml_expand_enum_dispatch! {
+            @dispatch {
+                variants: $variantstoks,
+                trait: $traittoks,
+                fn: $name,
+                recv: $recv,
+                bind: __inner,
+                call: (__inner, $($arg),*),
+            }
+        }
+    };
+    (
+        @body {
+            enum: $enum:ident,
+            variants: $variantstoks:tt,
+            trait: $traittoks:tt,
+            fn: $name:ident,
+            params: $paramstoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding enum dispatch `",
+            stringify!($enum),
+            "`: associated function `",
+            stringify!($name),
+            "` has to take `self`, `&self` or `&mut self` first and can't take `Self` anywhere else",
+        ))
+    };
+    (
+        @dispatch {
+            variants: [$($variant:ident)+],
+            trait: [$trait:path],
+            fn: $name:ident,
+            recv: $recv:tt,
+            bind: $bind:ident,
+            call: $calltoks:tt,
+        }
+    ) => {
+        match $recv {
+            $(
+                Self::$variant($bind) => <$variant as $trait>::$name $calltoks,
+            )+
+        }
+    };
+}
@@ -0,0 +1,184 @@
+/// Parses an `<example></example>` section.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_example! {
+///     @parse {
+///         input: [
+///                     <test>foo_works</test>
+///                     assert_eq!(1 + 1, 2);
+///                 </example>
+///             </trait>
+///         ],
+///         test: ,
+///         code: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@examplecallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_example {
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            test: $($test:ident)?,
+            code: $codetoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing example: expected `</example>`, found end of input. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed example
+    (
+        @parse {
+            input: [</example>$($rest:tt)*],
+            test: $($test:ident)?,
+            code: [$($code:tt)+],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                example: [[$($test)?] [$($code)+]],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [</example>$($rest:tt)*],
+            test: $($test:ident)?,
+            code: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing example: no code given. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Test name - has to come before the code, so that code containing a `<test>` still works
+    (
+        @parse {
+            input: [<test>$test:ident</test>$($rest:tt)*],
+            test: ,
+            code: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_example! {
+            @parse {
+                input: [$($rest)*],
+                test: $test,
+                code: [],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<test>$($rest:tt)*],
+            test: $($test:ident)?,
+            code: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing example: expected a single `<test></test>` containing an identifier. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Code
+    (
+        @parse {
+            input: [$first:tt$($rest:tt)*],
+            test: $($test:ident)?,
+            code: [$($code:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_example! {
+            @parse {
+                input: [$($rest)*],
+                test: $($test)?,
+                code: [$($code)* $first],
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
+
+/// Expands an example with a `<test></test>` name into a `#[cfg(test)]` module running it as a
+/// `#[test]`, with the same attributes as the function it's for. Examples without one only show up
+/// in the docs, so they expand to nothing.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_expand_example! {
+///     @expand {
+///         example: [[two_is_two] [assert_eq!(1 + 1, 2);]],
+///         attrs: [],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_example {
+    (
+        @expand {
+            example: [[] $codetoks:tt],
+            attrs: $attrstoks:tt,
+        }
+    ) => {};
+    (
+        @expand {
+            example: [[$test:ident] [$($code:tt)+]],
+            attrs: [$([$($attr:tt)*])*],
+        }
+    ) => {
+        $(#[$($attr)*])*
+        #[cfg(test)]
+        mod $test {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[test]
+            fn example() {
+                $($code)+
+            }
+        }
+    };
+}
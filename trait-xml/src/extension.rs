@@ -0,0 +1,517 @@
+/// Parses an `<extension></extension>` section.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_extension! {
+///     @parse {
+///         input: [
+///                     <for>I: Iterator<Item = u8></for>
+///                 </extension>
+///             </trait>
+///         ],
+///         for: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@extensioncallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_extension {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_extension,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            for: $fortoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed extension - either a generic parameter with bounds or a plain type
+    (
+        @parse {
+            input: [</extension>$($rest:tt)*],
+            for: [$gp:ident: $($bt:tt)+],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                extension: [extension [bound [$gp] [$($bt)+]]],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [</extension>$($rest:tt)*],
+            for: [$for:ty],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                extension: [extension [type [$for]]],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [</extension>$($rest:tt)*],
+            for: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: nothing to extend given with `<for></for>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [</extension>$($rest:tt)*],
+            for: [$($inv:tt)+],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: tokens `",
+            $(stringify!($inv)),+,
+            "` are neither a type nor a generic parameter with bounds. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse extended type or bound
+    (
+        @parse {
+            input: [<for>$($rest:tt)*],
+            for: [$($fortok:tt)+],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: already extending `",
+            $(stringify!($fortok)),+,
+            "`, but encountered a second `<for>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<for>$($rest:tt)*],
+            for: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_extension! {
+            @for {
+                input: [$($rest)*],
+                for: [],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @for {
+            input: [</for>$($rest:tt)*],
+            for: $fortoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_extension! {
+            @parse {
+                input: [$($rest)*],
+                for: $fortoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @for {
+            input: [],
+            for: $fortoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: ran out of tokens while parsing `<for>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @for {
+            input: [$first:tt$($rest:tt)*],
+            for: [$($fortok:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_extension! {
+            @for {
+                input: [$($rest)*],
+                for: [$($fortok)* $first],
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    // Unexpected input errors
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            for: $fortoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            for: $fortoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            for: $fortoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing extension: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+}
+
+/// Expands a parsed `<extension>` into the blanket implementation holding the trait's associated
+/// function bodies. The trait itself gets expanded without them.
+///
+/// Expected initial call example:
+/// ```
+/// trait NextOr0u8 {
+///     fn next_or_0u8(&mut self) -> u8;
+/// }
+///
+/// trait_xml::trait_xml_expand_extension! {
+///     @expand {
+///         extension: [bound [I] [Iterator<Item = u8>]],
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: NextOr0u8,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [next_or_0u8]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [[param [self] [&mut Self] [refmut] [] []]]
+///                     [u8]
+///                     []
+///                     [self.next().unwrap_or(0)]
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+///
+/// assert_eq!([].into_iter().next_or_0u8(), 0);
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_extension {
+    // The blanket impl would have to pick the associated types for every type at once
+    (
+        @expand {
+            extension: $exttoks:tt,
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: $gpt:tt,
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [$($att:tt)+],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding extension trait `",
+            stringify!($name),
+            "`: extension traits can't have associated types",
+        ));
+    };
+    (
+        @expand {
+            extension: $exttoks:tt,
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)*],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($gpt)*],
+                lifetimes: [],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_expand_extension,
+                    rule: [@namescallback],
+                    args: [
+                        extension: $exttoks,
+                        unsafe: $unsafetoks,
+                        name: $name,
+                        gparams: [$($gpt)*],
+                        wc: $wct,
+                        assocconsts: $act,
+                        fns: $fnt,
+                    ],
+                ],
+            }
+        }
+    };
+
+    (
+        @namescallback {
+            extension: [bound [$gp:ident] [$($bt:tt)+]],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            assocconsts: [$($act:tt)*],
+            fns: [$($fnt:tt)*],
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $($unsafe)? impl<$($gpt)* $gp: $($bt)+> $name<$($lt,)* $($ty),*> for $gp
+        where
+            $($wct)*
+        {
+            $(
+                $crate::trait_xml_expand_extension! {
+                    @expandac $name $act
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_extension! {
+                    @expandfn $name $fnt
+                }
+            )*
+        }
+    };
+    (
+        @namescallback {
+            extension: [type [$for:ty]],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            gparams: [$($gpt:tt)*],
+            wc: [$($wct:tt)*],
+            assocconsts: [$($act:tt)*],
+            fns: [$($fnt:tt)*],
+            lifetimes: [$($lt:lifetime)*],
+            types: [$($ty:ident)*],
+        }
+    ) => {
+        $($unsafe)? impl<$($gpt)*> $name<$($lt,)* $($ty),*> for $for
+        where
+            $($wct)*
+        {
+            $(
+                $crate::trait_xml_expand_extension! {
+                    @expandac $name $act
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_extension! {
+                    @expandfn $name $fnt
+                }
+            )*
+        }
+    };
+
+    // Constants keep their default, so they have to have one
+    (
+        @expandac $trait:ident [[$name:ident] [$type:ty] [$default:expr] $attrstoks:tt]
+    ) => {};
+    (
+        @expandac $trait:ident [[$name:ident] [$type:ty] [] $attrstoks:tt]
+    ) => {
+        compile_error!(concat!(
+            "error expanding extension trait `",
+            stringify!($trait),
+            "`: associated constant `",
+            stringify!($name),
+            "` needs a `<default-value></default-value>`",
+        ));
+    };
+
+    (
+        @expandfn $trait:ident [
+            af
+            [$name:ident]
+            $qualstoks:tt
+            $gptoks:tt
+            $paramstoks:tt
+            $rettoks:tt
+            $wheretoks:tt
+            []
+            $docstoks:tt
+            $attrstoks:tt
+        ]
+    ) => {
+        compile_error!(concat!(
+            "error expanding extension trait `",
+            stringify!($trait),
+            "`: associated function `",
+            stringify!($name),
+            "` needs a `<rust></rust>` body",
+        ));
+    };
+    // The docs already went on the trait's copy of the function
+    (
+        @expandfn $trait:ident [
+            af
+            $nametoks:tt
+            $qualstoks:tt
+            $gptoks:tt
+            $paramstoks:tt
+            $rettoks:tt
+            $wheretoks:tt
+            $deftoks:tt
+            $docstoks:tt
+            $attrstoks:tt
+        ]
+    ) => {
+        $crate::trait_xml_inner! {
+            @expandfn [
+                af
+                $nametoks
+                $qualstoks
+                $gptoks
+                $paramstoks
+                $rettoks
+                $wheretoks
+                $deftoks
+                []
+                $attrstoks
+            ]
+        }
+    };
+}
@@ -0,0 +1,747 @@
+/// Parses an `<ffi-vtable></ffi-vtable>` section.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_ffi_vtable! {
+///     @parse {
+///         input: [
+///                     <name>FooVTable</name>
+///                     <wrapper>FooHandle</wrapper>
+///                 </ffi-vtable>
+///             </trait>
+///         ],
+///         name: ,
+///         wrapper: ,
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@ffivtablecallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_ffi_vtable {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_ffi_vtable,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            name: $($name:ident)?,
+            wrapper: $($wrapper:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed FFI vtable
+    (
+        @parse {
+            input: [</ffi-vtable>$($rest:tt)*],
+            name: $name:ident,
+            wrapper: $wrapper:ident,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                ffivtable: [ffivtable [$name] [$wrapper]],
+            }
+        }
+    };
+
+    // Missing field errors
+    (
+        @parse {
+            input: [</ffi-vtable>$($rest:tt)*],
+            name: ,
+            wrapper: $($wrapper:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: no name given with `<name></name>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [</ffi-vtable>$($rest:tt)*],
+            name: $($name:ident)?,
+            wrapper: ,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: no wrapper given with `<wrapper></wrapper>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse name
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: $name:ident,
+            wrapper: $($wrapper:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: name already defined as `",
+            stringify!($name),
+            "`, but encountered a second `<name>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<name>$new:ident</name>$($rest:tt)*],
+            name: ,
+            wrapper: $($wrapper:ident)?,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_ffi_vtable! {
+            @parse {
+                input: [$($rest)*],
+                name: $new,
+                wrapper: $($wrapper)?,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: ,
+            wrapper: $($wrapper:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: `<name></name>` has to contain a valid identifier. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse wrapper
+    (
+        @parse {
+            input: [<wrapper>$($rest:tt)*],
+            name: $($name:ident)?,
+            wrapper: $wrapper:ident,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: wrapper already defined as `",
+            stringify!($wrapper),
+            "`, but encountered a second `<wrapper>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<wrapper>$new:ident</wrapper>$($rest:tt)*],
+            name: $($name:ident)?,
+            wrapper: ,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_ffi_vtable! {
+            @parse {
+                input: [$($rest)*],
+                name: $($name)?,
+                wrapper: $new,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<wrapper>$($rest:tt)*],
+            name: $($name:ident)?,
+            wrapper: ,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: `<wrapper></wrapper>` has to contain a valid identifier. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Unexpected input errors
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            wrapper: $($wrapper:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            wrapper: $($wrapper:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            wrapper: $($wrapper:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing FFI vtable: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+}
+
+/// Expands a parsed `<ffi-vtable>` into a `#[repr(C)]` table of `extern "C"` function pointers, a
+/// `const fn` building it for any implementor of the trait, and a wrapper calling through it.
+///
+/// Expected initial call example:
+/// ```
+/// trait Plugin {
+///     fn run(&mut self, input: u32) -> u32;
+/// }
+///
+/// trait_xml::trait_xml_expand_ffi_vtable! {
+///     @expand {
+///         vtable: [[PluginVTable] [PluginHandle]],
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: Plugin,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [run]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [
+///                         [param [self] [&mut Self] [refmut] [] []]
+///                         [param [input] [u32] [] [] []]
+///                     ]
+///                     [u32]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+///
+/// struct Doubler;
+///
+/// impl Plugin for Doubler {
+///     fn run(&mut self, input: u32) -> u32 {
+///         input * 2
+///     }
+/// }
+///
+/// const DOUBLER: PluginVTable = PluginVTable::vtable_for::<Doubler>();
+///
+/// let mut doubler = Doubler;
+/// let this = &mut doubler as *mut Doubler as *mut std::ffi::c_void;
+/// assert_eq!(unsafe { (DOUBLER.run)(this, 2) }, 4);
+/// assert_eq!(PluginHandle::new(&mut doubler).run(3), 6);
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_ffi_vtable {
+    // Trait shape errors
+    (
+        @expand {
+            vtable: [[$vtable:ident] $wrappertoks:tt],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)+],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding FFI vtable `",
+            stringify!($vtable),
+            "` of `",
+            stringify!($name),
+            "`: traits with generic parameters can't have FFI vtables",
+        ));
+    };
+    (
+        @expand {
+            vtable: [[$vtable:ident] $wrappertoks:tt],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [$($att:tt)+],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding FFI vtable `",
+            stringify!($vtable),
+            "` of `",
+            stringify!($name),
+            "`: traits with associated types can't have FFI vtables",
+        ));
+    };
+    (
+        @expand {
+            vtable: [[$vtable:ident] [$wrapper:ident]],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: [$($act:tt)*],
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        $(
+            $crate::trait_xml_expand_ffi_vtable! {
+                @checkac $vtable $act
+            }
+        )*
+
+        $crate::trait_xml_expand_ffi_vtable! {
+            @classify {
+                vtable: $vtable,
+                wrapper: $wrapper,
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                name: $name,
+                fns: $fnt,
+                entries: [],
+            }
+        }
+    };
+
+    // Constants don't go through the table, so they have to have a default
+    (
+        @checkac $vtable:ident [[$name:ident] [$type:ty] [$default:expr] $attrstoks:tt]
+    ) => {};
+    (
+        @checkac $vtable:ident [[$name:ident] [$type:ty] [] $attrstoks:tt]
+    ) => {
+        compile_error!(concat!(
+            "error expanding FFI vtable `",
+            stringify!($vtable),
+            "`: associated constant `",
+            stringify!($name),
+            "` needs a `<default-value></default-value>`",
+        ));
+    };
+
+    // Turns every associated function into a table entry, making sure it can be called through a
+    // `*mut c_void` along the way
+    (
+        @classify {
+            vtable: $vtable:ident,
+            wrapper: $wrapper:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            fns: [[af [$fname:ident] $qualstoks:tt [$($gpt:tt)+] $($frest:tt)*] $($rest:tt)*],
+            entries: $entriestoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding FFI vtable `",
+            stringify!($vtable),
+            "`: associated function `",
+            stringify!($fname),
+            "` has generic parameters, so it can't go in a vtable",
+        ));
+    };
+    (
+        @classify {
+            vtable: $vtable:ident,
+            wrapper: $wrapper:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            fns: [
+                [af [$fname:ident] $qualstoks:tt [] $paramstoks:tt [Self] $($frest:tt)*]
+                $($rest:tt)*
+            ],
+            entries: $entriestoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding FFI vtable `",
+            stringify!($vtable),
+            "`: associated function `",
+            stringify!($fname),
+            "` returns `Self`, so it can't go in a vtable",
+        ));
+    };
+    (
+        @classify {
+            vtable: $vtable:ident,
+            wrapper: $wrapper:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            fns: [
+                [
+                    af
+                    [$fname:ident]
+                    $qualstoks:tt
+                    []
+                    [[param [self] $typetoks:tt [value] $muttoks:tt $pattrstoks:tt] $($params:tt)*]
+                    $($frest:tt)*
+                ]
+                $($rest:tt)*
+            ],
+            entries: $entriestoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding FFI vtable `",
+            stringify!($vtable),
+            "`: associated function `",
+            stringify!($fname),
+            "` takes `self` by value, so it can't be called through a pointer",
+        ));
+    };
+    (
+        @classify {
+            vtable: $vtable:ident,
+            wrapper: $wrapper:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            fns: [
+                [
+                    af
+                    [$fname:ident]
+                    $qualstoks:tt
+                    []
+                    [
+                        [param [self] [$selftype:ty] [$recv:ident] $selfmut:tt $selfattrs:tt]
+                        $([param [$arg:tt] [$type:ty] [] $muttoks:tt $pattrstoks:tt])*
+                    ]
+                    $rettoks:tt
+                    $wheretoks:tt
+                    $deftoks:tt
+                    $docstoks:tt
+                    $attrstoks:tt
+                ]
+                $($rest:tt)*
+            ],
+            entries: [$($entries:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_ffi_vtable! {
+            @classify {
+                vtable: $vtable,
+                wrapper: $wrapper,
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                name: $name,
+                fns: [$($rest)*],
+                entries: [
+                    $($entries)*
+                    [[$fname] $qualstoks [$recv] [$([$arg] [$type])*] $rettoks $wheretoks $attrstoks]
+                ],
+            }
+        }
+    };
+    (
+        @classify {
+            vtable: $vtable:ident,
+            wrapper: $wrapper:ident,
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            fns: [[af [$fname:ident] $($frest:tt)*] $($rest:tt)*],
+            entries: $entriestoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding FFI vtable `",
+            stringify!($vtable),
+            "`: associated function `",
+            stringify!($fname),
+            "` has to take `&self` or `&mut self` first and can't take `Self` anywhere else",
+        ));
+    };
+    (
+        @classify {
+            vtable: $vtable:ident,
+            wrapper: $wrapper:ident,
+            vis: [$($vistok:tt)*],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
+            fns: [],
+            entries: [
+                $(
+                    [
+                        [$fname:ident]
+                        $qualstoks:tt
+                        [$recv:ident]
+                        [$([$arg:tt] [$type:ty])*]
+                        [$($rt:ty)?]
+                        $wheretoks:tt
+                        [$([$($attr:tt)*])*]
+                    ]
+                )*
+            ],
+        }
+    ) => {
+        #[repr(C)]
+        #[derive(Clone, Copy)]
+        $($vistok)* struct $vtable {
+            $(
+                $(#[$($attr)*])*
+                pub $fname: unsafe extern "C" fn(*mut ::std::ffi::c_void $(, $type)*) $(-> $rt)?,
+            )*
+        }
+
+        impl $vtable {
+            /// Builds the table for `T`. Every function in it expects a pointer to a `T`.
+            pub const fn vtable_for<T: $name>() -> Self {
+                Self {
+                    $(
+                        $(#[$($attr)*])*
+                        $fname: {
+                            unsafe extern "C" fn trampoline<T: $name>(
+                                this: *mut ::std::ffi::c_void,
+                                $($arg: $type),*
+                            ) $(-> $rt)? {
+                                unsafe {
+                                    T::$fname(
+                                        $crate::trait_xml_expand_ffi_vtable!(@this [$recv] this T),
+                                        $($arg),*
+                                    )
+                                }
+                            }
+                            trampoline::<T>
+                        },
+                    )*
+                }
+            }
+        }
+
+        $($vistok)* struct $wrapper<'a> {
+            this: *mut ::std::ffi::c_void,
+            vtable: $vtable,
+            _marker: ::std::marker::PhantomData<&'a mut ()>,
+        }
+
+        impl<'a> $wrapper<'a> {
+            /// Wraps `value`, calling its associated functions through its vtable.
+            pub fn new<T: $name>(value: &'a mut T) -> Self {
+                Self {
+                    this: value as *mut T as *mut ::std::ffi::c_void,
+                    vtable: $vtable::vtable_for::<T>(),
+                    _marker: ::std::marker::PhantomData,
+                }
+            }
+
+            /// Wraps a pointer that came from across an FFI boundary.
+            ///
+            /// # Safety
+            ///
+            /// `this` has to stay valid for `'a`, and has to be what every function in `vtable`
+            /// expects.
+            pub unsafe fn from_raw(this: *mut ::std::ffi::c_void, vtable: $vtable) -> Self {
+                Self {
+                    this,
+                    vtable,
+                    _marker: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        $($unsafe)? impl $name for $wrapper<'_> {
+            $(
+                $crate::trait_xml_expand_ffi_vtable! {
+                    @method
+                    [$fname]
+                    $qualstoks
+                    [$recv]
+                    [$([$arg] [$type])*]
+                    [$($rt)?]
+                    $wheretoks
+                    [$([$($attr)*])*]
+                }
+            )*
+        }
+    };
+
+    (@this [ref] $this:ident $t:ident) => { &*($this as *const $t) };
+    (@this [refmut] $this:ident $t:ident) => { &mut *($this as *mut $t) };
+
+    (
+        @method
+        [$fname:ident]
+        [
+            unsafe: [$($unsafe:tt)?],
+            extern: [$($extern:literal)?],
+        ]
+        [ref]
+        [$([$arg:tt] [$type:ty])*]
+        [$($rt:ty)?]
+        [$($([$($wt:tt)+])+)?]
+        [$([$($attr:tt)*])*]
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $fname(&self, $($arg: $type),*) $(-> $rt)?
+        $(where $($($wt)+,)+)?
+        {
+            unsafe { (self.vtable.$fname)(self.this, $($arg),*) }
+        }
+    };
+    (
+        @method
+        [$fname:ident]
+        [
+            unsafe: [$($unsafe:tt)?],
+            extern: [$($extern:literal)?],
+        ]
+        [refmut]
+        [$([$arg:tt] [$type:ty])*]
+        [$($rt:ty)?]
+        [$($([$($wt:tt)+])+)?]
+        [$([$($attr:tt)*])*]
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $fname(&mut self, $($arg: $type),*) $(-> $rt)?
+        $(where $($($wt)+,)+)?
+        {
+            unsafe { (self.vtable.$fname)(self.this, $($arg),*) }
+        }
+    };
+}
@@ -44,6 +44,24 @@
 /// ```
 #[macro_export]
 macro_rules! trait_xml_parse_for_bound {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_for_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     (
         @parse {
             input: [],
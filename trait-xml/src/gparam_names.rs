@@ -0,0 +1,184 @@
+/// Picks the names out of a list of generic parameters as produced by
+/// [`trait_xml_parse_bounds!`](crate::trait_xml_parse_bounds) or
+/// [`trait_xml_parse_gparams!`](crate::trait_xml_parse_gparams), so that they can be used as
+/// generic arguments (`Foo<'a, T, N>` rather than `Foo<'a: 'b, T: Clone, const N: usize>`).
+///
+/// Lifetimes are handed back separately from types and const generics, since those are the only
+/// generic arguments that it's ever safe to leave out. Unlike the parsing macros, there is no
+/// leftover input to hand back, so the callback only receives its own args plus `lifetimes` and
+/// `types`.
+///
+/// Expected initial call example:
+/// ```
+/// macro_rules! show {
+///     (@namescallback { note: [], lifetimes: [$($lt:lifetime)*], types: [$($ty:ident)*], }) => {
+///         const _: &str = stringify!($($lt)* $($ty)*);
+///     };
+/// }
+///
+/// trait_xml::trait_xml_gparam_names! {
+///     @parse {
+///         input: ['a: 'b +, T: Iterator<Item = u8> + Clone, const N: usize,],
+///         lifetimes: [],
+///         types: [],
+///         callback: [
+///             name: show,
+///             rule: [@namescallback],
+///             args: [
+///                 note: [],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_gparam_names {
+    // Done
+    (
+        @parse {
+            input: [],
+            lifetimes: $lttoks:tt,
+            types: $tytoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                lifetimes: $lttoks,
+                types: $tytoks,
+            }
+        }
+    };
+
+    // Lifetime
+    (
+        @parse {
+            input: [$lt:lifetime: $($rest:tt)*],
+            lifetimes: [$($lts:tt)*],
+            types: $tytoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @skip {
+                input: [$($rest)*],
+                lifetimes: [$($lts)* $lt],
+                types: $tytoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    // Const generic
+    (
+        @parse {
+            input: [const $name:ident: $($rest:tt)*],
+            lifetimes: $lttoks:tt,
+            types: [$($tys:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @skip {
+                input: [$($rest)*],
+                lifetimes: $lttoks,
+                types: [$($tys)* $name],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    // Generic type
+    (
+        @parse {
+            input: [$name:ident: $($rest:tt)*],
+            lifetimes: $lttoks:tt,
+            types: [$($tys:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @skip {
+                input: [$($rest)*],
+                lifetimes: $lttoks,
+                types: [$($tys)* $name],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    // Not the start of a parameter - we're in the middle of a bound's generic arguments, e.g. the
+    // `Output = u8` in `T: Add<u8, Output = u8>`
+    (
+        @parse {
+            input: [$unx:tt $($rest:tt)*],
+            lifetimes: $lttoks:tt,
+            types: $tytoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @skip {
+                input: [$($rest)*],
+                lifetimes: $lttoks,
+                types: $tytoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    // Skip bounds up to the next comma
+    (
+        @skip {
+            input: [, $($rest:tt)*],
+            lifetimes: $lttoks:tt,
+            types: $tytoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [$($rest)*],
+                lifetimes: $lttoks,
+                types: $tytoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @skip {
+            input: [$first:tt $($rest:tt)*],
+            lifetimes: $lttoks:tt,
+            types: $tytoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @skip {
+                input: [$($rest)*],
+                lifetimes: $lttoks,
+                types: $tytoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @skip {
+            input: [],
+            lifetimes: $lttoks:tt,
+            types: $tytoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_gparam_names! {
+            @parse {
+                input: [],
+                lifetimes: $lttoks,
+                types: $tytoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
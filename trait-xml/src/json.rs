@@ -0,0 +1,1876 @@
+/// Defines a trait from JSON-ish braces and colons instead of XML. It's parsed into the same IR
+/// as [`trait_xml!`](macro@crate::trait_xml) and expanded with
+/// [`trait_xml_emit!`](crate::trait_xml_emit), so the trait comes out the same either way.
+///
+/// Keys are strings, and can go in any order. Values are whatever Rust would have in that spot,
+/// unquoted, except for lists, which are `[...]`, and objects, which are `{...}`.
+///
+/// The trait object takes:
+/// - `"name"`: the trait's name
+/// - `"vis"`: its visibility
+/// - `"unsafe"`: `true` or `false`
+/// - `"gparams"`: a list of generic parameters, each one `{ "lifetime": 'a, "bounds": [...] }`,
+///   `{ "name": T, "bounds": [...] }` or `{ "const": N, "type": usize }`
+/// - `"reqs"`: a list of supertraits
+/// - `"where"`: a list of `where` clauses, each one `{ "type": T, "bounds": [...] }` or
+///   `{ "lifetime": 'a, "bounds": [...] }`
+/// - `"assoctype"`: a list of associated types, each one `{ "name": Item, "bounds": [...] }`
+/// - `"assocconst"`: a list of associated constants, each one
+///   `{ "name": LEN, "type": usize, "default": 4 }`
+/// - `"assocfn"`: a list of associated functions, see below
+///
+/// Bounds are lists of trait paths and lifetimes, e.g. `[Clone, 'a]`.
+///
+/// Each associated function takes:
+/// - `"name"`: the function's name
+/// - `"unsafe"`: `true` or `false`
+/// - `"extern"`: its ABI, e.g. `"C"`
+/// - `"gparams"` and `"where"`: same as the trait's
+/// - `"params"`: a list of parameters, each one `{ "name": x, "type": u8, "mut": true }`
+/// - `"ret"`: the return type
+/// - `"rust"`: a default body, e.g. `{ 3 }`
+///
+/// Everything else `trait_xml!` can do (extension traits, mocks, `<cfg>` and the rest) is XML
+/// only, at least for now. Every key is a macro step or few, so big traits might need a bigger
+/// `#![recursion_limit]`.
+///
+/// ```
+/// trait_xml::trait_json! {{
+///     "name": Foo,
+///     "vis": pub,
+///     "gparams": [{ "name": T, "bounds": [Clone] }],
+///     "assocfn": [
+///         {
+///             "name": bar,
+///             "params": [{ "name": self, "type": &Self }, { "name": t, "type": T }],
+///             "ret": T,
+///             "rust": { t.clone() },
+///         },
+///     ],
+/// }}
+///
+/// struct S;
+/// impl Foo<u8> for S {}
+/// assert_eq!(S.bar(3), 3);
+/// ```
+#[macro_export]
+macro_rules! trait_json {
+    ({$($input:tt)*}) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$($input)*],
+                output: [],
+                gparams: [],
+                reqs: [],
+            }
+        }
+    };
+    ($($input:tt)*) => {
+        compile_error!("error parsing JSON trait: expected a single `{...}` object");
+    };
+}
+
+/// Parses the insides of a [`trait_json!`](crate::trait_json) object into the trait IR, then hands
+/// it to [`trait_xml_emit!`](crate::trait_xml_emit).
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_json! {
+///     @trait {
+///         input: [
+///             "name": Foo,
+///             "assocfn": [{ "name": bar, "ret": u8 }],
+///         ],
+///         output: [],
+///         gparams: [],
+///         reqs: [],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_json {
+    /*
+        Trait
+    */
+    (
+        @trait {
+            input: ["name": $name:ident $(, $($rest:tt)*)?],
+            output: [$($out:tt)*],
+            gparams: $gpt:tt,
+            reqs: $rt:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$($($rest)*)?],
+                output: [$($out)* [name $name]],
+                gparams: $gpt,
+                reqs: $rt,
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["vis": $vis:vis $(, $($rest:tt)*)?],
+            output: [$($out:tt)*],
+            gparams: $gpt:tt,
+            reqs: $rt:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$($($rest)*)?],
+                output: [$($out)* [vis $vis]],
+                gparams: $gpt,
+                reqs: $rt,
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["unsafe": true $(, $($rest:tt)*)?],
+            output: [$($out:tt)*],
+            gparams: $gpt:tt,
+            reqs: $rt:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$($($rest)*)?],
+                output: [$($out)* [unsafe]],
+                gparams: $gpt,
+                reqs: $rt,
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["unsafe": false $(, $($rest:tt)*)?],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$($($rest)*)?],
+                $($state)*
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["gparams": [$($gparams:tt)*] $(, $($rest:tt)*)?],
+            output: $outtoks:tt,
+            gparams: [],
+            reqs: $rt:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparams {
+                input: [$($gparams)*],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@traitgparams],
+                    args: [
+                        input: [$($($rest)*)?],
+                        output: $outtoks,
+                        reqs: $rt,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["reqs": [$($req:path),* $(,)?] $(, $($rest:tt)*)?],
+            output: $outtoks:tt,
+            gparams: $gpt:tt,
+            reqs: [],
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$($($rest)*)?],
+                output: $outtoks,
+                gparams: $gpt,
+                reqs: [$($req +)*],
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["where": [$($clauses:tt)*] $(, $($rest:tt)*)?],
+            output: $outtoks:tt,
+            gparams: $gpt:tt,
+            reqs: $rt:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @where {
+                input: [$($clauses)*],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@traitwhere],
+                    args: [
+                        input: [$($($rest)*)?],
+                        output: $outtoks,
+                        gparams: $gpt,
+                        reqs: $rt,
+                    ],
+                ],
+            }
+        }
+    };
+    // Lists of items get spread out into the input, one marker per item, so they can be parsed
+    // one at a time
+    (
+        @trait {
+            input: ["assoctype": [$($item:tt),* $(,)?] $(, $($rest:tt)*)?],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$(@assoctype $item)* $($($rest)*)?],
+                $($state)*
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["assocconst": [$($item:tt),* $(,)?] $(, $($rest:tt)*)?],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$(@assocconst $item)* $($($rest)*)?],
+                $($state)*
+            }
+        }
+    };
+    (
+        @trait {
+            input: ["assocfn": [$($item:tt),* $(,)?] $(, $($rest:tt)*)?],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: [$(@assocfn $item)* $($($rest)*)?],
+                $($state)*
+            }
+        }
+    };
+    (
+        @trait {
+            input: [@assoctype {$($item:tt)*} $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assoctype {
+                input: [$($item)*],
+                name: [],
+                bounds: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@itemcallback],
+                    args: [
+                        input: [$($rest)*],
+                        $($state)*
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @trait {
+            input: [@assocconst {$($item:tt)*} $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocconst {
+                input: [$($item)*],
+                name: [],
+                type: [],
+                default: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@itemcallback],
+                    args: [
+                        input: [$($rest)*],
+                        $($state)*
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @trait {
+            input: [@assocfn {$($item:tt)*} $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: [$($item)*],
+                name: [],
+                unsafe: [],
+                extern: [],
+                gparams: [],
+                params: [],
+                ret: [],
+                where: [],
+                def: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@itemcallback],
+                    args: [
+                        input: [$($rest)*],
+                        $($state)*
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @trait {
+            input: [@$kind:ident $item:tt $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON trait: expected `\"",
+            stringify!($kind),
+            "\"` to be a list of objects, found `",
+            stringify!($item),
+            "`",
+        ));
+    };
+    (
+        @trait {
+            input: [$key:literal: $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON trait: unknown key, repeated key or bad value for `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (
+        @trait {
+            input: [],
+            output: [$($out:tt)*],
+            gparams: [$($gpt:tt)*],
+            reqs: [$($rt:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_emit! {
+            version: 1,
+            ir: [
+                [gparams [$($gpt)*] reqs [$($rt)*]]
+                $($out)*
+            ],
+        }
+    };
+    (
+        @trait {
+            input: [$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON trait: expected a `\"key\": value` pair, found `",
+            stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (
+        @traitgparams {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            reqs: $rt:tt,
+            gparams: $gpt:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: $inputtoks,
+                output: $outtoks,
+                gparams: $gpt,
+                reqs: $rt,
+            }
+        }
+    };
+    (
+        @traitwhere {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            gparams: $gpt:tt,
+            reqs: $rt:tt,
+            where: [$($clause:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: $inputtoks,
+                output: [$($out)* [wc $($clause)*]],
+                gparams: $gpt,
+                reqs: $rt,
+            }
+        }
+    };
+    (
+        @itemcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            gparams: $gpt:tt,
+            reqs: $rt:tt,
+            item: $itemtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @trait {
+                input: $inputtoks,
+                output: [$($out)* $itemtoks],
+                gparams: $gpt,
+                reqs: $rt,
+            }
+        }
+    };
+
+    /*
+        Generic parameters
+    */
+    (
+        @gparams {
+            input: [],
+            output: [$($out:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                gparams: [$($out)*],
+            }
+        }
+    };
+    (
+        @gparams {
+            input: [{$($gparam:tt)*} $(, $($rest:tt)*)?],
+            output: $outtoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparam {
+                input: [$($gparam)*],
+                lifetime: [],
+                name: [],
+                const: [],
+                type: [],
+                bounds: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@gparamcallback],
+                    args: [
+                        input: [$($($rest)*)?],
+                        output: $outtoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @gparams {
+            input: [$($rest:tt)+],
+            output: $outtoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON generic parameters: expected an object, found `",
+            stringify!($($rest)+),
+            "`",
+        ));
+    };
+    (
+        @gparamcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            callback: $callbacktoks:tt,
+            bounded: [$($gparam:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparams {
+                input: $inputtoks,
+                output: [$($out)* $($gparam)*,],
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    (
+        @gparam {
+            input: ["lifetime": $lt:lifetime $(, $($rest:tt)*)?],
+            lifetime: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparam {
+                input: [$($($rest)*)?],
+                lifetime: [$lt],
+                $($state)*
+            }
+        }
+    };
+    (
+        @gparam {
+            input: ["name": $name:ident $(, $($rest:tt)*)?],
+            lifetime: $lttoks:tt,
+            name: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparam {
+                input: [$($($rest)*)?],
+                lifetime: $lttoks,
+                name: [$name],
+                $($state)*
+            }
+        }
+    };
+    (
+        @gparam {
+            input: ["const": $name:ident $(, $($rest:tt)*)?],
+            lifetime: $lttoks:tt,
+            name: $nametoks:tt,
+            const: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparam {
+                input: [$($($rest)*)?],
+                lifetime: $lttoks,
+                name: $nametoks,
+                const: [$name],
+                $($state)*
+            }
+        }
+    };
+    (
+        @gparam {
+            input: ["type": $type:ty $(, $($rest:tt)*)?],
+            lifetime: $lttoks:tt,
+            name: $nametoks:tt,
+            const: $consttoks:tt,
+            type: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparam {
+                input: [$($($rest)*)?],
+                lifetime: $lttoks,
+                name: $nametoks,
+                const: $consttoks,
+                type: [$type],
+                $($state)*
+            }
+        }
+    };
+    (
+        @gparam {
+            input: ["bounds": [$($bound:tt)*] $(, $($rest:tt)*)?],
+            lifetime: $lttoks:tt,
+            name: $nametoks:tt,
+            const: $consttoks:tt,
+            type: $typetoks:tt,
+            bounds: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparam {
+                input: [$($($rest)*)?],
+                lifetime: $lttoks,
+                name: $nametoks,
+                const: $consttoks,
+                type: $typetoks,
+                bounds: [[$($bound)*]],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @gparam {
+            input: [],
+            lifetime: [$lt:lifetime],
+            name: [],
+            const: [],
+            type: [],
+            bounds: [$($boundtoks:tt)?],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @bounds {
+                input: [$($boundtoks)?],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@boundedcallback],
+                    args: [
+                        head: [$lt],
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @gparam {
+            input: [],
+            lifetime: [],
+            name: [$name:ident],
+            const: [],
+            type: [],
+            bounds: [$($boundtoks:tt)?],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @bounds {
+                input: [$($boundtoks)?],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@boundedcallback],
+                    args: [
+                        head: [$name],
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @gparam {
+            input: [],
+            lifetime: [],
+            name: [],
+            const: [$name:ident],
+            type: [$type:ty],
+            bounds: [],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                bounded: [const $name: $type],
+            }
+        }
+    };
+    (
+        @gparam {
+            input: [$key:literal: $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON generic parameter: unknown key, repeated key or bad value for `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (
+        @gparam {
+            input: [],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON generic parameter: expected `\"lifetime\"`, `\"name\"`, or ",
+            "`\"const\"` and `\"type\"`",
+        ));
+    };
+    (
+        @gparam {
+            input: [$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON generic parameter: expected a `\"key\": value` pair, found `",
+            stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (
+        @boundedcallback {
+            head: [$($head:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+            bounds: [$($bound:tt)*],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                bounded: [$($head)*: $($bound)*],
+            }
+        }
+    };
+
+    /*
+        Bounds
+    */
+    (
+        @bounds {
+            input: [],
+            output: [$($out:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                bounds: [$($out)*],
+            }
+        }
+    };
+    (
+        @bounds {
+            input: [[$($bound:tt)*]],
+            output: $outtoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @bounds {
+                input: [$($bound)*],
+                output: $outtoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @bounds {
+            input: [$lt:lifetime $(, $($rest:tt)*)?],
+            output: [$($out:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @bounds {
+                input: [$($($rest)*)?],
+                output: [$($out)* $lt +],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @bounds {
+            input: [$bound:path $(, $($rest:tt)*)?],
+            output: [$($out:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @bounds {
+                input: [$($($rest)*)?],
+                output: [$($out)* $bound +],
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    /*
+        `where` clauses
+    */
+    (
+        @where {
+            input: [],
+            output: [$($out:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                where: [$($out)*],
+            }
+        }
+    };
+    (
+        @where {
+            input: [{$($clause:tt)*} $(, $($rest:tt)*)?],
+            output: $outtoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @clause {
+                input: [$($clause)*],
+                head: [],
+                bounds: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@clausecallback],
+                    args: [
+                        input: [$($($rest)*)?],
+                        output: $outtoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @where {
+            input: [$($rest:tt)+],
+            output: $outtoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON `where` clauses: expected an object, found `",
+            stringify!($($rest)+),
+            "`",
+        ));
+    };
+    (
+        @clausecallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            callback: $callbacktoks:tt,
+            bounded: [$($clause:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @where {
+                input: $inputtoks,
+                output: [$($out)* [$($clause)*]],
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    (
+        @clause {
+            input: ["lifetime": $lt:lifetime $(, $($rest:tt)*)?],
+            head: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @clause {
+                input: [$($($rest)*)?],
+                head: [$lt],
+                $($state)*
+            }
+        }
+    };
+    (
+        @clause {
+            input: ["type": $type:ty $(, $($rest:tt)*)?],
+            head: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @clause {
+                input: [$($($rest)*)?],
+                head: [$type],
+                $($state)*
+            }
+        }
+    };
+    (
+        @clause {
+            input: ["bounds": [$($bound:tt)*] $(, $($rest:tt)*)?],
+            head: $headtoks:tt,
+            bounds: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @clause {
+                input: [$($($rest)*)?],
+                head: $headtoks,
+                bounds: [[$($bound)*]],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @clause {
+            input: [],
+            head: [$head:tt],
+            bounds: [$($boundtoks:tt)?],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @bounds {
+                input: [$($boundtoks)?],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@boundedcallback],
+                    args: [
+                        head: [$head],
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @clause {
+            input: [$key:literal: $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON `where` clause: unknown key, repeated key or bad value for `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (
+        @clause {
+            input: [],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(
+            "error parsing JSON `where` clause: expected a `\"type\"` or `\"lifetime\"`",
+        );
+    };
+    (
+        @clause {
+            input: [$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON `where` clause: expected a `\"key\": value` pair, found `",
+            stringify!($($rest)*),
+            "`",
+        ));
+    };
+
+    /*
+        Associated types
+    */
+    (
+        @assoctype {
+            input: ["name": $name:ident $(, $($rest:tt)*)?],
+            name: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assoctype {
+                input: [$($($rest)*)?],
+                name: [$name],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assoctype {
+            input: ["bounds": [$($bound:tt)*] $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            bounds: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assoctype {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                bounds: [[$($bound)*]],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @assoctype {
+            input: [],
+            name: [$name:ident],
+            bounds: [$($boundtoks:tt)?],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @bounds {
+                input: [$($boundtoks)?],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@assoctypecallback],
+                    args: [
+                        name: [$name],
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @assoctype {
+            input: [$key:literal: $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON associated type: unknown key, repeated key or bad value for `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (
+        @assoctype {
+            input: [],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!("error parsing JSON associated type: expected a `\"name\"`");
+    };
+    (
+        @assoctype {
+            input: [$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON associated type: expected a `\"key\": value` pair, found `",
+            stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (
+        @assoctypecallback {
+            name: $nametoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+            bounds: $boundstoks:tt,
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                item: [at $nametoks [] $boundstoks [] []],
+            }
+        }
+    };
+
+    /*
+        Associated constants
+    */
+    (
+        @assocconst {
+            input: ["name": $name:ident $(, $($rest:tt)*)?],
+            name: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocconst {
+                input: [$($($rest)*)?],
+                name: [$name],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assocconst {
+            input: ["type": $type:ty $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            type: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocconst {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                type: [$type],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assocconst {
+            input: ["default": $default:expr $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            type: $typetoks:tt,
+            default: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocconst {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                type: $typetoks,
+                default: [$default],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @assocconst {
+            input: [],
+            name: [$name:ident],
+            type: [$type:ty],
+            default: $defaulttoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                item: [ac [$name] [$type] $defaulttoks []],
+            }
+        }
+    };
+    (
+        @assocconst {
+            input: [$key:literal: $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON associated constant: unknown key, repeated key or bad value for `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (
+        @assocconst {
+            input: [],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(
+            "error parsing JSON associated constant: expected a `\"name\"` and `\"type\"`",
+        );
+    };
+    (
+        @assocconst {
+            input: [$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON associated constant: expected a `\"key\": value` pair, found `",
+            stringify!($($rest)*),
+            "`",
+        ));
+    };
+
+    /*
+        Associated functions
+    */
+    (
+        @assocfn {
+            input: ["name": $name:ident $(, $($rest:tt)*)?],
+            name: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: [$($($rest)*)?],
+                name: [$name],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["unsafe": true $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            unsafe: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                unsafe: [unsafe],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["unsafe": false $(, $($rest:tt)*)?],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: [$($($rest)*)?],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["extern": $abi:literal $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                unsafe: $unsafetoks,
+                extern: [$abi],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["gparams": [$($gparams:tt)*] $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: [],
+            params: $paramstoks:tt,
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            def: $deftoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @gparams {
+                input: [$($gparams)*],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@fngparams],
+                    args: [
+                        input: [$($($rest)*)?],
+                        name: $nametoks,
+                        unsafe: $unsafetoks,
+                        extern: $externtoks,
+                        params: $paramstoks,
+                        ret: $rettoks,
+                        where: $wheretoks,
+                        def: $deftoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["params": [$($param:tt),* $(,)?] $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: $gpt:tt,
+            params: [],
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            def: $deftoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @params {
+                input: [$($param)*],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@fnparams],
+                    args: [
+                        input: [$($($rest)*)?],
+                        name: $nametoks,
+                        unsafe: $unsafetoks,
+                        extern: $externtoks,
+                        gparams: $gpt,
+                        ret: $rettoks,
+                        where: $wheretoks,
+                        def: $deftoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["ret": $ret:ty $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: $gpt:tt,
+            params: $paramstoks:tt,
+            ret: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                unsafe: $unsafetoks,
+                extern: $externtoks,
+                gparams: $gpt,
+                params: $paramstoks,
+                ret: [$ret],
+                $($state)*
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["where": [$($clauses:tt)*] $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: $gpt:tt,
+            params: $paramstoks:tt,
+            ret: $rettoks:tt,
+            where: [],
+            def: $deftoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @where {
+                input: [$($clauses)*],
+                output: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@fnwhere],
+                    args: [
+                        input: [$($($rest)*)?],
+                        name: $nametoks,
+                        unsafe: $unsafetoks,
+                        extern: $externtoks,
+                        gparams: $gpt,
+                        params: $paramstoks,
+                        ret: $rettoks,
+                        def: $deftoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: ["rust": {$($def:tt)*} $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: $gpt:tt,
+            params: $paramstoks:tt,
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            def: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                unsafe: $unsafetoks,
+                extern: $externtoks,
+                gparams: $gpt,
+                params: $paramstoks,
+                ret: $rettoks,
+                where: $wheretoks,
+                def: [$($def)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: [],
+            name: [$name:ident],
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: $gpt:tt,
+            params: $paramstoks:tt,
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            def: $deftoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                item: [
+                    af
+                    [$name]
+                    [
+                        unsafe: $unsafetoks,
+                        extern: $externtoks,
+                    ]
+                    $gpt
+                    $paramstoks
+                    $rettoks
+                    $wheretoks
+                    $deftoks
+                    []
+                    []
+                ],
+            }
+        }
+    };
+    (
+        @assocfn {
+            input: [$key:literal: $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON associated function: unknown key, repeated key or bad value for `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (
+        @assocfn {
+            input: [],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!("error parsing JSON associated function: expected a `\"name\"`");
+    };
+    (
+        @assocfn {
+            input: [$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON associated function: expected a `\"key\": value` pair, found `",
+            stringify!($($rest)*),
+            "`",
+        ));
+    };
+    (
+        @fngparams {
+            input: $inputtoks:tt,
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            params: $paramstoks:tt,
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            def: $deftoks:tt,
+            callback: $callbacktoks:tt,
+            gparams: $gpt:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: $inputtoks,
+                name: $nametoks,
+                unsafe: $unsafetoks,
+                extern: $externtoks,
+                gparams: $gpt,
+                params: $paramstoks,
+                ret: $rettoks,
+                where: $wheretoks,
+                def: $deftoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @fnparams {
+            input: $inputtoks:tt,
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: $gpt:tt,
+            ret: $rettoks:tt,
+            where: $wheretoks:tt,
+            def: $deftoks:tt,
+            callback: $callbacktoks:tt,
+            params: $paramstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: $inputtoks,
+                name: $nametoks,
+                unsafe: $unsafetoks,
+                extern: $externtoks,
+                gparams: $gpt,
+                params: $paramstoks,
+                ret: $rettoks,
+                where: $wheretoks,
+                def: $deftoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @fnwhere {
+            input: $inputtoks:tt,
+            name: $nametoks:tt,
+            unsafe: $unsafetoks:tt,
+            extern: $externtoks:tt,
+            gparams: $gpt:tt,
+            params: $paramstoks:tt,
+            ret: $rettoks:tt,
+            def: $deftoks:tt,
+            callback: $callbacktoks:tt,
+            where: $wheretoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @assocfn {
+                input: $inputtoks,
+                name: $nametoks,
+                unsafe: $unsafetoks,
+                extern: $externtoks,
+                gparams: $gpt,
+                params: $paramstoks,
+                ret: $rettoks,
+                where: $wheretoks,
+                def: $deftoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    /*
+        Associated function parameters
+    */
+    (
+        @params {
+            input: [],
+            output: [$($out:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                params: [$($out)*],
+            }
+        }
+    };
+    (
+        @params {
+            input: [{$($param:tt)*} $($rest:tt)*],
+            output: $outtoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($param)*],
+                name: [],
+                type: [],
+                recv: [],
+                mut: [],
+                callback: [
+                    name: $crate::trait_xml_parse_json,
+                    rule: [@paramcallback],
+                    args: [
+                        input: [$($rest)*],
+                        output: $outtoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @params {
+            input: [$first:tt $($rest:tt)*],
+            output: $outtoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON parameters: expected an object, found `",
+            stringify!($first),
+            "`",
+        ));
+    };
+    (
+        @paramcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            callback: $callbacktoks:tt,
+            param: $paramtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @params {
+                input: $inputtoks,
+                output: [$($out)* $paramtoks],
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    (
+        @param {
+            input: ["name": $name:ident $(, $($rest:tt)*)?],
+            name: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($($rest)*)?],
+                name: [$name],
+                $($state)*
+            }
+        }
+    };
+    // Receivers get spotted by their type, same as in `<param>`
+    (
+        @param {
+            input: ["type": &mut Self $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            type: [],
+            recv: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                type: [&mut Self],
+                recv: [refmut],
+                $($state)*
+            }
+        }
+    };
+    (
+        @param {
+            input: ["type": &Self $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            type: [],
+            recv: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                type: [&Self],
+                recv: [ref],
+                $($state)*
+            }
+        }
+    };
+    (
+        @param {
+            input: ["type": Self $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            type: [],
+            recv: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                type: [Self],
+                recv: [value],
+                $($state)*
+            }
+        }
+    };
+    (
+        @param {
+            input: ["type": $type:ty $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            type: [],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                type: [$type],
+                $($state)*
+            }
+        }
+    };
+    (
+        @param {
+            input: ["mut": true $(, $($rest:tt)*)?],
+            name: $nametoks:tt,
+            type: $typetoks:tt,
+            recv: $recvtoks:tt,
+            mut: [],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($($rest)*)?],
+                name: $nametoks,
+                type: $typetoks,
+                recv: $recvtoks,
+                mut: [mut],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @param {
+            input: ["mut": false $(, $($rest:tt)*)?],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_parse_json! {
+            @param {
+                input: [$($($rest)*)?],
+                $($state)*
+            }
+        }
+    };
+    (
+        @param {
+            input: [],
+            name: [self],
+            type: [$($type:tt)+],
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                param: [param [self] [$($type)+] $recvtoks $muttoks []],
+            }
+        }
+    };
+    (
+        @param {
+            input: [],
+            name: [$name:ident],
+            type: [$($type:tt)+],
+            recv: $recvtoks:tt,
+            mut: $muttoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                param: [param [$name] [$($type)+] [] $muttoks []],
+            }
+        }
+    };
+    (
+        @param {
+            input: [$key:literal: $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON parameter: unknown key, repeated key or bad value for `",
+            stringify!($key),
+            "`",
+        ));
+    };
+    (
+        @param {
+            input: [],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!("error parsing JSON parameter: expected a `\"name\"` and `\"type\"`");
+    };
+    (
+        @param {
+            input: [$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing JSON parameter: expected a `\"key\": value` pair, found `",
+            stringify!($($rest)*),
+            "`",
+        ));
+    };
+}
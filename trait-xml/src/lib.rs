@@ -37,6 +37,22 @@
 //!   - `<assoctype></assoctype>`
 //!   - `<assocconst></assocconst>`
 //!   - `<assocfn></assocfn>`
+//!   - `<example></example>`
+//!   - `<cfg></cfg>`
+//!   - `<rust></rust>`
+//!   - `<extension></extension>`
+//!   - `<delegate></delegate>`
+//!   - `<descriptor></descriptor>`
+//!   - `<mock></mock>`
+//!   - `<dyn-safe/>`
+//!   - `<dyn-companion></dyn-companion>`
+//!   - `<enum-dispatch></enum-dispatch>`
+//!   - `<ffi-vtable></ffi-vtable>`
+//!   - `<alias/>`
+//!   - `<closure-impl/>`
+//!   - `<namespace/>`
+//!   - `<sealed/>`
+//!   - `<sealed-for></sealed-for>`
 //!
 //! Let's talk about what tags are valid within each of those contexts.
 //!
@@ -85,6 +101,10 @@
 //! - Const generics: `<const></const>`
 //! - Supertraits: `<req></req>`
 //!
+//! An empty `<bounds/>` is allowed too, and does nothing. Handy when the XML comes out of some
+//! template that might not have anything to put there. It's still the trait's one `<bounds>`
+//! section, so there can't be another one.
+//!
 //! Here's the requirements for each of them.
 //!
 //! #### Lifetimes
@@ -292,12 +312,16 @@
 //! `<lifetime-clause></lifetime-clause>`, or `<for-clause></for-clause>`. See the subsections for
 //! each of these to learn what is required and/or allowed in each of them.
 //!
+//! `<where></where>` with nothing in it is an error, but `<where/>` is fine and means no `where`
+//! clause at all. It still counts as the trait's one `where` section though, so it can't go
+//! alongside a `<where></where>`.
+//!
 //! #### Type clauses
 //!
 //! Type clauses are denoted by `<type-clause></type-clause>` and require:
 //!
 //! - Exactly one `<type></type>` containing a valid type that is bound by...
-//! - Zero or more `<type-bound></type-bound>`, `<lifetime-bound></lifetime-bound>`, or
+//! - One or more `<type-bound></type-bound>`, `<lifetime-bound></lifetime-bound>`, or
 //! `<for-bound></for-bound>`
 //!
 //! For example:
@@ -330,7 +354,7 @@
 //! Lifetime clauses are denoted by `<lifetime-clause></lifetime-clause>` and require:
 //!
 //! - Exactly one `<lifetime></lifetime>` containing a valid lifetime that is bound by...
-//! - Zero or more `<lifetime-bound></lifetime-bound>`
+//! - One or more `<lifetime-bound></lifetime-bound>`
 //!
 //! For example:
 //! ```
@@ -491,6 +515,10 @@
 //! - Zero or one `<where></where>`
 //! - Zero or one `<rust></rust>`
 //!
+//! `<gparams/>`, `<params/>` and `<where/>` can stand in for empty sections, and count as that
+//! section's one appearance. `<assoctype>` doesn't take `<bounds/>` or `<where/>` - leave those
+//! out instead.
+//!
 //! For example:
 //! ```
 //! trait_xml::trait_xml! {
@@ -549,6 +577,16 @@
 //!   - `<name></name>` requires a valid identifier
 //! - Exactly one `<type></type>`
 //!
+//! and can also have:
+//!
+//! - A `<mut/>`, making the binding mutable. Only allowed if a function body is provided, since
+//!   there's nothing to mutate it in otherwise
+//! - Any number of `<attr></attr>`s, each containing what you'd put inside `#[]`
+//!
+//! Both only apply to the trait's own declaration - generated impls (delegates, mocks, etc.) bring
+//! their own bodies and take the parameter as-is. That means a `#[cfg]`'d parameter won't play nice
+//! with them.
+//!
 //! The shorthands `self`, `&self`, `&mut self`, `&'lifetime self`, and `&'lifetime mut self` are
 //! not allowed and must be expressed using their expanded forms with `Self`.
 //!
@@ -575,6 +613,44 @@
 //!     fn bar(self: Self);
 //! }
 //! ```
+//!
+//! And with a body:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Foo</name>
+//!         <assocfn>
+//!             <name>bar</name>
+//!             <params>
+//!                 <param>
+//!                     <name>buf</name>
+//!                     <type>Vec<u8></type>
+//!                     <mut/>
+//!                 </param>
+//!                 <param>
+//!                     <name>unused</name>
+//!                     <type>u8</type>
+//!                     <attr>allow(unused_variables)</attr>
+//!                 </param>
+//!             </params>
+//!             <ret>Vec<u8></ret>
+//!             <rust>
+//!                 buf.push(0);
+//!                 buf
+//!             </rust>
+//!         </assocfn>
+//!     </trait>
+//! }
+//! ```
+//! This expands to
+//! ```
+//! trait Foo {
+//!     fn bar(mut buf: Vec<u8>, #[allow(unused_variables)] unused: u8) -> Vec<u8> {
+//!         buf.push(0);
+//!         buf
+//!     }
+//! }
+//! ```
 //! 
 //! #### Associated function return type
 //! 
@@ -635,96 +711,1205 @@
 //! }
 //! ```
 //!
-//! ## Meta items??????
+//! ## Doc examples
 //!
-//! LMFAO
+//! You've probably noticed by now that examples are the only way anyone understands anything in
+//! this crate, so your traits get them too. `<example></example>` works directly inside `<trait>`
+//! or inside an `<assocfn>`, and everything between the tags ends up in a fenced code block in the
+//! trait's or function's docs, which means rustdoc runs it as a doctest. It can also have:
+//! - A `<test></test>` before the code, containing an identifier
 //!
-//! NO
+//! which also runs the code as a `#[test]` in a `#[cfg(test)]` module with that name, right next to
+//! the trait. The module glob imports its parent, but doctests don't get that luxury - if the
+//! example has to `use your_crate::YourTrait`, stick `extern crate self as your_crate;` in your
+//! crate root so the path works in both places.
 //!
-//! YOURE WRITING TRAITS WITH XML BOZO
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Double</name>
+//!         <example>
+//!             <test>double_u8</test>
+//!             assert_eq!(4u8.double(), 8);
+//!         </example>
+//!         <assocfn>
+//!             <name>double</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>Self</type>
+//!                 </param>
+//!             </params>
+//!             <ret>Self</ret>
+//!         </assocfn>
+//!     </trait>
+//! }
 //!
-//! (i didnt feel like doing it)
+//! impl Double for u8 {
+//!     fn double(self) -> Self {
+//!         self * 2
+//!     }
+//! }
+//! ```
+//! expands to
+//! ```ignore
+//! /// ```
+//! /// assert_eq!(4u8.double(), 8);
+//! /// ```
+//! trait Double {
+//!     fn double(self) -> Self;
+//! }
 //!
-//! # Examples
+//! #[cfg(test)]
+//! mod double_u8 {
+//!     #[allow(unused_imports)]
+//!     use super::*;
 //!
-//! For some examples, let's rewrite some `std` traits in XML. [`Index`](std::ops::Index) could be
-//! written as the following using the syntax described by the above:
+//!     #[test]
+//!     fn example() {
+//!         assert_eq!(4u8.double(), 8);
+//!     }
+//! }
+//! ```
+//!
+//! ## Conditional compilation
+//!
+//! Sticking a `#[cfg]` on every item that only exists under `unix` or `feature = "async"` gets old
+//! fast, so `<cfg></cfg>` puts one on a whole group of them. It goes directly inside `<trait>`,
+//! and needs:
+//! - A `<when></when>`, first, containing the predicate you'd put inside `#[cfg()]`
+//!
+//! followed by any number of `<assocfn>`, `<assoctype>`, `<assocconst>` and more `<cfg>`s. Nested
+//! `<cfg>`s each add their own `#[cfg]`, so the predicates end up ANDed together. Generated impls
+//! (delegates, mocks, and friends) get the same attributes on the same items. The predicate is a
+//! child tag rather than a `pred="..."` attribute because a string can't be turned back into
+//! tokens by `macro_rules!`.
+//!
+//! A couple of things don't look at the `<cfg>`s: descriptors list every item regardless, and
+//! `<dyn-safe/>` checks all of them.
+//!
+//! For example:
 //! ```
 //! trait_xml::trait_xml! {
 //!     <trait>
-//!         <name>IndexButXml</name>
-//!         <vis>pub</vis>
-//!         <bounds>
-//!             <type>
-//!                 <name>Idx</name>
-//!                 <type-bound>?Sized</type-bound>
-//!             </type>
-//!         </bounds>
-//!         <assoctype>
-//!             <name>Output</name>
-//!             <bounds>
-//!                 <req>?Sized</req>
-//!             </bounds>
-//!         </assoctype>
+//!         <name>Fs</name>
+//!         <cfg>
+//!             <when>unix</when>
+//!             <assocfn>
+//!                 <name>mode</name>
+//!                 <params>
+//!                     <param>
+//!                         <name>self</name>
+//!                         <type>&Self</type>
+//!                     </param>
+//!                 </params>
+//!                 <ret>u32</ret>
+//!             </assocfn>
+//!             <cfg>
+//!                 <when>feature = "xattr"</when>
+//!                 <assocconst>
+//!                     <name>XATTRS</name>
+//!                     <type>bool</type>
+//!                 </assocconst>
+//!             </cfg>
+//!         </cfg>
+//!     </trait>
+//! }
+//! ```
+//! expands to
+//! ```
+//! trait Fs {
+//!     #[cfg(unix)]
+//!     fn mode(&self) -> u32;
+//!     #[cfg(feature = "xattr")]
+//!     #[cfg(unix)]
+//!     const XATTRS: bool;
+//! }
+//! ```
+//!
+//! ## Raw Rust items
+//!
+//! Not everything that can go in a trait has a tag - macro invocations that expand to trait items,
+//! say, or syntax that's still on nightly. Rather than giving up on `trait_xml!` for one of those,
+//! put it in a `<rust></rust>` directly inside `<trait>`, and it gets pasted into the trait body
+//! as-is.
+//!
+//! Where it goes in the body doesn't depend on where the `<rust>` was in the `<trait>` though:
+//! both `trait_xml!` and [`build`] write the associated types first, then the consts, then the
+//! functions, and only then the `<rust>` items, in the order they were written. The compiler
+//! doesn't care about item order, but rustdoc lists required and provided methods in source
+//! order, so a `getter!` written above everything else still shows up after the tagged functions.
+//!
+//! Nothing else gets to look inside them though, so delegates, mocks and the rest of the gang
+//! won't know they exist. Anything in there that implementors have to provide means you're writing
+//! those impls yourself.
+//!
+//! For example:
+//! ```
+//! macro_rules! getter {
+//!     ($name:ident: $ty:ty) => {
+//!         fn $name(&self) -> $ty;
+//!     };
+//! }
+//!
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Point</name>
+//!         <rust>
+//!             getter!(x: f64);
+//!             getter!(y: f64);
+//!         </rust>
 //!         <assocfn>
-//!             <name>index</name>
+//!             <name>norm</name>
 //!             <params>
 //!                 <param>
 //!                     <name>self</name>
 //!                     <type>&Self</type>
 //!                 </param>
 //!             </params>
+//!             <ret>f64</ret>
+//!             <rust>self.x().hypot(self.y())</rust>
 //!         </assocfn>
 //!     </trait>
 //! }
 //! ```
-//! [`AsRef`](std::convert::AsRef) could be written as
+//! expands to
+//! ```
+//! # macro_rules! getter {
+//! #     ($name:ident: $ty:ty) => {
+//! #         fn $name(&self) -> $ty;
+//! #     };
+//! # }
+//! trait Point {
+//!     fn norm(&self) -> f64 {
+//!         self.x().hypot(self.y())
+//!     }
+//!
+//!     getter!(x: f64);
+//!     getter!(y: f64);
+//! }
+//! ```
+//!
+//! ## Attribute shorthand
+//!
+//! Child elements can be written as XML attributes on their parent's opening tag instead:
+//! - a bare attribute is a flag, so `unsafe` on `<trait>` or `<assocfn>` is `<unsafe/>` and `mut`
+//!   on `<param>` is `<mut/>`
+//! - `extern="ABI"` on `<assocfn>` is `<extern>"ABI"</extern>`
+//! - anything else with a value is the element with that value inside, so `name=Foo` is
+//!   `<name>Foo</name>`, `vis=pub(crate)` is `<vis>pub(crate)</vis>` and `type=u8` is
+//!   `<type>u8</type>`
+//!
+//! Values other than `extern`'s go without quotes - `name="Foo"` would need `Foo` dug back out of a
+//! string literal, which `macro_rules!` has no way of doing. A value is one token tree, so anything
+//! longer goes in braces, which get dropped: `type={&mut Self}`. A tag with nothing but attributes
+//! can close itself, like `<param name=x type=u8/>`.
+//!
+//! That's only for specs written straight into `trait_xml!` though. The real `.xml` files that
+//! `trait-xml-include` and [`build`] read take their quotes off before `trait_xml!` sees them, so
+//! they can be written like any other XML: `name="Foo"`, `type="&amp;mut Self"`, and
+//! `unsafe="unsafe"` for a flag.
+//!
+//! This works on `<trait>`, `<assocfn>`, `<param>`, `<mock>`, `<dyn-companion>`, `<enum-dispatch>`
+//! and `<ffi-vtable>`.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait unsafe>
+//!         <name>Raw</name>
+//!         <assocfn unsafe extern="C">
+//!             <name>bump</name>
+//!             <params>
+//!                 <param mut>
+//!                     <name>x</name>
+//!                     <type>u8</type>
+//!                 </param>
+//!             </params>
+//!             <ret>u8</ret>
+//!             <rust>
+//!                 x += 1;
+//!                 x
+//!             </rust>
+//!         </assocfn>
+//!     </trait>
+//! }
+//! ```
+//! expands to
+//! ```
+//! unsafe trait Raw {
+//!     unsafe extern "C" fn bump(mut x: u8) -> u8 {
+//!         x += 1;
+//!         x
+//!     }
+//! }
+//! ```
+//!
+//! and
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait name=Shape vis=pub(crate)>
+//!         <assocfn name=scale>
+//!             <params>
+//!                 <param name=self type={&mut Self}/>
+//!                 <param name=by type=u32/>
+//!             </params>
+//!         </assocfn>
+//!     </trait>
+//! }
+//! ```
+//! expands to
+//! ```
+//! pub(crate) trait Shape {
+//!     fn scale(&mut self, by: u32);
+//! }
+//! ```
+//!
+//! ## XML comments
+//!
+//! `<!-- ... -->` can go anywhere a tag could: before `<trait>`, between the children of
+//! `<trait>`, `<assocfn>`, `<assocconst>`, `<assoctype>`, `<params>`, `<param>`, `<bounds>`,
+//! `<gparams>`, `<where>` and the tags inside those, and so on. They're thrown away.
+//!
+//! Two catches. The comment still gets tokenized by rustc before the macro ever sees it, so it
+//! has to be made of valid Rust tokens - no unbalanced brackets and no lone `'`s (so no `that's`).
+//! And skipping a comment takes one macro step per 8 tokens, so a really long one eats into the
+//! recursion limit. Comments also don't work inside tags whose contents are Rust, like
+//! `<type></type>` or `<rust></rust>` - use `//` there.
+//!
 //! ```
 //! trait_xml::trait_xml! {
+//!     <!-- nobody will ever see this -->
 //!     <trait>
-//!         <name>AsRefButXml</name>
+//!         <name>Foo</name>
 //!         <bounds>
-//!             <type>
-//!                 <name>T</name>
-//!                 <type-bound>?Sized</type-bound>
-//!             </type>
+//!             <!-- needs Clone for reasons -->
+//!             <req>Clone</req>
 //!         </bounds>
+//!     </trait>
+//! }
+//! ```
+//!
+//! ## CDATA sections
+//!
+//! `<rust></rust>`, `<type></type>`, `<ret></ret>` and `<default-value></default-value>` collect
+//! tokens until they see their closing tag, so anything that looks like that closing tag ends them
+//! early. Wrap the contents (or any part of them) in `<![CDATA[ ... ]]>` and they get passed
+//! through without being looked at. As a bonus, a CDATA section is taken in one go instead of
+//! token by token, so a big `<rust></rust>` body wrapped in one doesn't eat into the recursion
+//! limit. The one exception is a parameter `<type>` that's nothing but `Self`, `&Self` or
+//! `&mut Self` in CDATA - that one still gets looked at, so it counts as a receiver.
+//!
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Foo</name>
 //!         <assocfn>
-//!             <name>as_ref</name>
+//!             <name>make</name>
+//!             <ret><![CDATA[Box<dyn Fn() -> u8>]]></ret>
+//!             <rust><![CDATA[
+//!                 Box::new(|| 1)
+//!             ]]></rust>
+//!         </assocfn>
+//!     </trait>
+//! }
+//! ```
+//! expands to
+//! ```
+//! trait Foo {
+//!     fn make() -> Box<dyn Fn() -> u8> {
+//!         Box::new(|| 1)
+//!     }
+//! }
+//! ```
+//!
+//! ## XML entities
+//!
+//! `Vec<u8>` in a `<type></type>` is fine as far as the macro's concerned, but it's not valid XML,
+//! which upsets editors, validators and anything else that actually reads XML. So `&lt;`, `&gt;`
+//! and `&amp;` get decoded back into `<`, `>` and `&` in `<type></type>`,
+//! `<type-bound></type-bound>`, `<req></req>` and `<ret></ret>`, including inside brackets.
+//! `-&gt;` turns into `->`.
+//!
+//! Decoding a bracketed group costs a macro step per token in it, so a trait full of escaped
+//! types might need a bigger `#![recursion_limit]`.
+//!
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Foo</name>
+//!         <bounds>
+//!             <req>AsRef&lt;str&gt;</req>
+//!         </bounds>
+//!         <assocfn>
+//!             <name>bar</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&amp;Self</type>
+//!                 </param>
+//!             </params>
+//!             <ret>Option&lt;Box&lt;dyn Fn() -&gt; u8&gt;&gt;</ret>
+//!         </assocfn>
+//!     </trait>
+//! }
+//! ```
+//! expands to
+//! ```
+//! trait Foo: AsRef<str> {
+//!     fn bar(&self) -> Option<Box<dyn Fn() -> u8>>;
+//! }
+//! ```
+//!
+//! ## Extension traits
+//!
+//! The usual way to add methods to a type you don't own is a trait with a blanket impl, and
+//! `<extension></extension>` writes the impl for you. It requires:
+//! - Exactly one `<for></for>` containing either the type to extend (`Vec<u8>`) or a generic
+//!   parameter with bounds to extend everything matching (`I: Iterator<Item = u8>`)
+//!
+//! Every associated function needs a `<rust></rust>` body. The bodies go in the impl instead of the
+//! trait, so they can use anything the `<for></for>` provides - in the example below, `self.next()`
+//! works without `Iterator` being a supertrait. Associated constants need a default, and associated
+//! types aren't allowed, since there's no way to pick them for every type at once.
+//!
+//! For example, the `NextOr0u8` trait from earlier can be written as:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>NextOr0u8</name>
+//!         <extension>
+//!             <for>I: Iterator<Item = u8></for>
+//!         </extension>
+//!         <assocfn>
+//!             <name>next_or_0u8</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&mut Self</type>
+//!                 </param>
+//!             </params>
+//!             <ret>u8</ret>
+//!             <rust>
+//!                 self.next().unwrap_or(0)
+//!             </rust>
+//!         </assocfn>
+//!     </trait>
+//! }
+//!
+//! let mut bytes = [1].into_iter();
+//! assert_eq!(bytes.next_or_0u8(), 1);
+//! assert_eq!(bytes.next_or_0u8(), 0);
+//! ```
+//!
+//! ## Delegation
+//!
+//! Newtypes are great right up until you have to forward every single trait method to the inner
+//! field by hand. `<delegate></delegate>` does that for you - alongside the trait definition, it
+//! generates an `impl` of the trait that forwards everything to a field. It requires:
+//! - Exactly one `<for></for>` containing the type to implement the trait for
+//! - Exactly one `<field></field>` containing the field to forward to (`0`, `inner`, `0.0`, etc.)
+//! - Zero or one `<type></type>` containing the type of that field
+//!
+//! Associated types and constants are taken from the field's implementation, and associated
+//! functions get called on the field. Parameters with a type of `Self`, `&Self` or `&mut Self` get
+//! swapped out for the field (or a reference to it). The field's type can only be left out if the
+//! trait doesn't have any associated types or constants, since otherwise there's no way to name
+//! them.
+//!
+//! What can't be forwarded is anything returning `Self`, whether on its own or inside something
+//! like `Option<Self>` or `(Self, u8)`, since the field's `Self` isn't the newtype. Paths starting
+//! with `Self`, like `Self::Item`, are fine. Neither can receivers other than `self`, `&self` and
+//! `&mut self` (like `self: Box<Self>`), since there's no getting the field out of those. Both are
+//! compile errors.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Area</name>
+//!         <assocconst>
+//!             <name>SIDES</name>
+//!             <type>usize</type>
+//!         </assocconst>
+//!         <assocfn>
+//!             <name>area</name>
 //!             <params>
 //!                 <param>
 //!                     <name>self</name>
 //!                     <type>&Self</type>
 //!                 </param>
 //!             </params>
-//!             <ret>&T</ret>
+//!             <ret>f64</ret>
 //!         </assocfn>
+//!         <delegate>
+//!             <for>Tile</for>
+//!             <field>0</field>
+//!             <type>Square</type>
+//!         </delegate>
 //!     </trait>
 //! }
+//!
+//! struct Square(f64);
+//!
+//! impl Area for Square {
+//!     const SIDES: usize = 4;
+//!     fn area(&self) -> f64 {
+//!         self.0 * self.0
+//!     }
+//! }
+//!
+//! struct Tile(Square);
+//!
+//! assert_eq!(Tile::SIDES, 4);
+//! assert_eq!(Tile(Square(2.0)).area(), 4.0);
 //! ```
-//! And so on and so forth.
-
-mod assoc_const;
-mod assoc_fn;
-mod assoc_type;
-mod bounds;
-mod const_generics;
-mod for_bound;
-mod for_clause;
-mod gparams;
-mod lifetime;
-mod lifetime_bound;
-mod lifetime_clause;
-mod name_ident;
-mod supertrait;
-mod trait_xml_macro;
-mod r#type;
-mod type_bound;
-mod type_clause;
-mod type_ty;
-mod vis;
-mod r#where;
-
+//!
+//! ## Mocks
+//!
+//! `<mock></mock>` generates a test double for the trait without needing a mocking crate. It
+//! requires exactly one `<name></name>` with a valid identifier, which becomes the name of a struct
+//! with a public [`MockMethod`] field for each associated function. Each of those can be given
+//! return values to hand out in order with [`returns`](MockMethod::returns) and a closure to call
+//! once those run out with [`expect`](MockMethod::expect), and keeps a log of the arguments of every
+//! call in [`calls`](MockMethod::calls). The receiver doesn't get passed to the closure or logged,
+//! but everything else has to implement [`Debug`](std::fmt::Debug).
+//!
+//! Mocks can't be made for traits with generic parameters or associated types, and every
+//! associated function has to take `self` somehow and can't be generic. Associated constants need a
+//! `<default-value></default-value>`.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Clock</name>
+//!         <assocfn>
+//!             <name>now</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!                 <param>
+//!                     <name>offset</name>
+//!                     <type>u64</type>
+//!                 </param>
+//!             </params>
+//!             <ret>u64</ret>
+//!         </assocfn>
+//!         <mock>
+//!             <name>MockClock</name>
+//!         </mock>
+//!     </trait>
+//! }
+//!
+//! let clock = MockClock::default();
+//! clock.now.returns(10).expect(Box::new(|offset| 20 + offset));
+//! assert_eq!(clock.now(1), 10);
+//! assert_eq!(clock.now(1), 21);
+//! assert_eq!(clock.now.calls(), vec![vec!["1".to_string()], vec!["1".to_string()]]);
+//! ```
+//!
+//! ## Descriptors
+//!
+//! Sometimes you need to know what a trait looks like at runtime, like for a plugin registry.
+//! `<descriptor></descriptor>` adds a [`TraitDescriptor`] constant describing the trait, with the
+//! same visibility as the trait. It requires:
+//! - Exactly one `<name></name>` containing a valid identifier for the constant
+//!
+//! The descriptor has the trait's name, visibility, generic parameter names, supertraits,
+//! associated types and associated constants, plus a [`FnDescriptor`] for every associated
+//! function with its parameters, return type and whether it has a `<rust></rust>` body. Types are
+//! respaced so they always come out the same way, however they were written: `& mut Self` and
+//! `&mut Self` are both `"&mut Self"`, and `Iterator<Item=u8>` is `"Iterator<Item = u8>"`. A
+//! `<sealed/>` trait's private supertrait isn't listed.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <vis>pub</vis>
+//!         <name>Command</name>
+//!         <bounds>
+//!             <req>Send</req>
+//!         </bounds>
+//!         <assocfn>
+//!             <name>run</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!                 <param>
+//!                     <name>args</name>
+//!                     <type>&[String]</type>
+//!                 </param>
+//!             </params>
+//!             <ret>Result<(), String></ret>
+//!         </assocfn>
+//!         <descriptor>
+//!             <name>COMMAND_DESCRIPTOR</name>
+//!         </descriptor>
+//!     </trait>
+//! }
+//!
+//! assert_eq!(COMMAND_DESCRIPTOR.name, "Command");
+//! assert_eq!(COMMAND_DESCRIPTOR.vis, "pub");
+//! assert_eq!(COMMAND_DESCRIPTOR.supertraits, ["Send"]);
+//!
+//! let run = COMMAND_DESCRIPTOR.fns[0];
+//! assert_eq!(run.name, "run");
+//! assert_eq!(run.params[1].name, "args");
+//! assert_eq!(run.params[1].ty, "&[String]");
+//! assert_eq!(run.ret, Some("Result<(), String>"));
+//! assert!(!run.has_default);
+//! ```
+//!
+//! ## Dyn-compatibility checks
+//!
+//! If a trait is meant to be used as `dyn Trait`, `<dyn-safe/>` makes sure it stays that way by
+//! failing to compile as soon as it isn't. On top of the compiler's own error, you'll get told
+//! which associated item is the problem if it's an associated constant, an associated function
+//! with generic types or consts, or an associated function returning `Self` - those last two are
+//! fine with a `Self: Sized` clause in their `<where></where>`.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Greet</name>
+//!         <assocfn>
+//!             <name>greet</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!             </params>
+//!             <ret>String</ret>
+//!         </assocfn>
+//!         <assocfn>
+//!             <name>twin</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!             </params>
+//!             <ret>Self</ret>
+//!             <where>
+//!                 <type-clause>
+//!                     <type>Self</type>
+//!                     <type-bound>Sized</type-bound>
+//!                 </type-clause>
+//!             </where>
+//!         </assocfn>
+//!         <dyn-safe/>
+//!     </trait>
+//! }
+//! ```
+//!
+//! ## Dyn companions
+//!
+//! Traits with generic methods or associated constants can't be used as `dyn Trait`, so the usual
+//! workaround is a second, dyn-compatible trait with a blanket impl for everything implementing the
+//! first one. `<dyn-companion></dyn-companion>` writes that second trait for you. It requires:
+//! - Exactly one `<name></name>` with a valid identifier for the companion trait
+//! - Zero or one `<boxed/>`, which also implements the original trait for `Box<dyn Companion>`
+//!
+//! The companion has the same generics, `where` clause and associated types as the original
+//! trait, minus any associated constants and generic associated types. Associated functions that
+//! couldn't be called on a `dyn Companion` (generic ones, ones without a `self` or taking `self`
+//! by value, and ones taking or returning `Self`) get a `Self: Sized` bound instead of being
+//! removed, so they can still be called on concrete types through the companion. `<boxed/>` only
+//! works when nothing had to be removed or bounded and the trait has no supertraits or associated
+//! types, since otherwise `Box<dyn Companion>` can't implement everything.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Sink</name>
+//!         <assocfn>
+//!             <name>write</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&mut Self</type>
+//!                 </param>
+//!                 <param>
+//!                     <name>byte</name>
+//!                     <type>u8</type>
+//!                 </param>
+//!             </params>
+//!         </assocfn>
+//!         <assocfn>
+//!             <name>write_all</name>
+//!             <gparams>
+//!                 <type>
+//!                     <name>I</name>
+//!                     <type-bound>IntoIterator<Item = u8></type-bound>
+//!                 </type>
+//!             </gparams>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&mut Self</type>
+//!                 </param>
+//!                 <param>
+//!                     <name>bytes</name>
+//!                     <type>I</type>
+//!                 </param>
+//!             </params>
+//!             <rust>
+//!                 for byte in bytes {
+//!                     self.write(byte);
+//!                 }
+//!             </rust>
+//!         </assocfn>
+//!         <dyn-companion>
+//!             <name>DynSink</name>
+//!         </dyn-companion>
+//!     </trait>
+//! }
+//!
+//! impl Sink for Vec<u8> {
+//!     fn write(&mut self, byte: u8) {
+//!         self.push(byte);
+//!     }
+//! }
+//!
+//! let mut bytes = Vec::new();
+//! let sink: &mut dyn DynSink = &mut bytes;
+//! sink.write(1);
+//! Sink::write_all(&mut bytes, [2, 3]);
+//! assert_eq!(bytes, [1, 2, 3]);
+//! ```
+//!
+//! ## Enum dispatch
+//!
+//! When every implementor is known ahead of time, an enum over all of them is usually faster than
+//! `Box<dyn Trait>`. `<enum-dispatch></enum-dispatch>` generates that enum, a `From` impl for each
+//! variant and an impl of the trait that matches on `self` and forwards to the variant. It
+//! requires:
+//! - Exactly one `<name></name>` containing a valid identifier for the enum
+//! - One or more `<type></type>` containing the types to dispatch to
+//!
+//! Each type is also used as its variant's name, so it has to be a single identifier - `use` or
+//! alias anything else first. Every associated function has to take `self`, `&self` or
+//! `&mut self` first, can't take or return `Self` anywhere else, and gets called on whichever
+//! variant is present. Associated constants need a default, and associated types aren't allowed,
+//! since the variants could disagree on them.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Shape</name>
+//!         <assocfn>
+//!             <name>area</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!             </params>
+//!             <ret>f64</ret>
+//!         </assocfn>
+//!         <enum-dispatch>
+//!             <name>AnyShape</name>
+//!             <type>Square</type>
+//!             <type>Circle</type>
+//!         </enum-dispatch>
+//!     </trait>
+//! }
+//!
+//! struct Square(f64);
+//! struct Circle(f64);
+//!
+//! impl Shape for Square {
+//!     fn area(&self) -> f64 {
+//!         self.0 * self.0
+//!     }
+//! }
+//!
+//! impl Shape for Circle {
+//!     fn area(&self) -> f64 {
+//!         std::f64::consts::PI * self.0 * self.0
+//!     }
+//! }
+//!
+//! let shapes: Vec<AnyShape> = vec![Square(2.0).into(), Circle(1.0).into()];
+//! let total: f64 = shapes.iter().map(Shape::area).sum();
+//! assert_eq!(total, 4.0 + std::f64::consts::PI);
+//! ```
+//!
+//! ## FFI vtables
+//!
+//! Trait objects can't cross an FFI boundary, but a `#[repr(C)]` struct of function pointers can.
+//! `<ffi-vtable></ffi-vtable>` generates one with an `unsafe extern "C" fn` per associated function,
+//! where `self` becomes a `*mut c_void`. It requires:
+//! - Exactly one `<name></name>` containing a valid identifier for the vtable struct
+//! - Exactly one `<wrapper></wrapper>` containing a valid identifier for the wrapper struct
+//!
+//! The vtable gets a `const fn vtable_for<T: Trait>()` that fills it with trampolines calling `T`'s
+//! implementation. The wrapper holds a pointer and a vtable, implements the trait by calling
+//! through the table, and can be made safely from a `&mut T` with `new` or unsafely from a raw
+//! pointer with `from_raw`. Every associated function has to take `&self` or `&mut self` first,
+//! can't be generic and can't take or return `Self` anywhere else. The trait can't have generic
+//! parameters or associated types, and associated constants need a default. Making sure the
+//! parameter and return types are actually FFI-safe is up to you.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Plugin</name>
+//!         <assocfn>
+//!             <name>run</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&mut Self</type>
+//!                 </param>
+//!                 <param>
+//!                     <name>input</name>
+//!                     <type>u32</type>
+//!                 </param>
+//!             </params>
+//!             <ret>u32</ret>
+//!         </assocfn>
+//!         <ffi-vtable>
+//!             <name>PluginVTable</name>
+//!             <wrapper>PluginHandle</wrapper>
+//!         </ffi-vtable>
+//!     </trait>
+//! }
+//!
+//! struct Counter(u32);
+//!
+//! impl Plugin for Counter {
+//!     fn run(&mut self, input: u32) -> u32 {
+//!         self.0 += input;
+//!         self.0
+//!     }
+//! }
+//!
+//! const COUNTER_VTABLE: PluginVTable = PluginVTable::vtable_for::<Counter>();
+//!
+//! let mut counter = Counter(0);
+//! let this = &mut counter as *mut Counter as *mut std::ffi::c_void;
+//! // Pretend `this` and `COUNTER_VTABLE` just came back from a C library
+//! let mut handle = unsafe { PluginHandle::from_raw(this, COUNTER_VTABLE) };
+//! assert_eq!(handle.run(2), 2);
+//! assert_eq!(handle.run(3), 5);
+//! ```
+//!
+//! ## Trait aliases
+//!
+//! Stable Rust doesn't have trait aliases, so the usual stand-in is a trait with the aliased traits
+//! as supertraits and a blanket impl for everything implementing them. `<alias/>` adds that blanket
+//! impl, built from the trait's `<req></req>`s, generic parameters and `<where></where>` clauses.
+//! The trait can't have any associated types, constants or functions, and can't be `<unsafe/>`.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>ReadWrite</name>
+//!         <bounds>
+//!             <req>std::io::Read</req>
+//!             <req>std::io::Write</req>
+//!         </bounds>
+//!         <alias/>
+//!     </trait>
+//! }
+//!
+//! fn takes_read_write(_stream: &mut impl ReadWrite) {}
+//!
+//! takes_read_write(&mut std::io::Cursor::new(Vec::new()));
+//! ```
+//!
+//! ## Closure impls
+//!
+//! Callback traits are nicer to use when a plain closure will do. `<closure-impl/>` implements the
+//! trait for every closure with the same signature as its one associated function, which has to
+//! take `self`, `&self` or `&mut self` first. Those get you `FnOnce`, `Fn` and `FnMut` closures
+//! respectively. The trait can't have any other associated functions, generic associated
+//! functions, associated types or associated constants without a default, and can't be
+//! `<unsafe/>`.
+//!
+//! For example:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Filter</name>
+//!         <assocfn>
+//!             <name>keep</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!                 <param>
+//!                     <name>line</name>
+//!                     <type>&str</type>
+//!                 </param>
+//!             </params>
+//!             <ret>bool</ret>
+//!         </assocfn>
+//!         <closure-impl/>
+//!     </trait>
+//! }
+//!
+//! fn filter_lines<'a>(text: &'a str, filter: &impl Filter) -> Vec<&'a str> {
+//!     text.lines().filter(|line| filter.keep(line)).collect()
+//! }
+//!
+//! let filter = |line: &str| !line.starts_with('#');
+//! assert_eq!(filter_lines("a\n# b\nc", &filter), ["a", "c"]);
+//! ```
+//!
+//! ## Sealed traits
+//!
+//! `<sealed/>` stops anything outside of the module the trait is defined in from implementing it.
+//! It adds a private `mod __sealed { pub trait Sealed {} }` next to the trait and makes
+//! `__sealed::Sealed` a supertrait alongside any `<req></req>`s from `<bounds></bounds>`. Since the
+//! module name is always the same, there can only be one sealed trait per module.
+//!
+//! Types that are allowed to implement the trait are listed in a `<sealed-for></sealed-for>`,
+//! which requires:
+//! - One or more `<type></type>` with a valid type in each
+//!
+//! For example:
+//! ```
+//! mod shapes {
+//!     trait_xml::trait_xml! {
+//!         <trait>
+//!             <vis>pub</vis>
+//!             <name>Shape</name>
+//!             <sealed/>
+//!             <sealed-for>
+//!                 <type>Square</type>
+//!                 <type>Circle</type>
+//!             </sealed-for>
+//!             <assocfn>
+//!                 <name>area</name>
+//!                 <params>
+//!                     <param>
+//!                         <name>self</name>
+//!                         <type>&Self</type>
+//!                     </param>
+//!                 </params>
+//!                 <ret>f64</ret>
+//!             </assocfn>
+//!         </trait>
+//!     }
+//!
+//!     pub struct Square(pub f64);
+//!     pub struct Circle(pub f64);
+//!
+//!     impl Shape for Square {
+//!         fn area(&self) -> f64 {
+//!             self.0 * self.0
+//!         }
+//!     }
+//!
+//!     impl Shape for Circle {
+//!         fn area(&self) -> f64 {
+//!             std::f64::consts::PI * self.0 * self.0
+//!         }
+//!     }
+//! }
+//!
+//! use shapes::Shape;
+//! assert_eq!(shapes::Square(2.0).area(), 4.0);
+//! ```
+//!
+//! ## Namespaced tags
+//!
+//! If you want a tag the crate doesn't have, you don't have to fork it. Declare a namespace and a
+//! `macro_rules!` macro to handle it with `<namespace prefix=acme handler=path::to::macro/>`, and
+//! every `<acme:whatever>` tag in the trait or in its `<assocfn>`s gets handed to that macro. A
+//! trait can declare as many namespaces as it likes, each with its own handler.
+//!
+//! The handler gets called with the same `callback: [name:, rule:, args:]` setup the crate's own
+//! parsers use:
+//! ```text
+//! path::to::macro! {
+//!     @tag {
+//!         context: [trait], // or [assocfn fn_name]
+//!         ns: acme,
+//!         tag: whatever,
+//!         attrs: [...],     // everything between the tag name and the `>` or `/>`
+//!         body: [...],      // everything before the end tag, empty for `<acme:whatever/>`
+//!         callback: [name: ..., rule: [...], args: [...],],
+//!     }
+//! }
+//! ```
+//! and answers by calling the callback with `$($args)*` followed by `attrs: [...]` and
+//! `items: [...]`. Each attribute is written like `[must_use]` and goes on the trait, or the
+//! function in the `assocfn` context. Items go after the trait.
+//!
+//! Some things to keep in mind:
+//! - A prefix only gets one handler, and it has to be declared before any tags that use it.
+//! - The prefix has to be an identifier, not a string, since it gets matched against the tags'.
+//!   Files read by `trait-xml-include` can quote it like real XML: `prefix="acme"`.
+//! - The body ends at the first namespaced end tag, whatever its name, so namespaced tags can't
+//!   nest.
+//!
+//! ```
+//! macro_rules! acme {
+//!     (
+//!         @tag {
+//!             context: [assocfn $fname:ident],
+//!             ns: acme,
+//!             tag: hot,
+//!             attrs: [],
+//!             body: [],
+//!             callback: [
+//!                 name: $callback:path,
+//!                 rule: [$($rule:tt)+],
+//!                 args: [$($args:tt)*],
+//!             ],
+//!         }
+//!     ) => {
+//!         $callback! {
+//!             $($rule)+ {
+//!                 $($args)*
+//!                 attrs: [[inline]],
+//!                 items: [pub const HOT: &str = stringify!($fname);],
+//!             }
+//!         }
+//!     };
+//! }
+//!
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>Foo</name>
+//!         <namespace prefix=acme handler=acme/>
+//!         <assocfn>
+//!             <name>bar</name>
+//!             <ret>u8</ret>
+//!             <acme:hot/>
+//!             <rust>3</rust>
+//!         </assocfn>
+//!     </trait>
+//! }
+//!
+//! assert_eq!(HOT, "bar");
+//! ```
+//! expands to
+//! ```
+//! trait Foo {
+//!     #[inline]
+//!     fn bar() -> u8 {
+//!         3
+//!     }
+//! }
+//!
+//! pub const HOT: &str = "bar";
+//! ```
+//!
+//! ## XML files
+//!
+//! If the XML should live in its own `.xml` file, say because the people reviewing it don't want
+//! to go anywhere near a `.rs` file, the `trait-xml-include` crate next to this one has an
+//! `include_trait_xml!("spec/foo.xml")` proc macro for that. It deals with the parts of real XML
+//! files that don't work as Rust tokens, like the `<?xml ...?>` declaration and comments with
+//! apostrophes in them, and reports mistakes with the file, line and column.
+//!
+//! ## build.rs
+//!
+//! Or skip the macros altogether: [`build::generate`] reads a directory of `.xml` specs from
+//! `build.rs` and writes the traits out as ordinary Rust for you to `include!`. You get to read
+//! what was generated, and the crate doesn't sit through `trait_xml_inner!` on every build. Tags
+//! that generate more than the trait itself, like `<mock>`, are only for the macros though, and
+//! are an error here - the [`build`] docs list which.
+//! ```no_run
+//! // build.rs's main
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("traits.rs");
+//! trait_xml::build::generate("spec", out).unwrap();
+//! ```
+//! ```ignore
+//! // src/lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/traits.rs"));
+//! ```
+//!
+//! ## Skipping the XML
+//!
+//! Under the hood, the XML gets parsed into a list of token records, which then get turned into
+//! the trait. If you're generating traits from code anyway, you can write those records yourself
+//! and hand them to [`trait_xml_emit!`] instead. Its docs describe the records, and the version
+//! the IR is currently at.
+//!
+//! ```
+//! trait_xml::trait_xml_emit! {
+//!     version: 1,
+//!     ir: [
+//!         [name Foo]
+//!         [af [bar] [unsafe: [], extern: [],] [] [] [u8] [] [] [] []]
+//!     ],
+//! }
+//! ```
+//! expands to
+//! ```
+//! trait Foo {
+//!     fn bar() -> u8;
+//! }
+//! ```
+//!
+//! ## JSON
+//!
+//! If your specs come out of JSON tooling instead, [`trait_json!`] takes the same traits written
+//! as braces and colons. It goes through the same IR and backend, so what comes out is the same.
+//! It only covers the basics - the name, visibility, bounds, `where` clauses and associated items.
+//!
+//! ```
+//! trait_xml::trait_json! {{
+//!     "name": Foo,
+//!     "assocfn": [
+//!         {
+//!             "name": bar,
+//!             "params": [{ "name": self, "type": &Self }],
+//!             "ret": u8,
+//!             "rust": { 3 },
+//!         },
+//!     ],
+//! }}
+//! ```
+//! expands to
+//! ```
+//! trait Foo {
+//!     fn bar(&self) -> u8 {
+//!         3
+//!     }
+//! }
+//! ```
+//!
+//! ## Meta items??????
+//!
+//! LMFAO
+//!
+//! NO
+//!
+//! YOURE WRITING TRAITS WITH XML BOZO
+//!
+//! (i didnt feel like doing it)
+//!
+//! # Examples
+//!
+//! For some examples, let's rewrite some `std` traits in XML. [`Index`](std::ops::Index) could be
+//! written as the following using the syntax described by the above:
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>IndexButXml</name>
+//!         <vis>pub</vis>
+//!         <bounds>
+//!             <type>
+//!                 <name>Idx</name>
+//!                 <type-bound>?Sized</type-bound>
+//!             </type>
+//!         </bounds>
+//!         <assoctype>
+//!             <name>Output</name>
+//!             <bounds>
+//!                 <req>?Sized</req>
+//!             </bounds>
+//!         </assoctype>
+//!         <assocfn>
+//!             <name>index</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!             </params>
+//!         </assocfn>
+//!     </trait>
+//! }
+//! ```
+//! [`AsRef`](std::convert::AsRef) could be written as
+//! ```
+//! trait_xml::trait_xml! {
+//!     <trait>
+//!         <name>AsRefButXml</name>
+//!         <bounds>
+//!             <type>
+//!                 <name>T</name>
+//!                 <type-bound>?Sized</type-bound>
+//!             </type>
+//!         </bounds>
+//!         <assocfn>
+//!             <name>as_ref</name>
+//!             <params>
+//!                 <param>
+//!                     <name>self</name>
+//!                     <type>&Self</type>
+//!                 </param>
+//!             </params>
+//!             <ret>&T</ret>
+//!         </assocfn>
+//!     </trait>
+//! }
+//! ```
+//! And so on and so forth.
+
+mod alias;
+mod assoc_const;
+mod assoc_fn;
+mod assoc_type;
+mod bounds;
+pub mod build;
+mod cfg;
+mod closure_impl;
+mod comment;
+mod const_generics;
+mod delegate;
+mod descriptor;
+mod dyn_companion;
+mod dyn_safe;
+mod entities;
+mod enum_dispatch;
+mod example;
+mod extension;
+mod ffi_vtable;
+mod for_bound;
+mod for_clause;
+mod gparam_names;
+mod gparams;
+mod json;
+mod lifetime;
+mod lifetime_bound;
+mod lifetime_clause;
+mod mock;
+mod name_ident;
+mod namespace;
+mod rust_item;
+mod sealed;
+mod supertrait;
+mod trait_xml_macro;
+mod r#type;
+mod type_bound;
+mod type_clause;
+mod type_ty;
+mod vis;
+mod r#where;
+mod xml_attrs;
+
+pub use descriptor::{ConstDescriptor, FnDescriptor, ParamDescriptor, TraitDescriptor};
+#[doc(hidden)]
+pub use descriptor::{__normalize_type, __normalized_str};
+#[doc(hidden)]
+pub use dyn_safe::__where_self_sized;
+pub use mock::MockMethod;
+
+// So `<example>`s on the traits below can `use trait_xml::...` in both doctests and unit tests
+extern crate self as trait_xml;
+
 #[test]
 fn test() {
     let t = trybuild::TestCases::new();
@@ -777,3 +1962,31 @@ trait_xml! {
 pub trait Baz {}
 
 pub trait Qux {}
+
+trait_xml! {
+    <trait>
+        <name>Double</name>
+        <vis>pub</vis>
+        <example>
+            <test>double_u8</test>
+            use trait_xml::Double;
+            assert_eq!(4u8.double(), 8);
+        </example>
+        <assocfn>
+            <name>double</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>Self</type>
+                </param>
+            </params>
+            <ret>Self</ret>
+        </assocfn>
+    </trait>
+}
+
+impl Double for u8 {
+    fn double(self) -> Self {
+        self * 2
+    }
+}
@@ -38,6 +38,24 @@
 /// ```
 #[macro_export]
 macro_rules! trait_xml_parse_lifetime_clause {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_lifetime_clause,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     (
         @parse {
             input: [],
@@ -84,7 +102,7 @@ macro_rules! trait_xml_parse_lifetime_clause {
         @parse {
             input: [</lifetime-clause>$($rest:tt)*],
             lifetime: $lt:lifetime,
-            bounds: [$($lt1:lifetime $($ltn:lifetime)*)?],
+            bounds: [$lt1:lifetime $($ltn:lifetime)*],
             callback: [
                 name: $callback:path,
                 rule: [$($rule:tt)+],
@@ -96,10 +114,32 @@ macro_rules! trait_xml_parse_lifetime_clause {
             $($rule)+ {
                 input: [$($rest)*],
                 $($field: $fieldtokens,)+
-                lc: [$lt: $($lt1$( + $ltn)*)?],
+                lc: [$lt: $lt1$( + $ltn)*],
             }
         }
     };
+    (
+        @parse {
+            input: [</lifetime-clause>$($rest:tt)*],
+            lifetime: $lt:lifetime,
+            bounds: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(
+            concat!(
+                "error parsing lifetime clause: no bounds provided for `",
+                stringify!($lt),
+                "`. caller: `",
+                stringify!($callback),
+                "`",
+            )
+        );
+    };
 
     // Parse LC lifetime - broken up so that proper error handling can be provided.
     // 1: found lifetime tag, lifetime already provided
@@ -0,0 +1,711 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+/// One method of a mock generated with `<mock></mock>`.
+///
+/// `F` is the method's signature without the receiver as a `dyn FnMut`, and `R` is what it
+/// returns. Queued return values are handed out first, in the order they were queued, and once
+/// those run out the expectation closure gets called instead. Every call gets logged with the
+/// [`Debug`](std::fmt::Debug) representation of each of its arguments.
+pub struct MockMethod<F: ?Sized, R> {
+    expectation: RefCell<Option<Box<F>>>,
+    returns: RefCell<VecDeque<R>>,
+    calls: RefCell<Vec<Vec<String>>>,
+    calling: Cell<bool>,
+}
+
+impl<F: ?Sized, R> Default for MockMethod<F, R> {
+    fn default() -> Self {
+        Self {
+            expectation: RefCell::new(None),
+            returns: RefCell::new(VecDeque::new()),
+            calls: RefCell::new(Vec::new()),
+            calling: Cell::new(false),
+        }
+    }
+}
+
+impl<F: ?Sized, R> MockMethod<F, R> {
+    /// Sets the closure that gets called once there are no more queued return values, replacing
+    /// any previous one. It's fine for the closure to use the mock, including setting a new
+    /// expectation for itself, but calling its own method again only works while there are queued
+    /// return values for it.
+    pub fn expect(&self, expectation: Box<F>) -> &Self {
+        *self.expectation.borrow_mut() = Some(expectation);
+        self
+    }
+
+    /// Queues up a value to be returned by a future call.
+    pub fn returns(&self, value: R) -> &Self {
+        self.returns.borrow_mut().push_back(value);
+        self
+    }
+
+    /// The arguments of every call made so far, oldest first.
+    pub fn calls(&self) -> Vec<Vec<String>> {
+        self.calls.borrow().clone()
+    }
+
+    /// How many times the method has been called.
+    pub fn times_called(&self) -> usize {
+        self.calls.borrow().len()
+    }
+
+    #[doc(hidden)]
+    pub fn __call(&self, method: &str, args: Vec<String>, call: impl FnOnce(&mut F) -> R) -> R {
+        self.calls.borrow_mut().push(args);
+        if let Some(value) = self.returns.borrow_mut().pop_front() {
+            return value;
+        }
+        // Taken out for the call, so the expectation can use the mock itself - calling other
+        // methods, queueing return values or setting a new expectation
+        let Some(mut expectation) = self.expectation.borrow_mut().take() else {
+            match self.calling.get() {
+                true => panic!(
+                    "`{method}` was called from inside its own expectation, with no queued return \
+                     values left"
+                ),
+                false => panic!(
+                    "`{method}` was called, but it has no queued return values or expectation left"
+                ),
+            }
+        };
+        self.calling.set(true);
+        let value = call(&mut expectation);
+        self.calling.set(false);
+        self.expectation.borrow_mut().get_or_insert(expectation);
+        value
+    }
+}
+
+/// Parses a `<mock></mock>` section.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_mock! {
+///     @parse {
+///         input: [
+///                     <name>MockFoo</name>
+///                 </mock>
+///             </trait>
+///         ],
+///         name: ,
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@mockcallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_mock {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_mock,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing mock: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed mock
+    (
+        @parse {
+            input: [</mock>$($rest:tt)*],
+            name: $name:ident,
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                mock: [mock [$name]],
+            }
+        }
+    };
+
+    // Missing field errors
+    (
+        @parse {
+            input: [</mock>$($rest:tt)*],
+            name: ,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing mock: no name given with `<name></name>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse name
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: $name:ident,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing mock: name already defined as `",
+            stringify!($name),
+            "`, but encountered a second `<name>` tag. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<name>$new:ident</name>$($rest:tt)*],
+            name: ,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_mock! {
+            @parse {
+                input: [$($rest)*],
+                name: $new,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [<name>$($rest:tt)*],
+            name: ,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing mock: `<name></name>` has to contain a valid identifier. ",
+            "caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Unexpected input errors
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing mock: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing mock: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            name: $($name:ident)?,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing mock: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+}
+
+/// Expands a parsed `<mock>` into a struct with a [`MockMethod`](crate::MockMethod) for each
+/// associated function and an implementation of the trait for it.
+///
+/// Expected initial call example:
+/// ```
+/// trait Foo {
+///     fn bar(&self, baz: u8) -> u8;
+/// }
+///
+/// trait_xml::trait_xml_expand_mock! {
+///     @expand {
+///         mock: [MockFoo],
+///         trait: {
+///             vis: [],
+///             unsafe: [],
+///             name: Foo,
+///             gparams: [],
+///             tpbs: [],
+///             wc: [],
+///             assoc types: [],
+///             assoc consts: [],
+///             fns: [
+///                 [
+///                     af
+///                     [bar]
+///                     [
+///                         unsafe: [],
+///                         extern: [],
+///                     ]
+///                     []
+///                     [[param [self] [&Self] [ref] [] []] [param [baz] [u8] [] [] []]]
+///                     [u8]
+///                     []
+///                     []
+///                     []
+///                     []
+///                 ]
+///             ],
+///         },
+///     }
+/// }
+///
+/// let mock = MockFoo::default();
+/// mock.bar.returns(1).expect(Box::new(|baz| baz * 2));
+/// assert_eq!(mock.bar(3), 1);
+/// assert_eq!(mock.bar(3), 6);
+/// assert_eq!(mock.bar.calls(), vec![vec!["3".to_string()], vec!["3".to_string()]]);
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_mock {
+    // There's nothing to tell what a mock's generics or associated types should be
+    (
+        @expand {
+            mock: [$mock:ident],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [$($gpt:tt)+],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: $att:tt,
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding mock `",
+            stringify!($mock),
+            "` of `",
+            stringify!($name),
+            "`: traits with generic parameters can't be mocked",
+        ));
+    };
+    (
+        @expand {
+            mock: [$mock:ident],
+            trait: {
+                vis: $vistoks:tt,
+                unsafe: $unsafetoks:tt,
+                name: $name:ident,
+                gparams: [],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [$($att:tt)+],
+                assoc consts: $act:tt,
+                fns: $fnt:tt,
+            },
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding mock `",
+            stringify!($mock),
+            "` of `",
+            stringify!($name),
+            "`: traits with associated types can't be mocked",
+        ));
+    };
+    (
+        @expand {
+            mock: [$mock:ident],
+            trait: {
+                vis: [$($vistok:tt)*],
+                unsafe: [$($unsafe:ident)?],
+                name: $name:ident,
+                gparams: [],
+                tpbs: $tpbt:tt,
+                wc: $wct:tt,
+                assoc types: [],
+                assoc consts: [$($act:tt)*],
+                fns: [
+                    $([
+                        af
+                        [$fname:ident]
+                        $qualstoks:tt
+                        $gptoks:tt
+                        $paramstoks:tt
+                        $rettoks:tt
+                        $wheretoks:tt
+                        $deftoks:tt
+                        $docstoks:tt
+                        [$([$($attr:tt)*])*]
+                    ])*
+                ],
+            },
+        }
+    ) => {
+        #[derive(Default)]
+        #[allow(clippy::type_complexity)]
+        $($vistok)* struct $mock {
+            $(
+                $(#[$($attr)*])*
+                pub $fname: $crate::trait_xml_expand_mock!(@fieldty [
+                    $qualstoks
+                    $gptoks
+                    $paramstoks
+                    $rettoks
+                    $wheretoks
+                    $deftoks
+                    $docstoks
+                    [$([$($attr)*])*]
+                ]),
+            )*
+        }
+
+        // So that mocks can be logged when they get passed to themselves
+        impl ::std::fmt::Debug for $mock {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(stringify!($mock))
+            }
+        }
+
+        $($unsafe)? impl $name for $mock {
+            $(
+                $crate::trait_xml_expand_mock! {
+                    @expandac $mock $act
+                }
+            )*
+
+            $(
+                $crate::trait_xml_expand_mock! {
+                    @expandfn $mock [
+                        af
+                        [$fname]
+                        $qualstoks
+                        $gptoks
+                        $paramstoks
+                        $rettoks
+                        $wheretoks
+                        $deftoks
+                        $docstoks
+                        [$([$($attr)*])*]
+                    ]
+                }
+            )*
+        }
+    };
+
+    // Constants can't be mocked, so they have to come with a default
+    (
+        @expandac $mock:ident [[$name:ident] [$type:ty] [$default:expr] $attrstoks:tt]
+    ) => {};
+    (
+        @expandac $mock:ident [[$name:ident] [$type:ty] [] $attrstoks:tt]
+    ) => {
+        compile_error!(concat!(
+            "error expanding mock `",
+            stringify!($mock),
+            "`: associated constant `",
+            stringify!($name),
+            "` needs a `<default-value></default-value>` to be mocked",
+        ));
+    };
+
+    // Field types
+    (
+        @fieldty [$qualstoks:tt [$($gpt:tt)+] $($rest:tt)*]
+    ) => {
+        // Errors out in `@expandfn`
+        ()
+    };
+    // The receiver isn't passed along to the expectation
+    (
+        @fieldty [
+            $qualstoks:tt
+            []
+            [[param $argtoks:tt $typetoks:tt [$recv:tt] $muttoks:tt $pattrstoks:tt] $($param:tt)*]
+            [$($rt:ty)?]
+            $wheretoks:tt
+            $deftoks:tt
+            $docstoks:tt
+            $attrstoks:tt
+        ]
+    ) => {
+        $crate::trait_xml_expand_mock!(@fieldty {
+            params: [$($param)*],
+            types: [],
+            ret: [$($rt)?],
+        })
+    };
+    (
+        @fieldty [
+            $qualstoks:tt
+            []
+            [[param [self] $typetoks:tt [] $muttoks:tt $pattrstoks:tt] $($param:tt)*]
+            [$($rt:ty)?]
+            $wheretoks:tt
+            $deftoks:tt
+            $docstoks:tt
+            $attrstoks:tt
+        ]
+    ) => {
+        $crate::trait_xml_expand_mock!(@fieldty {
+            params: [$($param)*],
+            types: [],
+            ret: [$($rt)?],
+        })
+    };
+    (
+        @fieldty [
+            $qualstoks:tt
+            []
+            [$($param:tt)*]
+            [$($rt:ty)?]
+            $wheretoks:tt
+            $deftoks:tt
+            $docstoks:tt
+            $attrstoks:tt
+        ]
+    ) => {
+        // Errors out in `@body`
+        $crate::trait_xml_expand_mock!(@fieldty {
+            params: [$($param)*],
+            types: [],
+            ret: [$($rt)?],
+        })
+    };
+    (
+        @fieldty {
+            params: [
+                [param $argtoks:tt [$type:ty] $recvtoks:tt $muttoks:tt $pattrstoks:tt]
+                $($rest:tt)*
+            ],
+            types: [$($tys:tt)*],
+            ret: $rettoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_expand_mock!(@fieldty {
+            params: [$($rest)*],
+            types: [$($tys)* $type,],
+            ret: $rettoks,
+        })
+    };
+    (
+        @fieldty {
+            params: [],
+            types: [$($type:ty,)*],
+            ret: [$($rt:ty)?],
+        }
+    ) => {
+        $crate::MockMethod<dyn FnMut($($type),*) $(-> $rt)?, ($($rt)?)>
+    };
+
+    // Functions
+    (
+        @expandfn $mock:ident [af [$name:ident] $qualstoks:tt [$($gpt:tt)+] $($rest:tt)*]
+    ) => {
+        compile_error!(concat!(
+            "error expanding mock `",
+            stringify!($mock),
+            "`: associated function `",
+            stringify!($name),
+            "` has generic parameters, so it can't be mocked",
+        ));
+    };
+    (
+        @expandfn $mock:ident [
+            af
+            [$name:ident]
+            [
+                unsafe: [$($unsafe:tt)?],
+                extern: [$($extern:literal)?],
+            ]
+            []
+            [$([param [$arg:tt] [$type:ty] [$($recv:tt)?] $muttoks:tt $pattrstoks:tt])*]
+            [$($rt:ty)?]
+            [$($([$($wt:tt)+])+)?]
+            $deftoks:tt
+            $docstoks:tt
+            [$([$($attr:tt)*])*]
+        ]
+    ) => {
+        $(#[$($attr)*])*
+        $($unsafe)? $(extern $extern)? fn $name(
+            $($arg: $type),*
+        )$( -> $rt)?
+        $(where $($($wt)+,)+)?
+        {
+            $crate::trait_xml_expand_mock! {
+                @body {
+                    mock: $mock,
+                    name: $name,
+                    // The first copy of the arg is for matching `self`, the second one is the one
+                    // that actually gets used
+                    params: [$([param [$arg] [$arg] [$($recv)?]])*],
+                }
+            }
+        }
+    };
+
+    // Function bodies - the receiver has to come first, and everything after it gets passed along
+    (
+        @body {
+            mock: $mock:ident,
+            name: $name:ident,
+            params: [[param $matchtoks:tt [$arg:tt] [$recv:tt]] $($rest:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_mock! {
+            @bodyargs {
+                mock: $mock,
+                name: $name,
+                params: [$($rest)*],
+                recv: $arg,
+                args: [],
+            }
+        }
+    };
+    (
+        @body {
+            mock: $mock:ident,
+            name: $name:ident,
+            params: [[param [self] [$arg:tt] []] $($rest:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_mock! {
+            @bodyargs {
+                mock: $mock,
+                name: $name,
+                params: [$($rest)*],
+                recv: $arg,
+                args: [],
+            }
+        }
+    };
+    (
+        @body {
+            mock: $mock:ident,
+            name: $name:ident,
+            params: $paramstoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error expanding mock `",
+            stringify!($mock),
+            "`: associated function `",
+            stringify!($name),
+            "` doesn't take `self`, so there's nowhere to keep track of its calls",
+        ))
+    };
+    (
+        @bodyargs {
+            mock: $mock:ident,
+            name: $name:ident,
+            params: [[param $matchtoks:tt [$arg:tt] $recvtoks:tt] $($rest:tt)*],
+            recv: $recv:tt,
+            args: [$($args:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_expand_mock! {
+            @bodyargs {
+                mock: $mock,
+                name: $name,
+                params: [$($rest)*],
+                recv: $recv,
+                args: [$($args)* $arg],
+            }
+        }
+    };
+    (
+        @bodyargs {
+            mock: $mock:ident,
+            name: $name:ident,
+            params: [],
+            recv: $recv:tt,
+            args: [$($arg:tt)*],
+        }
+    ) => {
+        $recv.$name.__call(
+            concat!(stringify!($mock), "::", stringify!($name)),
+            ::std::vec![$(::std::format!("{:?}", $arg)),*],
+            |expectation| expectation($($arg),*),
+        )
+    };
+}
@@ -0,0 +1,313 @@
+// Namespaced tags like `<acme:metrics/>` get handed to whatever macro was declared with
+// `<namespace prefix=acme handler=.../>`. `macro_rules!` can only tell whether two identifiers are
+// the same by using one of them as a pattern, so finding the handler for a tag means writing a
+// little macro with each declared prefix in its pattern and handing it the tag's.
+
+/// Finds the handler declared for a namespace prefix, given `[prefix [path::to::handler]]` pairs,
+/// and calls back with `handler: [path::to::handler]`, or `handler: []` if there isn't one.
+///
+/// Expected initial call example:
+/// ```
+/// # macro_rules! my_handler {
+/// #     (@tag {
+/// #         context: $c:tt, ns: $ns:ident, tag: $tag:ident, attrs: $a:tt, body: $b:tt,
+/// #         callback: [name: $callback:path, rule: [$($rule:tt)+], args: [$($args:tt)*],],
+/// #     }) => {
+/// #         $callback! { $($rule)+ { $($args)* attrs: [], items: [], } }
+/// #     };
+/// # }
+/// trait_xml::trait_xml_find_ns_handler! {
+///     @find {
+///         ns: acme,
+///         handlers: [[other [other_handler]] [acme [my_handler]]],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@nstagfound],
+///             args: [
+///                 input: [</trait>],
+///                 output: [[nshandlers [other [other_handler]] [acme [my_handler]]] [name Foo]],
+///                 nstag: [acme metrics [level=3] []],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_find_ns_handler {
+    (
+        @find {
+            ns: $ns:ident,
+            handlers: [],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                handler: [],
+            }
+        }
+    };
+    // The little macro needs a `$` of its own, which can only come from here
+    (
+        @find {
+            ns: $ns:ident,
+            handlers: $handlerstoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_find_ns_handler! {
+            @compare {
+                dollar: [$],
+                ns: $ns,
+                handlers: $handlerstoks,
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @compare {
+            dollar: [$d:tt],
+            ns: $ns:ident,
+            handlers: [[$prefix:ident [$handler:path]] $($more:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        macro_rules! __trait_xml_ns_handler {
+            ($prefix $d callbacktoks:tt) => {
+                $crate::trait_xml_find_ns_handler! {
+                    @found {
+                        handler: [$handler],
+                        callback: $d callbacktoks,
+                    }
+                }
+            };
+            ($d ns:ident $d callbacktoks:tt) => {
+                $crate::trait_xml_find_ns_handler! {
+                    @find {
+                        ns: $d ns,
+                        handlers: [$($more)*],
+                        callback: $d callbacktoks,
+                    }
+                }
+            };
+        }
+
+        __trait_xml_ns_handler! { $ns $callbacktoks }
+    };
+    (
+        @found {
+            handler: [$handler:path],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($field: $fieldtokens,)+
+                handler: [$handler],
+            }
+        }
+    };
+}
+
+/// Parses a namespaced tag like `<acme:metrics level=3/>` or `<acme:span>...</acme:span>`, after
+/// the `<acme:metrics`. Everything up to the `>` or `/>` is passed on as the tag's attributes, and
+/// everything up to the next namespaced end tag as its body, both untouched - what they mean is up
+/// to the handler.
+///
+/// Expected initial call example:
+/// ```
+/// # macro_rules! my_handler {
+/// #     (@tag {
+/// #         context: $c:tt, ns: $ns:ident, tag: $tag:ident, attrs: $a:tt, body: $b:tt,
+/// #         callback: [name: $callback:path, rule: [$($rule:tt)+], args: [$($args:tt)*],],
+/// #     }) => {
+/// #         $callback! { $($rule)+ { $($args)* attrs: [], items: [], } }
+/// #     };
+/// # }
+/// trait_xml::trait_xml_parse_ns_tag! {
+///     @parse {
+///         ns: acme,
+///         tag: metrics,
+///         input: [
+///                 level=3/>
+///             </trait>
+///         ],
+///         attrs: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@nstagcallback],
+///             args: [
+///                 output: [[nshandlers [acme [my_handler]]] [name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_ns_tag {
+    (
+        @parse {
+            ns: $ns:ident,
+            tag: $tag:ident,
+            input: [/>$($rest:tt)*],
+            attrs: [$($attr:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                nstag: [$ns $tag [$($attr)*] []],
+            }
+        }
+    };
+    (
+        @parse {
+            ns: $ns:ident,
+            tag: $tag:ident,
+            input: [>$($rest:tt)*],
+            attrs: $attrstoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_ns_tag! {
+            @parsebody {
+                ns: $ns,
+                tag: $tag,
+                input: [$($rest)*],
+                attrs: $attrstoks,
+                body: [],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            ns: $ns:ident,
+            tag: $tag:ident,
+            input: [],
+            attrs: $attrstoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing `<",
+            stringify!($ns),
+            ":",
+            stringify!($tag),
+            ">`: expected `>` or `/>`, found end of input. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            ns: $ns:ident,
+            tag: $tag:ident,
+            input: [$first:tt$($rest:tt)*],
+            attrs: [$($attr:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_ns_tag! {
+            @parse {
+                ns: $ns,
+                tag: $tag,
+                input: [$($rest)*],
+                attrs: [$($attr)* $first],
+                callback: $callbacktoks,
+            }
+        }
+    };
+
+    // Body
+    (
+        @parsebody {
+            ns: $ns:ident,
+            tag: $tag:ident,
+            input: [</$endns:ident:$endtag:ident>$($rest:tt)*],
+            attrs: $attrstoks:tt,
+            body: [$($body:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                nstag: [$ns $tag $attrstoks [$($body)*]],
+            }
+        }
+    };
+    (
+        @parsebody {
+            ns: $ns:ident,
+            tag: $tag:ident,
+            input: [],
+            attrs: $attrstoks:tt,
+            body: $bodytoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing `<",
+            stringify!($ns),
+            ":",
+            stringify!($tag),
+            ">`: expected `</",
+            stringify!($ns),
+            ":",
+            stringify!($tag),
+            ">`, found end of input. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parsebody {
+            ns: $ns:ident,
+            tag: $tag:ident,
+            input: [$first:tt$($rest:tt)*],
+            attrs: $attrstoks:tt,
+            body: [$($body:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_ns_tag! {
+            @parsebody {
+                ns: $ns,
+                tag: $tag,
+                input: [$($rest)*],
+                attrs: $attrstoks,
+                body: [$($body)* $first],
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
@@ -0,0 +1,110 @@
+/// Parses a trait-level `<rust></rust>`. Everything up to the `</rust>` is passed on untouched, to
+/// be pasted into the trait body.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_rust_item! {
+///     @parse {
+///         input: [
+///                     const BAR: u8 = 1;
+///                 </rust>
+///             </trait>
+///         ],
+///         rust: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@rustcallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_rust_item {
+    (
+        @parse {
+            input: [</rust>$($rest:tt)*],
+            rust: [$($rust:tt)+],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                rust: [$($rust)+],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [</rust>$($rest:tt)*],
+            rust: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing rust item: `<rust></rust>` is empty. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [],
+            rust: $rusttoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing rust item: expected `</rust>`, found end of input. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    // CDATA sections are passed through without looking inside
+    (
+        @parse {
+            input: [<![CDATA[$($cdata:tt)*]]>$($rest:tt)*],
+            rust: [$($rust:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_rust_item! {
+            @parse {
+                input: [$($rest)*],
+                rust: [$($rust)* $($cdata)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            input: [$first:tt$($rest:tt)*],
+            rust: [$($rust:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_rust_item! {
+            @parse {
+                input: [$($rest)*],
+                rust: [$($rust)* $first],
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
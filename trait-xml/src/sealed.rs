@@ -0,0 +1,249 @@
+/// Parses a `<sealed-for></sealed-for>` section.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_sealed_for! {
+///     @parse {
+///         input: [
+///                     <type>u8</type>
+///                     <type>Vec<u8></type>
+///                 </sealed-for>
+///                 <sealed/>
+///             </trait>
+///         ],
+///         types: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@sealedforcallback],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_sealed_for {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_sealed_for,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    // Empty input error
+    (
+        @parse {
+            input: [],
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing sealed-for: ran out of tokens. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Successfully parsed sealed-for
+    (
+        @parse {
+            input: [</sealed-for>$($rest:tt)*],
+            types: [$($typetoks:tt)+],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)+],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [$($rest)*],
+                $($field: $fieldtokens,)+
+                sealedfor: [sealedfor $($typetoks)+],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [</sealed-for>$($rest:tt)*],
+            types: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing sealed-for: no types given with `<type></type>`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Parse sealed type
+    (
+        @parse {
+            input: [<type>$($rest:tt)*],
+            types: $typestoks:tt,
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_type_ty! {
+            @parse {
+                input: [$($rest)*],
+                type: ,
+                tokens: [],
+                callback: [
+                    name: $crate::trait_xml_parse_sealed_for,
+                    rule: [@typecallback],
+                    args: [
+                        types: $typestoks,
+                        callback: $callbacktoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    // Unexpected input errors
+    (
+        @parse {
+            input: [</$unk:tt$($rest:tt)*],
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing sealed-for: encountered unexpected end tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [<$unk:tt$($rest:tt)*],
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing sealed-for: encountered unknown start tag `",
+            stringify!($unk),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            input: [$unx:tt$($rest:tt)*],
+            types: $typestoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing sealed-for: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+
+    // Catch callbacks
+    (
+        @typecallback {
+            input: $inputtoks:tt,
+            types: [$($typetoks:tt)*],
+            callback: $callbacktoks:tt,
+            type: $type:ty,
+        }
+    ) => {
+        $crate::trait_xml_parse_sealed_for! {
+            @parse {
+                input: $inputtoks,
+                types: [$($typetoks)* [$type]],
+                callback: $callbacktoks,
+            }
+        }
+    };
+}
+
+/// Expands a `<sealed/>` into the module holding the trait's `Sealed` supertrait, or a parsed
+/// `<sealed-for>` into implementations of it.
+///
+/// The module is always called `__sealed`, so there can only be one sealed trait per module.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_expand_sealed! {
+///     @expand {
+///         sealed: [],
+///     }
+/// }
+///
+/// trait_xml::trait_xml_expand_sealed! {
+///     @expand {
+///         sealed: [[u8] [Vec<u8>]],
+///     }
+/// }
+///
+/// trait Foo: __sealed::Sealed {}
+///
+/// impl Foo for u8 {}
+/// impl Foo for Vec<u8> {}
+/// ```
+#[macro_export]
+macro_rules! trait_xml_expand_sealed {
+    (
+        @expand {
+            sealed: [],
+        }
+    ) => {
+        mod __sealed {
+            pub trait Sealed {}
+        }
+    };
+    (
+        @expand {
+            sealed: [$([$type:ty])+],
+        }
+    ) => {
+        $(
+            impl __sealed::Sealed for $type {}
+        )+
+    };
+}
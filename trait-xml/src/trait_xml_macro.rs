@@ -16,6 +16,107 @@ macro_rules! trait_xml {
     };
 }
 
+/// Expands a trait from the token IR that [`trait_xml!`](macro@crate::trait_xml) parses XML into,
+/// so code generators and other front-ends can skip the XML and share the same backend.
+///
+/// The IR is a list of bracketed records, in any order:
+///
+/// | Record | Meaning |
+/// |--------|---------|
+/// | `[vis pub]` | The trait's visibility, any `vis` works. At most one. |
+/// | `[unsafe]` | Makes it an `unsafe trait`. At most one. |
+/// | `[name Foo]` | The trait's name. Exactly one. |
+/// | `[gparams [...] reqs [...]]` | Generic parameters and supertraits. At most one. |
+/// | `[wc [...] ...]` | The `where` clause, one `[...]` per clause. At most one. |
+/// | `[ac [NAME] [type] [default] [attrs]]` | An associated constant. |
+/// | `[at [Name] [gparams] [bounds] [[clause] ...] [attrs]]` | An associated type. |
+/// | `[af ...]` | An associated function, see below. |
+/// | `[sealed]` | Seals the trait, like `<sealed/>`. |
+/// | `[cfgstart [pred]]`, `[cfgend]` | Start and end of a `<cfg><when>pred</when>` section. |
+///
+/// Generic parameters are written out the way they'd go between the `<>`, each one followed by a
+/// comma, e.g. `[T: Clone, 'a, const N: usize,]`. Supertraits are each followed by a `+`, e.g.
+/// `[Clone + Send +]`. A `[default]` is either `[]` or `[expr]`, and `[attrs]` is a list of
+/// attributes without the `#`, like `[[doc = "hi"] [must_use]]`.
+///
+/// An associated function looks like
+/// ```text
+/// [af
+///     [name]
+///     [unsafe: [], extern: [],]  // or `unsafe: [unsafe]`, `extern: ["C"]`
+///     [gparams]                  // same as the trait's, `[]` for none
+///     [[param [arg] [type] [recv] [mut] [attrs]] ...]
+///     [ret]                      // `[]` for no return type
+///     [[clause] ...]             // `where` clauses
+///     [body]                     // `[]` for no default body
+///     [docs]                     // attributes, same as `[attrs]`
+///     [attrs]
+/// ]
+/// ```
+/// where `[recv]` is `[]` for a normal parameter, or `[value]`, `[ref]` or `[refmut]` for `self`,
+/// `&self` and `&mut self`, and `[mut]` is `[]` or `[mut]`.
+///
+/// The rest of the tags reach the backend as `[extra ...]` records. What goes in those is up to
+/// the parsers and isn't part of the IR, so it can change at any time.
+///
+/// The IR has a version, and an invocation has to say which one it's written against. It's
+/// currently `1`, and only goes up when a record above changes shape.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_emit! {
+///     version: 1,
+///     ir: [
+///         [vis pub]
+///         [name Foo]
+///         [gparams [T: Clone,] reqs [Sized +]]
+///         [ac [LEN] [usize] [4] []]
+///         [
+///             af
+///             [get]
+///             [unsafe: [], extern: [],]
+///             []
+///             [[param [self] [&Self] [ref] [] []] [param [i] [usize] [] [] []]]
+///             [Option<T>]
+///             []
+///             [None]
+///             []
+///             [[must_use]]
+///         ]
+///     ],
+/// }
+///
+/// struct S;
+/// impl Foo<u8> for S {}
+/// assert_eq!(S.get(0), None);
+/// assert_eq!(S::LEN, 4);
+/// ```
+#[macro_export]
+macro_rules! trait_xml_emit {
+    (
+        version: 1,
+        ir: [$($record:tt)*]$(,)?
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($record)*],
+                cfgs: [],
+                output: [],
+            }
+        }
+    };
+    (
+        version: $version:literal,
+        ir: $irtoks:tt$(,)?
+    ) => {
+        compile_error!(concat!(
+            "unsupported trait IR version `",
+            stringify!($version),
+            "`, expected `1`",
+        ));
+    };
+}
+
 /// Start of internal parsing.
 #[macro_export]
 macro_rules! trait_xml_inner {
@@ -33,6 +134,41 @@ macro_rules! trait_xml_inner {
         #####################################################
     */
 
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parsetrait],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     // Entry point
     (
         @parse {
@@ -46,6 +182,25 @@ macro_rules! trait_xml_inner {
             }
         }
     };
+    // Attributes on `<trait>` get turned back into child elements first
+    (
+        @parse {
+            input: [<trait $($rest:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: [trait],
+                input: [$($rest)*],
+                children: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parse],
+                    args: [],
+                ],
+            }
+        }
+    };
     // Entry point failure
     (
         @parse {
@@ -126,6 +281,34 @@ macro_rules! trait_xml_inner {
         }
     };
 
+    // `<bounds/>` and `<where/>` are empty sections, so there can't be another one after them
+    (
+        @parsetrait {
+            input: [<bounds/>$($rest:tt)*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: [$($rest)*],
+                output: [$($out)* [gparams [] reqs []]],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [<where/>$($rest:tt)*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: [$($rest)*],
+                output: [$($out)* [wc]],
+            }
+        }
+    };
+
     // Generic bounds (lifetimes, types, const generics) and supertraits
     (
         @parsetrait {
@@ -242,10 +425,12 @@ macro_rules! trait_xml_inner {
                 gparams: [],
                 params: [],
                 hasparams: [],
-                ret: ,
+                ret: [],
                 where: [],
                 def: [],
                 hasdef: [],
+                examples: [],
+                nstags: [],
                 callback: [
                     name: $crate::trait_xml_inner,
                     rule: [@afcallback],
@@ -257,161 +442,1552 @@ macro_rules! trait_xml_inner {
         }
     };
 
-    // Catch callbacks
     (
-        @namecallback {
-            input: $inputtoks:tt,
-            output: [$($output:tt)*],
-            name: $name:ident,
+        @parsetrait {
+            input: [<assocfn $($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        $crate::trait_xml_inner! {
-            @parsetrait {
-                input: $inputtoks,
-                output: [$($output)* [name $name]],
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: [assocfn],
+                input: [$($rest)*],
+                children: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parsetrait],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
             }
         }
     };
+
+    // Newtype delegation
     (
-        @viscallback {
-            input: $inputtoks:tt,
-            output: [$($output:tt)*],
-            vis: [$vis:vis],
+        @parsetrait {
+            input: [<delegate>$($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        $crate::trait_xml_inner! {
-            @parsetrait {
-                input: $inputtoks,
-                output: [$($output)* [vis $vis]],
+        $crate::trait_xml_parse_delegate! {
+            @parse {
+                input: [$($rest)*],
+                for: ,
+                field: [],
+                type: ,
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@delegatecallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
             }
         }
     };
+
+    // Dyn-compatibility check
     (
-        @boundscallback {
-            input: $inputtoks:tt,
+        @parsetrait {
+            input: [<dyn-safe/>$($rest:tt)*],
             output: [$($out:tt)*],
-            gparams: $gpt:tt,
-            reqs: $rt:tt,
         }
     ) => {
         $crate::trait_xml_inner! {
             @parsetrait {
-                input: $inputtoks,
-                output: [$($out)* [gparams $gpt reqs $rt]],
+                input: [$($rest)*],
+                output: [$($out)* [extra [dynsafe]]],
             }
         }
     };
+
+    // Dyn-compatible companion trait
     (
-        @wherecallback {
-            input: $inputtoks:tt,
-            output: [$($out:tt)*],
-            where: [$($clause:tt)*],
+        @parsetrait {
+            input: [<dyn-companion>$($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        $crate::trait_xml_inner! {
-            @parsetrait {
-                input: $inputtoks,
-                output: [$($out)* [wc $($clause)*]],
+        $crate::trait_xml_parse_dyn_companion! {
+            @parse {
+                input: [$($rest)*],
+                name: ,
+                boxed: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@dyncompanioncallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
             }
         }
     };
     (
-        @accallback {
-            input: $inputtoks:tt,
-            output: [$($out:tt)*],
-            assoc const: [ac [$name:ident] [$type:ty] [$($default:expr)?]],
+        @parsetrait {
+            input: [<dyn-companion $($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        $crate::trait_xml_inner! {
-            @parsetrait {
-                input: $inputtoks,
-                output: [$($out)* [ac [$name] [$type] [$($default)?]]],
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: [dyn-companion],
+                input: [$($rest)*],
+                children: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parsetrait],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
             }
         }
     };
+
+    // Mock implementation
     (
-        @atcallback {
-            input: $inputtoks:tt,
-            output: [$($out:tt)*],
-            assoc type: $att:tt,
+        @parsetrait {
+            input: [<mock>$($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        $crate::trait_xml_inner! {
-            @parsetrait {
-                input: $inputtoks,
-                output: [$($out)* $att],
+        $crate::trait_xml_parse_mock! {
+            @parse {
+                input: [$($rest)*],
+                name: ,
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@mockcallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
             }
         }
     };
     (
-        @afcallback {
-            input: $inputtoks:tt,
-            output: [$($out:tt)*],
-            assoc fn: $attoks:tt,
+        @parsetrait {
+            input: [<mock $($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        $crate::trait_xml_inner! {
-            @parsetrait {
-                input: $inputtoks,
-                output: [$($out)* $attoks],
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: [mock],
+                input: [$($rest)*],
+                children: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parsetrait],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
             }
         }
     };
 
-    // End of trait definition
+    // Sealing
     (
         @parsetrait {
-            input: [</trait>],
-            output: $outtoks:tt,
+            input: [<sealed/>$($rest:tt)*],
+            output: [$($out:tt)*],
         }
     ) => {
-        // const _: &str = stringify!($outtoks);
         $crate::trait_xml_inner! {
-            @expand {
-                output: $outtoks,
-                vis: [],
-                unsafe: ,
-                name: ,
-                gparams: [],
-                tpbs: [],
-                wc: [],
-                assoc types: [],
-                assoc consts: [],
-                fns: [],
+            @parsetrait {
+                input: [$($rest)*],
+                output: [$($out)* [sealed]],
             }
         }
     };
     (
         @parsetrait {
-            input: [</trait> $($rest:tt)*]
-            output: [$($out:tt)*],
+            input: [<sealed-for>$($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        compile_error!(concat!(
-            "extraneous tokens after end of trait def: `",
-            $(stringify!($rest)),+
-            "`",
-        ));
+        $crate::trait_xml_parse_sealed_for! {
+            @parse {
+                input: [$($rest)*],
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@sealedforcallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
     };
 
-    // Unknown tags
+    // Extension trait
     (
         @parsetrait {
-            input: [<$unk:tt> $($rest:tt)*],
-            output: [$($out:tt)*],
+            input: [<extension>$($rest:tt)*],
+            output: $outtoks:tt,
         }
     ) => {
-        compile_error!(concat!("unknown start tag: `", stringify!($unk), "`"));
+        $crate::trait_xml_parse_extension! {
+            @parse {
+                input: [$($rest)*],
+                for: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@extensioncallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
     };
+
+    // Namespaces - each prefix gets one handler, and they all go in one `[nshandlers ...]` at the
+    // front of the output (after the `[cfgs]` marker, if there is one) so they're easy to find
+    // again. `prefix={acme}` and `handler={path}` are what `build::to_tokens` makes of quoted ones
     (
         @parsetrait {
-            input: [</$unk:tt> $($rest:tt)*],
-            output: [$($out:tt)*],
+            input: [<namespace prefix = {$prefix:ident} $($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: [<namespace prefix = $prefix $($rest)*],
+                output: $outtoks,
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [
+                <namespace prefix = $prefix:ident handler = $($handler:ident)::+ />$($rest:tt)*
+            ],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @nsregister {
+                input: [$($rest)*],
+                output: $outtoks,
+                ns: [$prefix [$($handler)::+]],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [<namespace prefix = $prefix:ident handler = {$handler:path}/>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @nsregister {
+                input: [$($rest)*],
+                output: $outtoks,
+                ns: [$prefix [$handler]],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [<namespace prefix = $prefix:literal $($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing namespace: the prefix can't be a string, since `macro_rules!` has no ",
+            "way of getting it back out. Leave the quotes off, e.g. `prefix=acme`",
+        ));
+    };
+    (
+        @parsetrait {
+            input: [<namespace $($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        compile_error!(
+            "error parsing namespace: expected `<namespace prefix=acme handler=path::to::macro/>`"
+        );
+    };
+    (
+        @nsregister {
+            input: $inputtoks:tt,
+            output: [[nshandlers $($handler:tt)*] $($out:tt)*],
+            ns: [$prefix:ident $handlertoks:tt],
+        }
+    ) => {
+        $crate::trait_xml_find_ns_handler! {
+            @find {
+                ns: $prefix,
+                handlers: [$($handler)*],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@nsregistercallback],
+                    args: [
+                        input: $inputtoks,
+                        output: [[nshandlers $($handler)*] $($out)*],
+                        ns: [$prefix $handlertoks],
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @nsregister {
+            input: $inputtoks:tt,
+            output: [[cfgs] [nshandlers $($handler:tt)*] $($out:tt)*],
+            ns: [$prefix:ident $handlertoks:tt],
+        }
+    ) => {
+        $crate::trait_xml_find_ns_handler! {
+            @find {
+                ns: $prefix,
+                handlers: [$($handler)*],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@nsregistercallback],
+                    args: [
+                        input: $inputtoks,
+                        output: [[cfgs] [nshandlers $($handler)*] $($out)*],
+                        ns: [$prefix $handlertoks],
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @nsregister {
+            input: $inputtoks:tt,
+            output: [[cfgs] $($out:tt)*],
+            ns: $nstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [[cfgs] [nshandlers $nstoks] $($out)*],
+            }
+        }
+    };
+    (
+        @nsregister {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            ns: $nstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [[nshandlers $nstoks] $($out)*],
+            }
+        }
+    };
+    (
+        @nsregistercallback {
+            input: $inputtoks:tt,
+            output: [[nshandlers $($handler:tt)*] $($out:tt)*],
+            ns: $nstoks:tt,
+            handler: [],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [[nshandlers $($handler)* $nstoks] $($out)*],
+            }
+        }
+    };
+    (
+        @nsregistercallback {
+            input: $inputtoks:tt,
+            output: [[cfgs] [nshandlers $($handler:tt)*] $($out:tt)*],
+            ns: $nstoks:tt,
+            handler: [],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [[cfgs] [nshandlers $($handler)* $nstoks] $($out)*],
+            }
+        }
+    };
+    (
+        @nsregistercallback {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            ns: [$prefix:ident $handlertoks:tt],
+            handler: $existingtoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing namespace: `",
+            stringify!($prefix),
+            "` already has a handler, and a prefix only gets one",
+        ));
+    };
+
+    // Namespaced tags, for the namespace handler
+    (
+        @parsetrait {
+            input: [<$ns:ident:$tag:ident $($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_ns_tag! {
+            @parse {
+                ns: $ns,
+                tag: $tag,
+                input: [$($rest)*],
+                attrs: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@nstagcallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    // Trait alias
+    (
+        @parsetrait {
+            input: [<alias/>$($rest:tt)*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: [$($rest)*],
+                output: [$($out)* [extra [alias]]],
+            }
+        }
+    };
+
+    // Closure implementation
+    (
+        @parsetrait {
+            input: [<closure-impl/>$($rest:tt)*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: [$($rest)*],
+                output: [$($out)* [extra [closureimpl]]],
+            }
+        }
+    };
+
+    // Enum dispatch
+    (
+        @parsetrait {
+            input: [<enum-dispatch>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_enum_dispatch! {
+            @parse {
+                input: [$($rest)*],
+                name: ,
+                types: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@enumdispatchcallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [<enum-dispatch $($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: [enum-dispatch],
+                input: [$($rest)*],
+                children: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parsetrait],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    // FFI vtable
+    (
+        @parsetrait {
+            input: [<ffi-vtable>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_ffi_vtable! {
+            @parse {
+                input: [$($rest)*],
+                name: ,
+                wrapper: ,
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@ffivtablecallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [<ffi-vtable $($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: [ffi-vtable],
+                input: [$($rest)*],
+                children: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@parsetrait],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    // Descriptor
+    (
+        @parsetrait {
+            input: [<descriptor>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_descriptor! {
+            @parse {
+                input: [$($rest)*],
+                name: ,
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@descriptorcallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    // Conditional compilation - the `cfg`s get applied to the items in between once the whole trait
+    // has been parsed
+    (
+        @parsetrait {
+            input: [<cfg>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_cfg! {
+            @parse {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@cfgcallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [</cfg>$($rest:tt)*],
+            output: [[cfgs] $($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: [$($rest)*],
+                output: [[cfgs] $($out)* [cfgend]],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [</cfg>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        compile_error!("error parsing cfg: found `</cfg>` without a matching `<cfg>`");
+    };
+
+    // Example
+    (
+        @parsetrait {
+            input: [<example>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_example! {
+            @parse {
+                input: [$($rest)*],
+                test: ,
+                code: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@examplecallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    // Raw items - these go straight into the trait body, so they ride along with the extras
+    (
+        @parsetrait {
+            input: [<rust>$($rest:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_rust_item! {
+            @parse {
+                input: [$($rest)*],
+                rust: [],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@rustcallback],
+                    args: [
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+
+    // Catch callbacks
+    (
+        @namecallback {
+            input: $inputtoks:tt,
+            output: [$($output:tt)*],
+            name: $name:ident,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($output)* [name $name]],
+            }
+        }
+    };
+    (
+        @viscallback {
+            input: $inputtoks:tt,
+            output: [$($output:tt)*],
+            vis: [$vis:vis],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($output)* [vis $vis]],
+            }
+        }
+    };
+    (
+        @boundscallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            gparams: $gpt:tt,
+            reqs: $rt:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [gparams $gpt reqs $rt]],
+            }
+        }
+    };
+    (
+        @wherecallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            where: [$($clause:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [wc $($clause)*]],
+            }
+        }
+    };
+    (
+        @accallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            assoc const: [ac [$name:ident] [$type:ty] [$($default:expr)?] $attrstoks:tt],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [ac [$name] [$type] [$($default)?] $attrstoks]],
+            }
+        }
+    };
+    (
+        @atcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            assoc type: $att:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* $att],
+            }
+        }
+    };
+    (
+        @afcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            assoc fn: $attoks:tt,
+            examples: [$($example:tt)*],
+            nstags: [],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* $attoks $([extra [fnexample [] $example]])*],
+            }
+        }
+    };
+
+    // Namespaced tags on a function go to their handlers one at a time. Attributes a handler gives
+    // back are for the function, items go after the trait like for the trait's own tags.
+    (
+        @afcallback {
+            input: $inputtoks:tt,
+            output: [[nshandlers $($handler:tt)*] $($out:tt)*],
+            assoc fn: $attoks:tt,
+            examples: $exampletoks:tt,
+            nstags: $nstagstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @fnnstag {
+                input: $inputtoks,
+                output: [[nshandlers $($handler)*] $($out)*],
+                handlers: [$($handler)*],
+                assoc fn: $attoks,
+                examples: $exampletoks,
+                nstags: $nstagstoks,
+            }
+        }
+    };
+    (
+        @afcallback {
+            input: $inputtoks:tt,
+            output: [[cfgs] [nshandlers $($handler:tt)*] $($out:tt)*],
+            assoc fn: $attoks:tt,
+            examples: $exampletoks:tt,
+            nstags: $nstagstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @fnnstag {
+                input: $inputtoks,
+                output: [[cfgs] [nshandlers $($handler)*] $($out)*],
+                handlers: [$($handler)*],
+                assoc fn: $attoks,
+                examples: $exampletoks,
+                nstags: $nstagstoks,
+            }
+        }
+    };
+    (
+        @afcallback {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            assoc fn: $attoks:tt,
+            examples: $exampletoks:tt,
+            nstags: $nstagstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @fnnstag {
+                input: $inputtoks,
+                output: $outtoks,
+                handlers: [],
+                assoc fn: $attoks,
+                examples: $exampletoks,
+                nstags: $nstagstoks,
+            }
+        }
+    };
+    (
+        @fnnstag {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            handlers: $handlerstoks:tt,
+            assoc fn: $attoks:tt,
+            examples: $exampletoks:tt,
+            nstags: [],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @afcallback {
+                input: $inputtoks,
+                output: $outtoks,
+                assoc fn: $attoks,
+                examples: $exampletoks,
+                nstags: [],
+            }
+        }
+    };
+    (
+        @fnnstag {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            handlers: $handlerstoks:tt,
+            assoc fn: $attoks:tt,
+            examples: $exampletoks:tt,
+            nstags: [[$ns:ident $($nstag:tt)*] $($more:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_find_ns_handler! {
+            @find {
+                ns: $ns,
+                handlers: $handlerstoks,
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@fnnstagfound],
+                    args: [
+                        input: $inputtoks,
+                        output: $outtoks,
+                        handlers: $handlerstoks,
+                        af: $attoks,
+                        examples: $exampletoks,
+                        nstags: [$($more)*],
+                        nstag: [$ns $($nstag)*],
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @fnnstagfound {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            handlers: $handlerstoks:tt,
+            af: [af [$fname:ident] $($afrest:tt)*],
+            examples: $exampletoks:tt,
+            nstags: $nstagstoks:tt,
+            nstag: [$ns:ident $tag:ident $attrstoks:tt $bodytoks:tt],
+            handler: [$handler:path],
+        }
+    ) => {
+        $handler! {
+            @tag {
+                context: [assocfn $fname],
+                ns: $ns,
+                tag: $tag,
+                attrs: $attrstoks,
+                body: $bodytoks,
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@fnnscallback],
+                    args: [
+                        input: $inputtoks,
+                        output: $outtoks,
+                        handlers: $handlerstoks,
+                        af: [af [$fname] $($afrest)*],
+                        examples: $exampletoks,
+                        nstags: $nstagstoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @fnnstagfound {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            handlers: $handlerstoks:tt,
+            af: [af [$fname:ident] $($afrest:tt)*],
+            examples: $exampletoks:tt,
+            nstags: $nstagstoks:tt,
+            nstag: [$ns:ident $tag:ident $attrstoks:tt $bodytoks:tt],
+            handler: [],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing `<",
+            stringify!($ns),
+            ":",
+            stringify!($tag),
+            ">` in associated function `",
+            stringify!($fname),
+            "`: no handler declared for namespace `",
+            stringify!($ns),
+            "`. Add a `<namespace prefix=",
+            stringify!($ns),
+            " handler=path::to::macro/>` before it",
+        ));
+    };
+    (
+        @fnnscallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            handlers: $handlerstoks:tt,
+            af: [
+                af $namet:tt $qt:tt $gpt:tt $pt:tt $rettoks:tt $wt:tt $dt:tt $docstoks:tt
+                [$($afattr:tt)*]
+            ],
+            examples: $exampletoks:tt,
+            nstags: $nstagstoks:tt,
+            attrs: [$([$($attr:tt)*])*],
+            items: [$($item:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @fnnstag {
+                input: $inputtoks,
+                output: [$($out)* [extra [ns [] [$($item)*]]]],
+                handlers: $handlerstoks,
+                assoc fn: [
+                    af $namet $qt $gpt $pt $rettoks $wt $dt $docstoks
+                    [$($afattr)* $([$($attr)*])*]
+                ],
+                examples: $exampletoks,
+                nstags: $nstagstoks,
+            }
+        }
+    };
+
+    (
+        @delegatecallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            delegate: $delegatetoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $delegatetoks]],
+            }
+        }
+    };
+
+    (
+        @sealedforcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            sealedfor: $sealedfortoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $sealedfortoks]],
+            }
+        }
+    };
+
+    (
+        @extensioncallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            extension: $extensiontoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $extensiontoks]],
+            }
+        }
+    };
+
+    (
+        @mockcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            mock: $mocktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $mocktoks]],
+            }
+        }
+    };
+
+    (
+        @dyncompanioncallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            dyncompanion: $dyncompaniontoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $dyncompaniontoks]],
+            }
+        }
+    };
+
+    (
+        @ffivtablecallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            ffivtable: $ffivtabletoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $ffivtabletoks]],
+            }
+        }
+    };
+
+    (
+        @descriptorcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            descriptor: $descriptortoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $descriptortoks]],
+            }
+        }
+    };
+
+    (
+        @enumdispatchcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            enumdispatch: $enumdispatchtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra $enumdispatchtoks]],
+            }
+        }
+    };
+
+    (
+        @examplecallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            example: $exampletoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra [example $exampletoks]]],
+            }
+        }
+    };
+
+    (
+        @rustcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            rust: $rusttoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra [rust $rusttoks]]],
+            }
+        }
+    };
+
+    // Namespaced tags go to their namespace's handler, which calls back with attributes for the
+    // trait and items to put after it
+    (
+        @nstagcallback {
+            input: $inputtoks:tt,
+            output: [[nshandlers $($handler:tt)*] $($out:tt)*],
+            nstag: [$ns:ident $($nstag:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_find_ns_handler! {
+            @find {
+                ns: $ns,
+                handlers: [$($handler)*],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@nstagfound],
+                    args: [
+                        input: $inputtoks,
+                        output: [[nshandlers $($handler)*] $($out)*],
+                        nstag: [$ns $($nstag)*],
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @nstagcallback {
+            input: $inputtoks:tt,
+            output: [[cfgs] [nshandlers $($handler:tt)*] $($out:tt)*],
+            nstag: [$ns:ident $($nstag:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_find_ns_handler! {
+            @find {
+                ns: $ns,
+                handlers: [$($handler)*],
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@nstagfound],
+                    args: [
+                        input: $inputtoks,
+                        output: [[cfgs] [nshandlers $($handler)*] $($out)*],
+                        nstag: [$ns $($nstag)*],
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @nstagcallback {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            nstag: $nstagtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @nstagfound {
+                input: $inputtoks,
+                output: $outtoks,
+                nstag: $nstagtoks,
+                handler: [],
+            }
+        }
+    };
+    (
+        @nstagfound {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            nstag: [$ns:ident $tag:ident $attrstoks:tt $bodytoks:tt],
+            handler: [$handler:path],
+        }
+    ) => {
+        $handler! {
+            @tag {
+                context: [trait],
+                ns: $ns,
+                tag: $tag,
+                attrs: $attrstoks,
+                body: $bodytoks,
+                callback: [
+                    name: $crate::trait_xml_inner,
+                    rule: [@nshandlercallback],
+                    args: [
+                        input: $inputtoks,
+                        output: $outtoks,
+                    ],
+                ],
+            }
+        }
+    };
+    (
+        @nstagfound {
+            input: $inputtoks:tt,
+            output: $outtoks:tt,
+            nstag: [$ns:ident $tag:ident $attrstoks:tt $bodytoks:tt],
+            handler: [],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing `<",
+            stringify!($ns),
+            ":",
+            stringify!($tag),
+            ">`: no handler declared for namespace `",
+            stringify!($ns),
+            "`. Add a `<namespace prefix=",
+            stringify!($ns),
+            " handler=path::to::macro/>` before it",
+        ));
+    };
+    (
+        @nshandlercallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            attrs: [$([$($attr:tt)*])*],
+            items: [$($item:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [$($out)* [extra [ns [$([$($attr)*])*] [$($item)*]]]],
+            }
+        }
+    };
+
+    // The `[cfgs]` at the front marks that the `<cfg>` pass is needed, so traits without any don't
+    // pay for it
+    (
+        @cfgcallback {
+            input: $inputtoks:tt,
+            output: [[cfgs] $($out:tt)*],
+            cfg: $cfgtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [[cfgs] $($out)* [cfgstart $cfgtoks]],
+            }
+        }
+    };
+    (
+        @cfgcallback {
+            input: $inputtoks:tt,
+            output: [$($out:tt)*],
+            cfg: $cfgtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @parsetrait {
+                input: $inputtoks,
+                output: [[cfgs] $($out)* [cfgstart $cfgtoks]],
+            }
+        }
+    };
+
+    // End of trait definition
+    (
+        @parsetrait {
+            input: [</trait>],
+            output: [[cfgs] $($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($out)*],
+                cfgs: [],
+                output: [],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [</trait>],
+            output: $outtoks:tt,
+        }
+    ) => {
+        // const _: &str = stringify!($outtoks);
+        $crate::trait_xml_inner! {
+            @expand {
+                output: $outtoks,
+                vis: [],
+                unsafe: ,
+                name: ,
+                // `none` until there's a `[gparams ...]` or `[wc ...]`, so a second one can be told
+                // apart from an empty first one
+                gparams: none,
+                tpbs: [],
+                wc: none,
+                assoc types: [],
+                assoc consts: [],
+                fns: [],
+                extras: [],
+            }
+        }
+    };
+    (
+        @parsetrait {
+            input: [</trait> $($rest:tt)*]
+            output: [$($out:tt)*],
+        }
+    ) => {
+        compile_error!(concat!(
+            "extraneous tokens after end of trait def: `",
+            $(stringify!($rest)),+
+            "`",
+        ));
+    };
+
+    // Unknown tags
+    (
+        @parsetrait {
+            input: [<$unk:tt> $($rest:tt)*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        compile_error!(concat!("unknown start tag: `", stringify!($unk), "`"));
+    };
+    (
+        @parsetrait {
+            input: [</$unk:tt> $($rest:tt)*],
+            output: [$($out:tt)*],
         }
     ) => {
         compile_error!(concat!("unknown end tag: `", stringify!($unk), "`"));
     };
 
+    // Apply `<cfg>`s to the items between them. Every enclosing `<cfg>` adds its own attribute, so
+    // nested ones end up ANDed together.
+    (
+        @applycfg {
+            input: [[cfgstart $cfgtoks:tt] $($rest:tt)*],
+            cfgs: [$($cfgs:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: [$cfgtoks $($cfgs)*],
+                output: $outtoks,
+            }
+        }
+    };
+    (
+        @applycfg {
+            input: [[cfgend] $($rest:tt)*],
+            cfgs: [$first:tt $($cfgs:tt)*],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: [$($cfgs)*],
+                output: $outtoks,
+            }
+        }
+    };
+    (
+        @applycfg {
+            input: [[cfgend] $($rest:tt)*],
+            cfgs: [],
+            output: $outtoks:tt,
+        }
+    ) => {
+        compile_error!("error parsing cfg: found `</cfg>` without a matching `<cfg>`");
+    };
+    (
+        @applycfg {
+            input: [[ac $nametok:tt $typetok:tt $defaulttok:tt [$($attr:tt)*]] $($rest:tt)*],
+            cfgs: [$([$($cfg:tt)+])*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: [$([$($cfg)+])*],
+                output: [
+                    $($out)*
+                    [ac $nametok $typetok $defaulttok [$($attr)* $([cfg($($cfg)+)])*]]
+                ],
+            }
+        }
+    };
+    (
+        @applycfg {
+            input: [
+                [at $nametok:tt $gptok:tt $boundstok:tt $wheretok:tt [$($attr:tt)*]]
+                $($rest:tt)*
+            ],
+            cfgs: [$([$($cfg:tt)+])*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: [$([$($cfg)+])*],
+                output: [
+                    $($out)*
+                    [at $nametok $gptok $boundstok $wheretok [$($attr)* $([cfg($($cfg)+)])*]]
+                ],
+            }
+        }
+    };
+    (
+        @applycfg {
+            input: [
+                [
+                    af
+                    $nametok:tt
+                    $qualstok:tt
+                    $gptok:tt
+                    $paramstok:tt
+                    $rettok:tt
+                    $wheretok:tt
+                    $deftok:tt
+                    $docstok:tt
+                    [$($attr:tt)*]
+                ]
+                $($rest:tt)*
+            ],
+            cfgs: [$([$($cfg:tt)+])*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: [$([$($cfg)+])*],
+                output: [
+                    $($out)*
+                    [
+                        af
+                        $nametok
+                        $qualstok
+                        $gptok
+                        $paramstok
+                        $rettok
+                        $wheretok
+                        $deftok
+                        $docstok
+                        [$($attr)* $([cfg($($cfg)+)])*]
+                    ]
+                ],
+            }
+        }
+    };
+    // Tested examples of functions inside a `<cfg>` can only run when the function exists
+    (
+        @applycfg {
+            input: [[extra [fnexample [$($attr:tt)*] $exampletoks:tt]] $($rest:tt)*],
+            cfgs: [$([$($cfg:tt)+])*],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: [$([$($cfg)+])*],
+                output: [
+                    $($out)*
+                    [extra [fnexample [$($attr)* $([cfg($($cfg)+)])*] $exampletoks]]
+                ],
+            }
+        }
+    };
+    // Whatever a namespace handler made for a function is up to the handler, `<cfg>` or not
+    (
+        @applycfg {
+            input: [[extra [ns $($nstoks:tt)*]] $($rest:tt)*],
+            cfgs: $cfgstoks:tt,
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: $cfgstoks,
+                output: [$($out)* [extra [ns $($nstoks)*]]],
+            }
+        }
+    };
+    (
+        @applycfg {
+            input: [$other:tt $($rest:tt)*],
+            cfgs: [],
+            output: [$($out:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @applycfg {
+                input: [$($rest)*],
+                cfgs: [],
+                output: [$($out)* $other],
+            }
+        }
+    };
+    (
+        @applycfg {
+            input: [$other:tt $($rest:tt)*],
+            cfgs: [$($cfgs:tt)+],
+            output: $outtoks:tt,
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing cfg: only `<assocfn>`, `<assoctype>` and `<assocconst>` can go inside ",
+            "`<cfg></cfg>`",
+        ));
+    };
+    (
+        @applycfg {
+            input: [],
+            cfgs: [$($cfgs:tt)+],
+            output: $outtoks:tt,
+        }
+    ) => {
+        compile_error!("error parsing cfg: expected `</cfg>`, found end of trait def");
+    };
+    (
+        @applycfg {
+            input: [],
+            cfgs: [],
+            output: $outtoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @expand {
+                output: $outtoks,
+                vis: [],
+                unsafe: ,
+                name: ,
+                // `none` until there's a `[gparams ...]` or `[wc ...]`, so a second one can be told
+                // apart from an empty first one
+                gparams: none,
+                tpbs: [],
+                wc: none,
+                assoc types: [],
+                assoc consts: [],
+                fns: [],
+                extras: [],
+            }
+        }
+    };
+
     /*
         #################################################################
         #################################################################
@@ -426,6 +2002,21 @@ macro_rules! trait_xml_inner {
         #################################################################
     */
 
+    // The namespace handlers were only needed while parsing
+    (
+        @expand {
+            output: [[nshandlers $($nshandlerstoks:tt)*]$($out:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @expand {
+                output: [$($out)*],
+                $($state)*
+            }
+        }
+    };
+
     // Expand vis
     (
         @expand {
@@ -439,6 +2030,7 @@ macro_rules! trait_xml_inner {
             assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -453,6 +2045,7 @@ macro_rules! trait_xml_inner {
                 assoc types: $att,
                 assoc consts: $act,
                 fns: $fnt,
+                extras: $extt,
             }
         }
     };
@@ -469,6 +2062,7 @@ macro_rules! trait_xml_inner {
             assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         compile_error!(
@@ -495,6 +2089,7 @@ macro_rules! trait_xml_inner {
             assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -509,6 +2104,7 @@ macro_rules! trait_xml_inner {
                 assoc types: $att,
                 assoc consts: $act,
                 fns: $fnt,
+                extras: $extt,
             }
         }
     };
@@ -525,6 +2121,7 @@ macro_rules! trait_xml_inner {
             assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         compile_error!("trait marked as unsafe multiple times");
@@ -543,6 +2140,7 @@ macro_rules! trait_xml_inner {
             assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -557,6 +2155,7 @@ macro_rules! trait_xml_inner {
                 assoc types: $att,
                 assoc consts: $act,
                 fns: $fnt,
+                extras: $extt,
             }
         }
     };
@@ -573,6 +2172,7 @@ macro_rules! trait_xml_inner {
             assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         compile_error!(concat!(
@@ -586,16 +2186,120 @@ macro_rules! trait_xml_inner {
     // Expand gparams - none present
     (
         @expand {
-            output: [[gparams $gpt:tt reqs $rt:tt] $($out:tt)*],
+            output: [[gparams $gpt:tt reqs [$($rt:tt)*]] $($out:tt)*],
+            vis: $vistoks:tt,
+            unsafe: $($unsafe:ident)?,
+            name: $($name:ident)?,
+            gparams: none,
+            tpbs: [$($tpbt:tt)*],
+            wc: $wct:tt,
+            assoc types: $att:tt,
+            assoc consts: $act:tt,
+            fns: $fnt:tt,
+            extras: $extt:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @expand {
+                output: [$($out)*],
+                vis: $vistoks,
+                unsafe: $($unsafe)?,
+                name: $($name)?,
+                gparams: $gpt,
+                // `<sealed/>` may already have added a supertrait
+                tpbs: [$($rt)* $($tpbt)*],
+                wc: $wct,
+                assoc types: $att,
+                assoc consts: $act,
+                fns: $fnt,
+                extras: $extt,
+            }
+        }
+    };
+    // Expand gparams - already present
+    (
+        @expand {
+            output: [[gparams $gpt:tt reqs $rt:tt]$($out:tt)*],
+            vis: $vistoks:tt,
+            unsafe: $($unsafe:ident)?,
+            name: $($name:ident)?,
+            gparams: [$($present:tt)*],
+            tpbs: $tpbt:tt,
+            wc: $wct:tt,
+            assoc types: $att:tt,
+            assoc consts: $act:tt,
+            fns: $fnt:tt,
+            extras: $extt:tt,
+        }
+    ) => {
+        compile_error!(concat!("multiple `<bounds>` sections present!"));
+    };
+
+    // Expand where clause
+    (
+        @expand {
+            output: [[wc $([$($clausetok:tt)+])*]$($out:tt)*],
+            vis: $vistoks:tt,
+            unsafe: $($unsafe:ident)?,
+            name: $($name:ident)?,
+            gparams: $gpt:tt,
+            tpbs: $tpbt:tt,
+            wc: none,
+            assoc types: $att:tt,
+            assoc consts: $act:tt,
+            fns: $fnt:tt,
+            extras: $extt:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @expand {
+                output: [$($out)*],
+                vis: $vistoks,
+                unsafe: $($unsafe)?,
+                name: $($name)?,
+                gparams: $gpt,
+                tpbs: $tpbt,
+                wc: [$($($clausetok)+,)*],
+                assoc types: $att,
+                assoc consts: $act,
+                fns: $fnt,
+                extras: $extt,
+            }
+        }
+    };
+    // Expand where clause - already present
+    (
+        @expand {
+            output: [[wc $($clausetoks:tt)*]$($out:tt)*],
+            vis: $vistoks:tt,
+            unsafe: $($unsafe:ident)?,
+            name: $($name:ident)?,
+            gparams: $gpt:tt,
+            tpbs: $tpbt:tt,
+            wc: [$($present:tt)*],
+            assoc types: $att:tt,
+            assoc consts: $act:tt,
+            fns: $fnt:tt,
+            extras: $extt:tt,
+        }
+    ) => {
+        compile_error!(concat!("multiple `<where>` sections present!"));
+    };
+
+    // Move associated constant expansion
+    (
+        @expand {
+            output: [[ac $nametok:tt $typetok:tt $defaulttok:tt $attrstok:tt]$($out:tt)*],
             vis: $vistoks:tt,
             unsafe: $($unsafe:ident)?,
             name: $($name:ident)?,
-            gparams: [],
-            tpbs: [],
+            gparams: $gpt:tt,
+            tpbs: $tpbt:tt,
             wc: $wct:tt,
             assoc types: $att:tt,
-            assoc consts: $act:tt,
+            assoc consts: [$($act:tt)*],
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -605,45 +2309,30 @@ macro_rules! trait_xml_inner {
                 unsafe: $($unsafe)?,
                 name: $($name)?,
                 gparams: $gpt,
-                tpbs: $rt,
+                tpbs: $tpbt,
                 wc: $wct,
                 assoc types: $att,
-                assoc consts: $act,
+                assoc consts: [$($act)* [$nametok $typetok $defaulttok $attrstok]],
                 fns: $fnt,
+                extras: $extt,
             }
         }
     };
-    // Expand gparams - already present
-    (
-        @expand {
-            output: [[gparams $gpt:tt reqs $rt:tt]$($out:tt)*],
-            vis: $vistoks:tt,
-            unsafe: $($unsafe:ident)?,
-            name: $($name:ident)?,
-            gparams: [$($present:tt)+],
-            tpbs: $tpbt:tt,
-            wc: $wct:tt,
-            assoc types: $att:tt,
-            assoc consts: $act:tt,
-            fns: $fnt:tt,
-        }
-    ) => {
-        compile_error!(concat!("multiple `<bounds>` sections present!"));
-    };
 
-    // Expand where clause
+    // Move associated type expansion
     (
         @expand {
-            output: [[wc $([$($clausetok:tt)+])*]$($out:tt)*],
+            output: [[at $($new:tt)+]$($out:tt)*],
             vis: $vistoks:tt,
             unsafe: $($unsafe:ident)?,
             name: $($name:ident)?,
             gparams: $gpt:tt,
             tpbs: $tpbt:tt,
-            wc: [],
-            assoc types: $att:tt,
+            wc: $wct:tt,
+            assoc types: [$($att:tt)*],
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -654,18 +2343,19 @@ macro_rules! trait_xml_inner {
                 name: $($name)?,
                 gparams: $gpt,
                 tpbs: $tpbt,
-                wc: [$($($clausetok)+,)*],
-                assoc types: $att,
+                wc: $wct,
+                assoc types: [$($att)* [$($new)+]],
                 assoc consts: $act,
                 fns: $fnt,
+                extras: $extt,
             }
         }
     };
 
-    // Move associated constant expansion
+    // Move associated function expansion
     (
         @expand {
-            output: [[ac $nametok:tt $typetok:tt $defaulttok:tt]$($out:tt)*],
+            output: [[af $($args:tt)*]$($out:tt)*],
             vis: $vistoks:tt,
             unsafe: $($unsafe:ident)?,
             name: $($name:ident)?,
@@ -673,8 +2363,9 @@ macro_rules! trait_xml_inner {
             tpbs: $tpbt:tt,
             wc: $wct:tt,
             assoc types: $att:tt,
-            assoc consts: [$($act:tt)*],
-            fns: $fnt:tt,
+            assoc consts: $act:tt,
+            fns: [$($fnt:tt)*],
+            extras: $extt:tt,
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -687,25 +2378,28 @@ macro_rules! trait_xml_inner {
                 tpbs: $tpbt,
                 wc: $wct,
                 assoc types: $att,
-                assoc consts: [$($act)* [$nametok $typetok $defaulttok]],
-                fns: $fnt,
+                assoc consts: $act,
+                fns: [$($fnt)* [af $($args)*]],
+                extras: $extt,
             }
         }
     };
 
-    // Move associated type expansion
+    // Expand sealed - the supertrait goes next to the `<req>`s, and the module it lives in gets
+    // expanded after the trait
     (
         @expand {
-            output: [[at $($new:tt)+]$($out:tt)*],
+            output: [[sealed]$($out:tt)*],
             vis: $vistoks:tt,
             unsafe: $($unsafe:ident)?,
             name: $($name:ident)?,
             gparams: $gpt:tt,
-            tpbs: $tpbt:tt,
+            tpbs: [$($tpbt:tt)*],
             wc: $wct:tt,
-            assoc types: [$($att:tt)*],
+            assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: [$($extt:tt)*],
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -715,19 +2409,20 @@ macro_rules! trait_xml_inner {
                 unsafe: $($unsafe)?,
                 name: $($name)?,
                 gparams: $gpt,
-                tpbs: $tpbt,
+                tpbs: [__sealed::Sealed + $($tpbt)*],
                 wc: $wct,
-                assoc types: [$($att)* [$($new)+]],
+                assoc types: $att,
                 assoc consts: $act,
                 fns: $fnt,
+                extras: [$($extt)* [sealed]],
             }
         }
     };
 
-    // Move associated function expansion
+    // Move extra item expansion (delegates, mocks, etc.) - these get expanded after the trait
     (
         @expand {
-            output: [[af $($args:tt)*]$($out:tt)*],
+            output: [[extra $extra:tt]$($out:tt)*],
             vis: $vistoks:tt,
             unsafe: $($unsafe:ident)?,
             name: $($name:ident)?,
@@ -736,7 +2431,8 @@ macro_rules! trait_xml_inner {
             wc: $wct:tt,
             assoc types: $att:tt,
             assoc consts: $act:tt,
-            fns: [$($fnt:tt)*],
+            fns: $fnt:tt,
+            extras: [$($extt:tt)*],
         }
     ) => {
         $crate::trait_xml_inner! {
@@ -750,7 +2446,8 @@ macro_rules! trait_xml_inner {
                 wc: $wct,
                 assoc types: $att,
                 assoc consts: $act,
-                fns: [$($fnt)* [af $($args)*]],
+                fns: $fnt,
+                extras: [$($extt)* $extra],
             }
         }
     };
@@ -761,24 +2458,249 @@ macro_rules! trait_xml_inner {
             vis: [$($vistok:tt)*],
             unsafe: $($unsafe:ident)?,
             name: ,
-            gparams: [$($gpt:tt)*],
-            tpbs: [$($tpbt:tt)*],
-            wc: [$($wct:tt)*],
+            gparams: $gpt:tt,
+            tpbs: $tpbt:tt,
+            wc: $wct:tt,
             assoc types: $att:tt,
             assoc consts: $act:tt,
             fns: $fnt:tt,
+            extras: $extt:tt,
         }
     ) => {
         compile_error!("error expanding trait: no name provided.");
     };
 
-    // Finish trait expansion
+    // Finish trait expansion. A `gparams` or `wc` that's still `none` is the same as an empty one
     (
         @expand {
             output: [],
-            vis: [$($vistok:tt)*],
+            vis: $vistoks:tt,
             unsafe: $($unsafe:ident)?,
             name: $name:ident,
+            gparams: $(none)? $([$($gpt:tt)*])?,
+            tpbs: $tpbt:tt,
+            wc: $(none)? $([$($wct:tt)*])?,
+            assoc types: $att:tt,
+            assoc consts: $act:tt,
+            fns: $fnt:tt,
+            extras: $extt:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @emit {
+                trait: {
+                    vis: $vistoks,
+                    unsafe: [$($unsafe)?],
+                    name: $name,
+                    gparams: [$($($gpt)*)?],
+                    tpbs: $tpbt,
+                    wc: [$($($wct)*)?],
+                    assoc types: $att,
+                    assoc consts: $act,
+                    fns: $fnt,
+                },
+                extras: $extt,
+            }
+        }
+    };
+
+    // Emit the trait itself, then every extra item that was requested alongside it. Extension
+    // traits' function bodies go in their blanket impl rather than the trait, examples end up in
+    // the trait's docs and raw `<rust>` items in its body, and namespace handlers can put
+    // attributes on it, so the extras get checked for those first.
+    (
+        @emit {
+            trait: $traittoks:tt,
+            extras: $extt:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @emit {
+                trait: $traittoks,
+                extras: $extt,
+                unchecked: $extt,
+                defs: [],
+                attrs: [],
+                items: [],
+            }
+        }
+    };
+    (
+        @emit {
+            trait: $traittoks:tt,
+            extras: $extt:tt,
+            unchecked: [[extension $exttoks:tt] $($rest:tt)*],
+            defs: $defstoks:tt,
+            attrs: $attrstoks:tt,
+            items: $itemstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @emit {
+                trait: $traittoks,
+                extras: $extt,
+                unchecked: [$($rest)*],
+                defs: [@nodefs],
+                attrs: $attrstoks,
+                items: $itemstoks,
+            }
+        }
+    };
+    (
+        @emit {
+            trait: $traittoks:tt,
+            extras: $extt:tt,
+            unchecked: [[example [$testtoks:tt [$($example:tt)+]]] $($rest:tt)*],
+            defs: $defstoks:tt,
+            attrs: [$($attr:tt)*],
+            items: $itemstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @emit {
+                trait: $traittoks,
+                extras: $extt,
+                unchecked: [$($rest)*],
+                defs: $defstoks,
+                attrs: [
+                    $($attr)*
+                    [doc = "```"]
+                    [doc = stringify!($($example)+)]
+                    [doc = "```"]
+                ],
+                items: $itemstoks,
+            }
+        }
+    };
+    (
+        @emit {
+            trait: $traittoks:tt,
+            extras: $extt:tt,
+            unchecked: [[rust $rusttoks:tt] $($rest:tt)*],
+            defs: $defstoks:tt,
+            attrs: $attrstoks:tt,
+            items: [$($item:tt)*],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @emit {
+                trait: $traittoks,
+                extras: $extt,
+                unchecked: [$($rest)*],
+                defs: $defstoks,
+                attrs: $attrstoks,
+                items: [$($item)* $rusttoks],
+            }
+        }
+    };
+    (
+        @emit {
+            trait: $traittoks:tt,
+            extras: $extt:tt,
+            unchecked: [[ns [$($nsattr:tt)*] $nsitemstoks:tt] $($rest:tt)*],
+            defs: $defstoks:tt,
+            attrs: [$($attr:tt)*],
+            items: $itemstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @emit {
+                trait: $traittoks,
+                extras: $extt,
+                unchecked: [$($rest)*],
+                defs: $defstoks,
+                attrs: [$($attr)* $($nsattr)*],
+                items: $itemstoks,
+            }
+        }
+    };
+    (
+        @emit {
+            trait: $traittoks:tt,
+            extras: $extt:tt,
+            unchecked: [$other:tt $($rest:tt)*],
+            defs: $defstoks:tt,
+            attrs: $attrstoks:tt,
+            items: $itemstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @emit {
+                trait: $traittoks,
+                extras: $extt,
+                unchecked: [$($rest)*],
+                defs: $defstoks,
+                attrs: $attrstoks,
+                items: $itemstoks,
+            }
+        }
+    };
+    (
+        @emit {
+            trait: $traittoks:tt,
+            extras: [$($extra:tt)*],
+            unchecked: [],
+            defs: [$($defs:tt)*],
+            attrs: $attrstoks:tt,
+            items: $itemstoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @expandtrait $($defs)* $attrstoks $itemstoks $traittoks
+        }
+
+        $(
+            $crate::trait_xml_inner! {
+                @expandextra $extra $traittoks
+            }
+        )*
+    };
+
+    (
+        @expandtrait @nodefs $attrstoks:tt $itemstoks:tt {
+            vis: $vistoks:tt,
+            unsafe: $unsafetoks:tt,
+            name: $name:ident,
+            gparams: $gpt:tt,
+            tpbs: $tpbt:tt,
+            wc: $wct:tt,
+            assoc types: $att:tt,
+            assoc consts: $act:tt,
+            fns: [
+                $([
+                    af
+                    $fname:tt
+                    $quals:tt
+                    $fgp:tt
+                    $params:tt
+                    $ret:tt
+                    $fwc:tt
+                    $def:tt
+                    $fdocs:tt
+                    $fattrs:tt
+                ])*
+            ],
+        }
+    ) => {
+        $crate::trait_xml_inner! {
+            @expandtrait $attrstoks $itemstoks {
+                vis: $vistoks,
+                unsafe: $unsafetoks,
+                name: $name,
+                gparams: $gpt,
+                tpbs: $tpbt,
+                wc: $wct,
+                assoc types: $att,
+                assoc consts: $act,
+                fns: [$([af $fname $quals $fgp $params $ret $fwc [] $fdocs $fattrs])*],
+            }
+        }
+    };
+    (
+        @expandtrait [$([$($attr:tt)*])*] [$([$($item:tt)*])*] {
+            vis: [$($vistok:tt)*],
+            unsafe: [$($unsafe:ident)?],
+            name: $name:ident,
             gparams: [$($gpt:tt)*],
             tpbs: [$($tpbt:tt)*],
             wc: [$($wct:tt)*],
@@ -787,6 +2709,7 @@ macro_rules! trait_xml_inner {
             fns: [$($fnt:tt)*],
         }
     ) => {
+        $(#[$($attr)*])*
         $($vistok)* $($unsafe)? trait $name<
             $($gpt)*
         >: $($tpbt)*
@@ -810,12 +2733,171 @@ macro_rules! trait_xml_inner {
                     @expandfn $fnt
                 }
             )*
+
+            $($($item)*)*
+        }
+    };
+
+    // Extras
+    (
+        @expandextra [delegate $($args:tt)*] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_delegate! {
+            @expand {
+                delegate: [$($args)*],
+                trait: $traittoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [dynsafe] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_dyn_safe! {
+            @expand {
+                trait: $traittoks,
+            }
+        }
+    };
+    (
+        @expandextra [dyncompanion $companiontoks:tt] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_dyn_companion! {
+            @expand {
+                companion: $companiontoks,
+                trait: $traittoks,
+            }
+        }
+    };
+    (
+        @expandextra [mock $($args:tt)*] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_mock! {
+            @expand {
+                mock: $($args)*,
+                trait: $traittoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [extension $exttoks:tt] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_extension! {
+            @expand {
+                extension: $exttoks,
+                trait: $traittoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [alias] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_alias! {
+            @expand {
+                trait: $traittoks,
+            }
         }
     };
 
     (
-        @expandac [[$name:ident] [$type:ty] [$($default:expr)?]]
+        @expandextra [closureimpl] $traittoks:tt
     ) => {
+        $crate::trait_xml_expand_closure_impl! {
+            @expand {
+                trait: $traittoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [enumdispatch $($args:tt)*] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_enum_dispatch! {
+            @expand {
+                dispatch: [$($args)*],
+                trait: $traittoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [ffivtable $($args:tt)*] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_ffi_vtable! {
+            @expand {
+                vtable: [$($args)*],
+                trait: $traittoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [descriptor $($args:tt)*] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_descriptor! {
+            @expand {
+                descriptor: $($args)*,
+                trait: $traittoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [example $exampletoks:tt] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_example! {
+            @expand {
+                example: $exampletoks,
+                attrs: [],
+            }
+        }
+    };
+    (
+        @expandextra [fnexample $attrstoks:tt $exampletoks:tt] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_example! {
+            @expand {
+                example: $exampletoks,
+                attrs: $attrstoks,
+            }
+        }
+    };
+
+    (
+        @expandextra [rust $rusttoks:tt] $traittoks:tt
+    ) => {};
+
+    (
+        @expandextra [ns $attrstoks:tt [$($item:tt)*]] $traittoks:tt
+    ) => {
+        $($item)*
+    };
+
+    (
+        @expandextra [sealed] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_sealed! {
+            @expand {
+                sealed: [],
+            }
+        }
+    };
+    (
+        @expandextra [sealedfor $($types:tt)+] $traittoks:tt
+    ) => {
+        $crate::trait_xml_expand_sealed! {
+            @expand {
+                sealed: [$($types)+],
+            }
+        }
+    };
+
+    (
+        @expandac [[$name:ident] [$type:ty] [$($default:expr)?] [$([$($attr:tt)*])*]]
+    ) => {
+        $(#[$($attr)*])*
         const $name: $type$( = $default)?;
     };
 
@@ -825,8 +2907,10 @@ macro_rules! trait_xml_inner {
             [$($($gpt:tt)+)?]
             [$($($bt:tt)+)?]
             [$($([$($bw:tt)+])+)?]
+            [$([$($attr:tt)*])*]
         ]
     ) => {
+        $(#[$($attr)*])*
         type $name$(<
             $($gpt)+
         >)?$(: $($bt)+)?
@@ -842,16 +2926,29 @@ macro_rules! trait_xml_inner {
                 extern: [$($extern:literal)?],
             ]
             [$($($gpt:tt)+)?]
-            [$([param [$arg:tt] [$type:ty]])*]
+            [
+                $([
+                    param
+                    [$arg:tt]
+                    [$type:ty]
+                    $recvtoks:tt
+                    $muttoks:tt
+                    [$([$($pattr:tt)*])*]
+                ])*
+            ]
             [$($rt:ty)?]
-            [$($($wt:tt)+)?]
+            [$($([$($wt:tt)+])+)?]
             []
+            [$([$($doc:tt)*])*]
+            [$([$($attr:tt)*])*]
         ]
     ) => {
+        $(#[$($doc)*])*
+        $(#[$($attr)*])*
         $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
-            $($arg: $type)*
+            $($(#[$($pattr)*])* $arg: $type),*
         )$( -> $rt)?
-        $(where $($wt)+)?;
+        $(where $($($wt)+,)+)?;
     };
     (
         @expandfn [
@@ -862,16 +2959,29 @@ macro_rules! trait_xml_inner {
                 extern: [$($extern:literal)?],
             ]
             [$($($gpt:tt)+)?]
-            [$([param [$arg:tt] [$type:ty]])*]
+            [
+                $([
+                    param
+                    [$arg:tt]
+                    [$type:ty]
+                    $recvtoks:tt
+                    [$($mut:tt)?]
+                    [$([$($pattr:tt)*])*]
+                ])*
+            ]
             [$($rt:ty)?]
-            [$($($wt:tt)+)?]
+            [$($([$($wt:tt)+])+)?]
             [$($dt:tt)*]
+            [$([$($doc:tt)*])*]
+            [$([$($attr:tt)*])*]
         ]
     ) => {
+        $(#[$($doc)*])*
+        $(#[$($attr)*])*
         $($unsafe)? $(extern $extern)? fn $name$(<$($gpt)+>)?(
-            $($arg: $type)*
+            $($(#[$($pattr)*])* $($mut)? $arg: $type),*
         )$( -> $rt)?
-        $(where $($wt)+)?
+        $(where $($($wt)+,)+)?
         {
             $($dt)*
         }
@@ -170,6 +170,149 @@ macro_rules! trait_xml_parse_type_bound {
     };
 
     // Munch first token
+    // XML entities, which might be hiding in groups too
+    (
+        @parse {
+            input: [& lt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& lt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [& gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [& amp ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& amp ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [- & gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [- & gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [($($group:tt)*) $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [($($group)*) $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [[$($group:tt)*] $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [[$($group)*] $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [{$($group:tt)*} $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [{$($group)*} $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_bound,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [@decoded $decoded:tt $($rest:tt)*],
+            tokens: [$($tok:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        $crate::trait_xml_parse_type_bound! {
+            @parse {
+                input: [$($rest)*],
+                tokens: [$($tok)* $decoded],
+                callback: [
+                    name: $callback,
+                    rule: $ruletoks,
+                    args: $argstoks,
+                ],
+            }
+        }
+    };
     (
         @parse {
             input: [$first:tt $($rest:tt)*],
@@ -38,6 +38,24 @@
 /// ```
 #[macro_export]
 macro_rules! trait_xml_parse_type_clause {
+    // XML comments
+    (
+        @parse {
+            input: [<!--$($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_skip_comment! {
+            @skip {
+                input: [$($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_clause,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
     (
         @parse {
             input: [],
@@ -63,7 +81,7 @@ macro_rules! trait_xml_parse_type_clause {
         @parse {
             input: [</type-clause>$($rest:tt)*],
             type: $type:ty,
-            bounds: [$([$($bound1:tt)+]$([$($boundn:tt)+])*)?],
+            bounds: [[$($bound1:tt)+]$([$($boundn:tt)+])*],
             callback: [
                 name: $callback:path,
                 rule: [$($rule:tt)+],
@@ -75,10 +93,32 @@ macro_rules! trait_xml_parse_type_clause {
             $($rule)+ {
                 input: [$($rest)*],
                 $($field: $fieldtokens,)+
-                tc: [$type: $($($bound1)+$( + $($boundn)+)*)?],
+                tc: [$type: $($bound1)+$( + $($boundn)+)*],
             }
         }
     };
+    (
+        @parse {
+            input: [</type-clause>$($rest:tt)*],
+            type: $type:ty,
+            bounds: [],
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(
+            concat!(
+                "error parsing type clause: no bounds provided for `",
+                stringify!($type),
+                "`. caller: `",
+                stringify!($callback),
+                "`",
+            )
+        );
+    };
 
     (
         @parse {
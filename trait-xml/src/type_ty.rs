@@ -113,6 +113,161 @@ macro_rules! trait_xml_parse_type_ty {
             )
         );
     };
+    // CDATA sections are passed through without looking inside
+    (
+        @parse {
+            input: [<![CDATA[$($cdata:tt)*]]>$($rest:tt)*],
+            type: ,
+            tokens: [$($tytok:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_type_ty! {
+            @parse {
+                input: [$($rest)*],
+                type: ,
+                tokens: [$($tytok)* $($cdata)*],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    // XML entities, which might be hiding in groups too
+    (
+        @parse {
+            input: [& lt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& lt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_ty,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [& gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_ty,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [& amp ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [& amp ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_ty,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [- & gt ; $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [- & gt ; $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_ty,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [($($group:tt)*) $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [($($group)*) $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_ty,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [[$($group:tt)*] $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [[$($group)*] $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_ty,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [{$($group:tt)*} $($rest:tt)*],
+            $($state:tt)*
+        }
+    ) => {
+        $crate::trait_xml_decode_entities! {
+            @step {
+                input: [{$($group)*} $($rest)*],
+                callback: [
+                    name: $crate::trait_xml_parse_type_ty,
+                    rule: [@parse],
+                    args: [$($state)*],
+                ],
+            }
+        }
+    };
+    (
+        @parse {
+            input: [@decoded $tok:tt $($rest:tt)*],
+            type: ,
+            tokens: [$($tytok:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_type_ty! {
+            @parse {
+                input: [$($rest)*],
+                type: ,
+                tokens: [$($tytok)* $tok],
+                callback: $callbacktoks,
+            }
+        }
+    };
     (
         @parse {
             input: [$first:tt$($rest:tt)*],
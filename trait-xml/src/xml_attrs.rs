@@ -0,0 +1,229 @@
+/// Parses the attributes on an opening tag, like the `name=bar unsafe extern="C"` in
+/// `<assocfn name=bar unsafe extern="C">`, and turns them back into the child elements they're
+/// short for so the tag's own parser doesn't have to know the difference:
+/// - a bare flag becomes `<flag/>`
+/// - `extern="ABI"` becomes `<extern>"ABI"</extern>`
+/// - `attr=value` becomes `<attr>value</attr>`, where the value is one token tree, `pub(...)`, or
+///   anything at all wrapped in `{}` (the braces get dropped)
+///
+/// A tag closed with `/>` gets its closing tag put back too.
+///
+/// Quoted values other than `extern`'s are an error, since `macro_rules!` can't get anything back
+/// out of a string literal. `build::to_tokens` unquotes them first for the front-ends that read
+/// real XML files.
+///
+/// Expected initial call example:
+/// ```
+/// trait_xml::trait_xml_parse_xml_attrs! {
+///     @parse {
+///         tag: [assocfn],
+///         input: [
+///                 name=bar unsafe extern="C">
+///                 </assocfn>
+///             </trait>
+///         ],
+///         children: [],
+///         callback: [
+///             name: trait_xml::trait_xml_inner,
+///             rule: [@parsetrait],
+///             args: [
+///                 output: [[name Foo]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! trait_xml_parse_xml_attrs {
+    (
+        @parse {
+            tag: [$($tag:tt)+],
+            input: [>$($rest:tt)*],
+            children: [$($child:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [<$($tag)+> $($child)* $($rest)*],
+                $($field: $fieldtokens,)*
+            }
+        }
+    };
+    (
+        @parse {
+            tag: [$($tag:tt)+],
+            input: [/>$($rest:tt)*],
+            children: [$($child:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($field:tt: $fieldtokens:tt,)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                input: [<$($tag)+> $($child)* </$($tag)+> $($rest)*],
+                $($field: $fieldtokens,)*
+            }
+        }
+    };
+    (
+        @parse {
+            tag: $tagtoks:tt,
+            input: [extern = $abi:literal $($rest:tt)*],
+            children: [$($child:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: $tagtoks,
+                input: [$($rest)*],
+                children: [$($child)* <extern>$abi</extern>],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            tag: [$($tag:tt)+],
+            input: [$attr:ident = $value:literal $($rest:tt)*],
+            children: $childtoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing attributes of `<",
+            $(stringify!($tag),)+
+            ">`: the value of `",
+            stringify!($attr),
+            "` can't be a string, since `macro_rules!` has no way of getting it back out. Leave ",
+            "the quotes off, e.g. `",
+            stringify!($attr),
+            "=Foo`, or use `{}` for anything longer than one token. Quoted values only work in ",
+            "`.xml` files read by `trait-xml-include` or `trait_xml::build`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            tag: $tagtoks:tt,
+            input: [vis = pub ($($restriction:tt)*) $($rest:tt)*],
+            children: [$($child:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: $tagtoks,
+                input: [$($rest)*],
+                children: [$($child)* <vis>pub($($restriction)*)</vis>],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            tag: $tagtoks:tt,
+            input: [$attr:ident = {$($value:tt)*} $($rest:tt)*],
+            children: [$($child:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: $tagtoks,
+                input: [$($rest)*],
+                children: [$($child)* <$attr>$($value)*</$attr>],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            tag: $tagtoks:tt,
+            input: [$attr:ident = $value:tt $($rest:tt)*],
+            children: [$($child:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: $tagtoks,
+                input: [$($rest)*],
+                children: [$($child)* <$attr>$value</$attr>],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            tag: $tagtoks:tt,
+            input: [$flag:ident $($rest:tt)*],
+            children: [$($child:tt)*],
+            callback: $callbacktoks:tt,
+        }
+    ) => {
+        $crate::trait_xml_parse_xml_attrs! {
+            @parse {
+                tag: $tagtoks,
+                input: [$($rest)*],
+                children: [$($child)* <$flag/>],
+                callback: $callbacktoks,
+            }
+        }
+    };
+    (
+        @parse {
+            tag: [$($tag:tt)+],
+            input: [],
+            children: $childtoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing attributes of `<",
+            $(stringify!($tag),)+
+            ">`: expected `>`, found end of input. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+    (
+        @parse {
+            tag: [$($tag:tt)+],
+            input: [$unx:tt $($rest:tt)*],
+            children: $childtoks:tt,
+            callback: [
+                name: $callback:path,
+                rule: $ruletoks:tt,
+                args: $argstoks:tt,
+            ],
+        }
+    ) => {
+        compile_error!(concat!(
+            "error parsing attributes of `<",
+            $(stringify!($tag),)+
+            ">`: encountered unexpected token `",
+            stringify!($unx),
+            "`. caller: `",
+            stringify!($callback),
+            "`",
+        ));
+    };
+}
@@ -0,0 +1,193 @@
+// Checks what `build::generate` writes for tests/spec against tests/spec/traits.rs, which gets
+// `include!`d here too so it has to compile. Run with `TRAIT_XML=overwrite` to update it.
+
+// Specs can't give associated functions docs yet, so shape.xml's `unsafe fn double` has nowhere
+// to put a `# Safety` section
+#![allow(clippy::missing_safety_doc)]
+
+use std::path::Path;
+use trait_xml::build;
+
+include!("spec/traits.rs");
+
+#[test]
+fn generate() {
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join("traits.rs");
+    build::generate("tests/spec", &out).unwrap();
+    let generated = std::fs::read_to_string(&out).unwrap();
+
+    if std::env::var("TRAIT_XML").is_ok_and(|var| var == "overwrite") {
+        std::fs::write("tests/spec/traits.rs", generated).unwrap();
+    } else {
+        let expected = std::fs::read_to_string("tests/spec/traits.rs").unwrap();
+        assert_eq!(
+            generated, expected,
+            "rerun with TRAIT_XML=overwrite to accept"
+        );
+    }
+}
+
+#[test]
+fn errors() {
+    let error = |source: &str| build::to_rust(source).unwrap_err().to_string();
+
+    assert_eq!(
+        error("<trait>\n    <name>Foo</nmae>\n</trait>"),
+        "2:14: expected </name> (opened at 2:5), found </nmae>",
+    );
+    assert_eq!(
+        error("<trait>\n    <type>Vec<u8></type>\n</trait>"),
+        "2:18: expected </u8> (opened at 2:14), found </type>",
+    );
+    assert_eq!(
+        error("<trait>\n  <!-- never ends\n</trait>"),
+        "2:3: expected `-->` to close this comment",
+    );
+    assert_eq!(
+        error("<trait>\n  <type>&nbsp;</type>\n</trait>"),
+        "2:9: unknown entity `&nbsp;`",
+    );
+    assert_eq!(
+        error("<trait>\n  <rust>a < b</rust>\n</trait>"),
+        "2:11: expected a tag name after `<` - write `&lt;` for a less-than sign",
+    );
+    assert_eq!(
+        error("<trait>\n  <name>Foo</name>\n"),
+        "1:1: <trait> is never closed - if it's not meant to be a tag, write `&lt;` instead of `<`",
+    );
+    assert_eq!(
+        error("<trait>\n  <rust>&self</rust>\n</trait>"),
+        "2:9: bare `&` - write `&amp;` instead",
+    );
+    assert_eq!(
+        error("<?xml version=\"1.0\"?>\n<!DOCTYPE trait>\n<trait></trait>"),
+        "2:1: DTDs and other `<!` declarations aren't supported",
+    );
+    assert_eq!(error("<trait>\n</trait>"), "1:1: a <trait> needs a <name>");
+    assert_eq!(
+        error("<trait>\n  <name>Foo</name>\n  <name>Bar</name>\n</trait>"),
+        "3:3: only one <name> allowed here",
+    );
+    assert_eq!(
+        error("<trait>\n  <name>3d</name>\n</trait>"),
+        "2:3: expected an identifier, found `3d`",
+    );
+    assert_eq!(
+        error("<trait>\n  <name>Foo</name>\n  <sealed-for></sealed-for>\n</trait>"),
+        "3:3: a <sealed-for> needs at least one <type>",
+    );
+    assert_eq!(
+        error("<trait>\n  <name>Foo</name>\n  <where></where>\n</trait>"),
+        "3:3: <where></where> can't be empty - use <where/> for no clauses",
+    );
+    assert_eq!(
+        error(
+            "<trait>\n  <name>Foo</name>\n  <assocfn>\n    <name>bar</name>\n    <params>\n      \
+             <param mut><name>x</name><type>u8</type></param>\n    </params>\n  </assocfn>\n\
+             </trait>"
+        ),
+        "3:3: `bar` has <mut/> parameters, which need a <rust></rust> body",
+    );
+    assert_eq!(
+        error("<trait>\n  <name>Foo</name>\n  <mock name=MockFoo/>\n</trait>"),
+        "3:3: <mock> isn't supported by trait_xml::build - use trait_xml! for this trait",
+    );
+    assert_eq!(
+        error("<trait>\n  <name>Foo</name>\n  <acme:metrics/>\n</trait>"),
+        "3:3: <acme:metrics> isn't supported by trait_xml::build - use trait_xml! for this trait",
+    );
+    assert_eq!(
+        error(
+            "<trait>\n  <name>Foo</name>\n  \
+             <namespace prefix=\"acme\" handler=\"acme\"/>\n</trait>"
+        ),
+        "3:3: <namespace> isn't supported by trait_xml::build - use trait_xml! for this trait",
+    );
+    assert_eq!(
+        error("<trait name=Foo size=big>\n</trait>"),
+        "1:1: unknown attribute `size` on <trait>",
+    );
+    assert_eq!(
+        error(
+            "<trait>\n  <name>Foo</name>\n  <assoctype>\n    <ret>u8</ret>\n  </assoctype>\n\
+               </trait>"
+        ),
+        "4:5: <ret> doesn't go in <assoctype>",
+    );
+}
+
+/// The bodies of the `trait_xml!` invocations in one of the crate's trybuild tests.
+fn invocations(path: &str) -> Vec<String> {
+    let file = std::fs::read_to_string(path).unwrap();
+    file.split("trait_xml::trait_xml! {")
+        .skip(1)
+        .map(|rest| rest[..rest.find("\n}").unwrap()].to_owned())
+        .collect()
+}
+
+#[test]
+fn parity() {
+    for spec in invocations("tests/pass/parity.rs") {
+        if let Err(err) = build::to_rust(&spec) {
+            panic!("trait_xml! takes this, but build::to_rust says {err}:{spec}");
+        }
+    }
+    for spec in invocations("tests/fail/parity.rs") {
+        if let Ok(generated) = build::to_rust(&spec) {
+            panic!("trait_xml! rejects this, but build::to_rust wrote\n{generated}for{spec}");
+        }
+    }
+}
+
+#[test]
+fn rust_items_go_last() {
+    let source = "<trait>\n  <name>Foo</name>\n  <rust>fn first(&amp;self);</rust>\n  \
+                  <assocfn>\n    <name>second</name>\n  </assocfn>\n  \
+                  <assoctype>\n    <name>Third</name>\n  </assoctype>\n</trait>";
+    assert_eq!(
+        build::to_rust(source).unwrap(),
+        "trait Foo {\n    type Third;\n\n    fn second();\n\n    fn first(&self);\n}\n",
+    );
+}
+
+#[test]
+fn quoted_tokens() {
+    let source = "<trait name=\"Foo\" unsafe=\"unsafe\">\n  \
+                  <namespace prefix=\"acme\" handler=\"acme::tags\"/>\n  \
+                  <assocfn name=bar extern=\"C\">\n    \
+                  <params>\n      <param name=\"x\" type=\"&amp;'static str\" mut=\"mut\"/>\n    \
+                  </params>\n  </assocfn>\n</trait>";
+    assert_eq!(
+        build::to_tokens(source).unwrap(),
+        "<trait name={Foo} unsafe>\n  <namespace prefix={acme} handler={acme::tags}/>\n  \
+         <assocfn name=bar extern=\"C\">\n    <params>\n      \
+         <param name={x} type={&'static str} mut/>\n    </params>\n  </assocfn>\n</trait>",
+    );
+}
+
+#[test]
+fn reindent() {
+    let source = "<trait>\n  <name>Foo</name>\n  <rust>\u{3000}\u{3000}1\n  2</rust>\n</trait>";
+    assert_eq!(
+        build::to_rust(source).unwrap(),
+        "trait Foo {\n    \u{3000}\u{3000}1\n      2\n}\n",
+    );
+}
+
+#[test]
+fn quoted_attrs() {
+    let source = "<trait name=\"Foo\" vis=\"pub(crate)\" unsafe=\"unsafe\">\n  \
+                  <assocfn name=\"bar\" extern=\"C\">\n    <params>\n      \
+                  <param name=\"x\" type=\"&amp;'static str\"/>\n    </params>\n  \
+                  </assocfn>\n</trait>";
+    assert_eq!(
+        build::to_rust(source).unwrap(),
+        "pub(crate) unsafe trait Foo {\n    extern \"C\" fn bar(x: &'static str);\n}\n",
+    );
+}
+
+impl Digit for u8 {
+    fn value(&self) -> u8 {
+        *self
+    }
+}
@@ -0,0 +1,21 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>ReadWrite</name>
+        <bounds>
+            <req>std::io::Read</req>
+            <req>std::io::Write</req>
+        </bounds>
+        <assocfn>
+            <name>flush_all</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+            </params>
+        </assocfn>
+        <alias/>
+    </trait>
+}
+
+fn main() {}
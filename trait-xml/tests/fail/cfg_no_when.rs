@@ -0,0 +1,12 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <cfg>
+            <assocfn>
+                <name>bar</name>
+            </assocfn>
+        </cfg>
+    </trait>
+}
+
+fn main() {}
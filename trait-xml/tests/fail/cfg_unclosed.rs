@@ -0,0 +1,12 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <cfg>
+            <when>unix</when>
+            <assocfn>
+                <name>bar</name>
+            </assocfn>
+    </trait>
+}
+
+fn main() {}
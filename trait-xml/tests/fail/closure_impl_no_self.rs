@@ -0,0 +1,12 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Factory</name>
+        <assocfn>
+            <name>make</name>
+            <ret>u32</ret>
+        </assocfn>
+        <closure-impl/>
+    </trait>
+}
+
+fn main() {}
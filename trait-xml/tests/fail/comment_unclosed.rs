@@ -0,0 +1,8 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <!-- this never ends
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Length</name>
+        <assocfn>
+            <name>into_value</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>Box<Self></type>
+                </param>
+            </params>
+            <ret>f64</ret>
+        </assocfn>
+        <delegate>
+            <for>Meters</for>
+            <field>0</field>
+        </delegate>
+    </trait>
+}
+
+struct Meters(f64);
+
+impl Length for f64 {
+    fn into_value(self: Box<Self>) -> f64 {
+        *self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <delegate>
+            <for>Baz</for>
+        </delegate>
+    </trait>
+}
+
+struct Baz(u8);
+
+fn main() {}
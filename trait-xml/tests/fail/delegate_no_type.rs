@@ -0,0 +1,16 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assoctype>
+            <name>Bar</name>
+        </assoctype>
+        <delegate>
+            <for>Baz</for>
+            <field>0</field>
+        </delegate>
+    </trait>
+}
+
+struct Baz(u8);
+
+fn main() {}
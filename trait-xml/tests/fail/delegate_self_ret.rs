@@ -0,0 +1,29 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Length</name>
+        <assocfn>
+            <name>doubled</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>Self</ret>
+        </assocfn>
+        <delegate>
+            <for>Meters</for>
+            <field>0</field>
+        </delegate>
+    </trait>
+}
+
+struct Meters(f64);
+
+impl Length for f64 {
+    fn doubled(&self) -> Self {
+        self * 2.0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocfn>
+            <name>bar</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>Self</type>
+                </param>
+            </params>
+        </assocfn>
+        <dyn-companion>
+            <name>DynFoo</name>
+            <boxed/>
+        </dyn-companion>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocconst>
+            <name>BAR</name>
+            <type>u8</type>
+        </assocconst>
+        <dyn-safe/>
+    </trait>
+}
+
+fn main() {}
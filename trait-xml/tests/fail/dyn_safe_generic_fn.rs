@@ -0,0 +1,26 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocfn>
+            <name>bar</name>
+            <gparams>
+                <type>
+                    <name>T</name>
+                </type>
+            </gparams>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>baz</name>
+                    <type>T</type>
+                </param>
+            </params>
+        </assocfn>
+        <dyn-safe/>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocfn>
+            <name>bar</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>Self</ret>
+        </assocfn>
+        <dyn-safe/>
+    </trait>
+}
+
+fn main() {}
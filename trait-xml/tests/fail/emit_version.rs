@@ -0,0 +1,8 @@
+trait_xml::trait_xml_emit! {
+    version: 2,
+    ir: [
+        [name Foo]
+    ],
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Shape</name>
+        <assocfn>
+            <name>doubled</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>Self</ret>
+        </assocfn>
+        <enum-dispatch>
+            <name>AnyShape</name>
+            <type>Square</type>
+        </enum-dispatch>
+    </trait>
+}
+
+struct Square;
+
+impl Shape for Square {
+    fn doubled(&self) -> Self {
+        Square
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <example>
+            <test>foo_works</test>
+        </example>
+    </trait>
+}
+
+fn main() {}
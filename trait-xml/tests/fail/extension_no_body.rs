@@ -0,0 +1,20 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Total</name>
+        <extension>
+            <for>Vec<u32></for>
+        </extension>
+        <assocfn>
+            <name>total</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>u32</ret>
+        </assocfn>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Device</name>
+        <ffi-vtable>
+            <!-- forgot the wrapper -->
+            <name>DeviceVTable</name>
+        </ffi-vtable>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Device</name>
+        <assocfn>
+            <name>close</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>Self</type>
+                </param>
+            </params>
+        </assocfn>
+        <ffi-vtable>
+            <name>DeviceVTable</name>
+            <wrapper>DeviceHandle</wrapper>
+        </ffi-vtable>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+trait_xml::trait_json! {{
+    "name": Foo,
+    "assocfn": [{ "name": bar, "return": u8 }],
+}}
+
+fn main() {}
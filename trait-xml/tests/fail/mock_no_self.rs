@@ -0,0 +1,14 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocfn>
+            <name>new</name>
+            <ret>Self</ret>
+        </assocfn>
+        <mock>
+            <name>MockFoo</name>
+        </mock>
+    </trait>
+}
+
+fn main() {}
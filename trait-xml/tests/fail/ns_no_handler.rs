@@ -0,0 +1,24 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocfn>
+            <name>bar</name>
+            <acme:hot/>
+        </assocfn>
+    </trait>
+}
+
+#[allow(unused_macros)]
+macro_rules! other {
+    ($($tokens:tt)*) => {};
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Baz</name>
+        <namespace prefix=other handler=other/>
+        <acme:hot/>
+    </trait>
+}
+
+fn main() {}
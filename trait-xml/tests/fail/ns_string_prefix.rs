@@ -0,0 +1,13 @@
+#[allow(unused_macros)]
+macro_rules! acme {
+    ($($tokens:tt)*) => {};
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <namespace prefix="acme" handler=acme/>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocfn>
+            <name>bar</name>
+            <params>
+                <param>
+                    <name>buf</name>
+                    <type>Vec<u8></type>
+                    <mut/>
+                </param>
+            </params>
+        </assocfn>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Foo</name>
+        <assocfn>
+            <name>bar</name>
+            <params/>
+            <params></params>
+        </assocfn>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,113 @@
+// Every spec here is also run through `build::to_rust` by tests/build.rs, which expects all of
+// them to be rejected there too. tests/pass/parity.rs is the same for accepted ones.
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>AssocTypeEmptyBounds</name>
+        <assoctype>
+            <name>Item</name>
+            <bounds/>
+        </assoctype>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>AssocTypeEmptyWhere</name>
+        <assoctype>
+            <name>Item</name>
+            <where/>
+        </assoctype>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <vis>pubx</vis>
+        <name>BadVis</name>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>TypeClauseWithoutBounds</name>
+        <where>
+            <type-clause>
+                <type>u8</type>
+            </type-clause>
+        </where>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>LifetimeClauseWithoutBounds</name>
+        <bounds>
+            <lifetime>
+                <name>'a</name>
+            </lifetime>
+        </bounds>
+        <where>
+            <lifetime-clause>
+                <lifetime>'a</lifetime>
+            </lifetime-clause>
+        </where>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>TwoBounds</name>
+        <bounds>
+            <req>Clone</req>
+        </bounds>
+        <bounds/>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>TwoWheres</name>
+        <where/>
+        <where>
+            <type-clause>
+                <type>Self</type>
+                <type-bound>Sized</type-bound>
+            </type-clause>
+        </where>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>TwoGparams</name>
+        <assocfn>
+            <name>f</name>
+            <gparams/>
+            <gparams/>
+        </assocfn>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>EmptyGparams</name>
+        <assocfn>
+            <name>f</name>
+            <gparams></gparams>
+        </assocfn>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>TwoFnWheres</name>
+        <assocfn>
+            <name>f</name>
+            <where/>
+            <where/>
+        </assocfn>
+    </trait>
+}
+
+fn main() {}
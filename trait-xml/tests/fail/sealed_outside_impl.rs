@@ -0,0 +1,15 @@
+mod shapes {
+    trait_xml::trait_xml! {
+        <trait>
+            <vis>pub</vis>
+            <name>Shape</name>
+            <sealed/>
+        </trait>
+    }
+}
+
+struct Square;
+
+impl shapes::Shape for Square {}
+
+fn main() {}
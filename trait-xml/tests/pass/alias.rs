@@ -0,0 +1,33 @@
+use std::fmt::Debug;
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>DebugIter</name>
+        <bounds>
+            <lifetime>
+                <name>'a</name>
+            </lifetime>
+            <type>
+                <name>T</name>
+                <lifetime-bound>'a</lifetime-bound>
+            </type>
+            <req>Iterator<Item = &'a T></req>
+            <req>Clone</req>
+        </bounds>
+        <where>
+            <type-clause>
+                <type>T</type>
+                <type-bound>Debug</type-bound>
+            </type-clause>
+        </where>
+        <alias/>
+    </trait>
+}
+
+fn debug_twice<'a, T: Debug + 'a>(iter: impl DebugIter<'a, T>) -> String {
+    format!("{:?}", iter.clone().chain(iter).collect::<Vec<_>>())
+}
+
+fn main() {
+    assert_eq!(debug_twice([1, 2].iter()), "[1, 2, 1, 2]");
+}
@@ -0,0 +1,75 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Opaque</name>
+        <rust><![CDATA[
+            const TAG: &'static str = stringify!(</rust>);
+        ]]></rust>
+        <assocconst>
+            <name>ZERO</name>
+            <type><![CDATA[u8]]></type>
+            <default-value><![CDATA[<u8>::MIN]]></default-value>
+        </assocconst>
+        <assocfn>
+            <name>make</name>
+            <ret><![CDATA[Box<dyn Fn() -> u8>]]></ret>
+            <rust>
+                let tag = Self::TAG;
+                <![CDATA[
+                    Box::new(move || tag.len() as u8 + Self::ZERO)
+                ]]>
+            </rust>
+        </assocfn>
+    </trait>
+}
+
+// Receivers still count as receivers when they're in CDATA
+trait_xml::trait_xml! {
+    <trait>
+        <name>Tally</name>
+        <assocfn>
+            <name>tally</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type><![CDATA[&mut Self]]></type>
+                </param>
+                <param>
+                    <name>by</name>
+                    <type>u8</type>
+                </param>
+            </params>
+        </assocfn>
+        <closure-impl/>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Peek</name>
+        <assocfn>
+            <name>peek</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type><![CDATA[&Self]]></type>
+                </param>
+            </params>
+            <ret>u8</ret>
+        </assocfn>
+        <closure-impl/>
+    </trait>
+}
+
+struct S;
+
+impl Opaque for S {}
+
+fn main() {
+    assert_eq!(S::TAG, "</rust>");
+    assert_eq!(S::make()(), 7);
+
+    let mut total = 0;
+    (|by: u8| total += by).tally(2);
+    assert_eq!(total, 2);
+    assert_eq!((|| 3).peek(), 3);
+}
@@ -0,0 +1,85 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Store</name>
+        <assocfn>
+            <name>get</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>u32</ret>
+        </assocfn>
+        <cfg>
+            <when>all()</when>
+            <assocconst>
+                <name>CAPACITY</name>
+                <type>usize</type>
+            </assocconst>
+            <assocfn>
+                <name>present</name>
+                <params>
+                    <param>
+                        <name>self</name>
+                        <type>&Self</type>
+                    </param>
+                </params>
+                <ret>bool</ret>
+            </assocfn>
+            <cfg>
+                <when>any()</when>
+                <assoctype>
+                    <name>Gone</name>
+                </assoctype>
+                <assocfn>
+                    <name>gone</name>
+                    <params>
+                        <param>
+                            <name>self</name>
+                            <type>&Self</type>
+                        </param>
+                    </params>
+                    <ret>Self::Gone</ret>
+                </assocfn>
+            </cfg>
+        </cfg>
+        <cfg>
+            <when>any()</when>
+            <assocconst>
+                <name>ALSO_GONE</name>
+                <type>NotAType</type>
+            </assocconst>
+        </cfg>
+        <delegate>
+            <for>Wrapper</for>
+            <field>0</field>
+            <type>Inner</type>
+        </delegate>
+    </trait>
+}
+
+struct Inner;
+
+impl Store for Inner {
+    const CAPACITY: usize = 4;
+
+    fn get(&self) -> u32 {
+        7
+    }
+
+    fn present(&self) -> bool {
+        true
+    }
+}
+
+struct Wrapper(Inner);
+
+fn main() {
+    let w = Wrapper(Inner);
+    assert_eq!(w.get(), 7);
+    assert!(w.present());
+    assert_eq!(<Wrapper as Store>::CAPACITY, 4);
+}
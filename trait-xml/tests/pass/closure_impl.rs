@@ -0,0 +1,61 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Callback</name>
+        <bounds>
+            <type>
+                <name>T</name>
+            </type>
+        </bounds>
+        <assocconst>
+            <name>NAME</name>
+            <type>&'static str</type>
+            <default-value>"callback"</default-value>
+        </assocconst>
+        <assocfn>
+            <name>call</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+                <param>
+                    <name>value</name>
+                    <type>T</type>
+                </param>
+            </params>
+        </assocfn>
+        <closure-impl/>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Finish</name>
+        <assocfn>
+            <name>finish</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>Self</type>
+                </param>
+            </params>
+            <ret>String</ret>
+        </assocfn>
+        <closure-impl/>
+    </trait>
+}
+
+fn run<T>(values: Vec<T>, mut callback: impl Callback<T>) {
+    for value in values {
+        callback.call(value);
+    }
+}
+
+fn main() {
+    let mut total = 0;
+    run(vec![1, 2, 3], |value: u32| total += value);
+    assert_eq!(total, 6);
+
+    let name = String::from("done");
+    assert_eq!((move || name).finish(), "done");
+}
@@ -0,0 +1,135 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Length</name>
+        <bounds>
+            <type>
+                <name>T</name>
+            </type>
+        </bounds>
+        <assoctype>
+            <name>Unit</name>
+        </assoctype>
+        <assocconst>
+            <name>SCALE</name>
+            <type>f64</type>
+        </assocconst>
+        <assocfn>
+            <name>value</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>f64</ret>
+        </assocfn>
+        <assocfn>
+            <name>scale</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+                <param>
+                    <name>by</name>
+                    <type>T</type>
+                </param>
+            </params>
+        </assocfn>
+        <assocfn>
+            <name>into_value</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>Self</type>
+                </param>
+            </params>
+            <ret>f64</ret>
+        </assocfn>
+        <assocfn>
+            <name>unit</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>Option<Self::Unit></ret>
+        </assocfn>
+        <delegate>
+            <for>Meters</for>
+            <field>0</field>
+            <type>Raw</type>
+        </delegate>
+        <delegate>
+            <for>Named</for>
+            <field>raw</field>
+            <type>Raw</type>
+        </delegate>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Describe</name>
+        <assocfn>
+            <name>describe</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>String</ret>
+        </assocfn>
+        <delegate>
+            <for>Meters</for>
+            <field>0.0</field>
+        </delegate>
+    </trait>
+}
+
+struct Raw(f64);
+
+impl Length<f64> for Raw {
+    type Unit = ();
+    const SCALE: f64 = 2.0;
+    fn value(&self) -> f64 {
+        self.0
+    }
+    fn scale(&mut self, by: f64) {
+        self.0 *= by;
+    }
+    fn into_value(self) -> f64 {
+        self.0
+    }
+    fn unit(&self) -> Option<Self::Unit> {
+        Some(())
+    }
+}
+
+impl Describe for f64 {
+    fn describe(&self) -> String {
+        format!("{self}m")
+    }
+}
+
+struct Meters(Raw);
+
+struct Named {
+    raw: Raw,
+}
+
+fn main() {
+    let mut meters = Meters(Raw(1.5));
+    meters.scale(2.0);
+    assert_eq!(meters.value(), 3.0);
+    assert_eq!(<Meters as Length<f64>>::SCALE, 2.0);
+    assert_eq!(meters.describe(), "3m");
+    assert_eq!(meters.unit(), Some(()));
+    assert_eq!(meters.into_value(), 3.0);
+    let named = Named { raw: Raw(4.0) };
+    assert_eq!(named.into_value(), 4.0);
+}
@@ -0,0 +1,122 @@
+#![recursion_limit = "256"]
+
+use trait_xml::{ConstDescriptor, FnDescriptor, ParamDescriptor, TraitDescriptor};
+
+trait_xml::trait_xml! {
+    <trait>
+        <vis>pub(crate)</vis>
+        <unsafe/>
+        <name>Store</name>
+        <bounds>
+            <lifetime>
+                <name>'a</name>
+            </lifetime>
+            <type>
+                <name>K</name>
+            </type>
+            <const>
+                <name>N</name>
+                <type>usize</type>
+            </const>
+            <req>Clone</req>
+            <req>std::fmt::Debug</req>
+        </bounds>
+        <sealed/>
+        <assoctype>
+            <name>Value</name>
+        </assoctype>
+        <assocconst>
+            <name>CAPACITY</name>
+            <type>usize</type>
+            <default-value>N</default-value>
+        </assocconst>
+        <assocfn>
+            <name>get</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>key</name>
+                    <type>&'a K</type>
+                </param>
+            </params>
+            <ret>Option<&Self::Value></ret>
+        </assocfn>
+        <assocfn>
+            <name>clear</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+            </params>
+            <rust>let _ = self;</rust>
+        </assocfn>
+        <assocfn>
+            <name>visit</name>
+            <params>
+                <param>
+                    <name>f</name>
+                    <type>& mut dyn FnMut(&'a K)->bool</type>
+                </param>
+            </params>
+        </assocfn>
+        <descriptor>
+            <name>STORE_DESCRIPTOR</name>
+        </descriptor>
+    </trait>
+}
+
+fn main() {
+    const EXPECTED: TraitDescriptor = TraitDescriptor {
+        name: "Store",
+        vis: "pub(crate)",
+        is_unsafe: true,
+        generic_params: &["'a", "K", "N"],
+        supertraits: &["Clone", "std::fmt::Debug"],
+        assoc_types: &["Value"],
+        assoc_consts: &[ConstDescriptor {
+            name: "CAPACITY",
+            ty: "usize",
+            has_default: true,
+        }],
+        fns: &[
+            FnDescriptor {
+                name: "get",
+                params: &[
+                    ParamDescriptor {
+                        name: "self",
+                        ty: "&Self",
+                    },
+                    ParamDescriptor {
+                        name: "key",
+                        ty: "&'a K",
+                    },
+                ],
+                ret: Some("Option<&Self::Value>"),
+                has_default: false,
+            },
+            FnDescriptor {
+                name: "clear",
+                params: &[ParamDescriptor {
+                    name: "self",
+                    ty: "&mut Self",
+                }],
+                ret: None,
+                has_default: true,
+            },
+            FnDescriptor {
+                name: "visit",
+                params: &[ParamDescriptor {
+                    name: "f",
+                    ty: "&mut dyn FnMut(&'a K) -> bool",
+                }],
+                ret: None,
+                has_default: false,
+            },
+        ],
+    };
+    assert_eq!(STORE_DESCRIPTOR, EXPECTED);
+}
@@ -0,0 +1,142 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Codec</name>
+        <assoctype>
+            <name>Item</name>
+        </assoctype>
+        <assocconst>
+            <name>VERSION</name>
+            <type>u8</type>
+        </assocconst>
+        <assocfn>
+            <name>encode</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>item</name>
+                    <type>&Self::Item</type>
+                </param>
+            </params>
+            <ret>Vec<u8></ret>
+        </assocfn>
+        <assocfn>
+            <name>encode_all</name>
+            <gparams>
+                <type>
+                    <name>I</name>
+                    <type-bound>IntoIterator<Item = Self::Item></type-bound>
+                </type>
+            </gparams>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>items</name>
+                    <type>I</type>
+                </param>
+            </params>
+            <ret>Vec<u8></ret>
+        </assocfn>
+        <assocfn>
+            <name>fresh</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>Box<Self></ret>
+        </assocfn>
+        <dyn-companion>
+            <name>DynCodec</name>
+        </dyn-companion>
+    </trait>
+}
+
+#[derive(Clone)]
+struct Bytes;
+
+impl Codec for Bytes {
+    type Item = u8;
+    const VERSION: u8 = 1;
+
+    fn encode(&self, item: &u8) -> Vec<u8> {
+        vec![*item]
+    }
+
+    fn encode_all<I: IntoIterator<Item = u8>>(&self, items: I) -> Vec<u8> {
+        items.into_iter().collect()
+    }
+
+    fn fresh(&self) -> Box<Self> {
+        Box::new(self.clone())
+    }
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Counter</name>
+        <assocfn>
+            <name>bump</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+                <param>
+                    <name>by</name>
+                    <type>u32</type>
+                </param>
+            </params>
+        </assocfn>
+        <assocfn>
+            <name>get</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>u32</ret>
+        </assocfn>
+        <dyn-companion>
+            <name>DynCounter</name>
+            <boxed/>
+        </dyn-companion>
+    </trait>
+}
+
+struct Simple(u32);
+
+impl Counter for Simple {
+    fn bump(&mut self, by: u32) {
+        self.0 += by;
+    }
+
+    fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+fn bump_twice<C: Counter>(counter: &mut C) {
+    counter.bump(1);
+    counter.bump(1);
+}
+
+fn main() {
+    let codec: &dyn DynCodec<Item = u8> = &Bytes;
+    assert_eq!(codec.encode(&3), vec![3]);
+    assert_eq!(Codec::encode_all(&Bytes, [1, 2]), vec![1, 2]);
+    let _: Box<Bytes> = DynCodec::fresh(&Bytes);
+
+    let mut counter: Box<dyn DynCounter> = Box::new(Simple(0));
+    bump_twice(&mut counter);
+    assert_eq!(Counter::get(&counter), 2);
+}
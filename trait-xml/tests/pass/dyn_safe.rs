@@ -0,0 +1,63 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Shape</name>
+        <bounds>
+            <lifetime>
+                <name>'a</name>
+            </lifetime>
+            <type>
+                <name>T</name>
+                <type-bound>Clone</type-bound>
+            </type>
+        </bounds>
+        <assoctype>
+            <name>Unit</name>
+        </assoctype>
+        <assocfn>
+            <name>area</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>scale</name>
+                    <type>&'a T</type>
+                </param>
+            </params>
+            <ret>f64</ret>
+        </assocfn>
+        <assocfn>
+            <name>scaled</name>
+            <gparams>
+                <type>
+                    <name>U</name>
+                    <type-bound>Into<f64></type-bound>
+                </type>
+            </gparams>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>by</name>
+                    <type>U</type>
+                </param>
+            </params>
+            <ret>Self</ret>
+            <where>
+                <type-clause>
+                    <type>Self</type>
+                    <type-bound>Sized</type-bound>
+                    <type-bound>Clone</type-bound>
+                </type-clause>
+            </where>
+        </assocfn>
+        <dyn-safe/>
+    </trait>
+}
+
+fn main() {}
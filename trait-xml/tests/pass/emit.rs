@@ -0,0 +1,65 @@
+trait_xml::trait_xml_emit! {
+    version: 1,
+    ir: [
+        [vis pub(crate)]
+        [name Store]
+        [gparams [K: Eq, V,] reqs [Default +]]
+        [wc [V: Clone]]
+        [at [Iter] [] [Iterator<Item = V>] [] [[doc = "All the values."]]]
+        [ac [CAPACITY] [usize] [16] []]
+        [
+            af
+            [get]
+            [unsafe: [], extern: [],]
+            []
+            [[param [self] [&Self] [ref] [] []] [param [key] [&K] [] [] []]]
+            [Option<V>]
+            []
+            []
+            [[doc = "Looks a value up."]]
+            []
+        ]
+        [cfgstart [any()]]
+        [
+            af
+            [gone]
+            [unsafe: [], extern: [],]
+            []
+            []
+            []
+            []
+            []
+            []
+            []
+        ]
+        [cfgend]
+        [
+            af
+            [len]
+            [unsafe: [], extern: [],]
+            []
+            [[param [self] [&Self] [ref] [] []]]
+            [usize]
+            []
+            [Self::CAPACITY]
+            []
+            []
+        ]
+    ],
+}
+
+#[derive(Default)]
+struct Empty;
+
+impl Store<u8, u8> for Empty {
+    type Iter = core::iter::Empty<u8>;
+
+    fn get(&self, _key: &u8) -> Option<u8> {
+        None
+    }
+}
+
+fn main() {
+    assert_eq!(Empty.get(&1), None);
+    assert_eq!(Empty.len(), 16);
+}
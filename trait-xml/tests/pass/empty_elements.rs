@@ -0,0 +1,23 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Empty</name>
+        <bounds/>
+        <where/>
+        <assocfn>
+            <name>nothing</name>
+            <gparams/>
+            <params/>
+            <ret>u8</ret>
+            <where/>
+            <rust>0</rust>
+        </assocfn>
+    </trait>
+}
+
+struct S;
+
+impl Empty for S {}
+
+fn main() {
+    assert_eq!(S::nothing(), 0);
+}
@@ -0,0 +1,57 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Escaped</name>
+        <bounds>
+            <type>
+                <name>T</name>
+                <type-bound>Into&lt;Vec&lt;u8&gt;&gt;</type-bound>
+            </type>
+            <type>
+                <name>F</name>
+                <type-bound>Fn(&amp;[Option&lt;u8&gt;; 2]) -&gt; u8</type-bound>
+            </type>
+            <req>AsRef&lt;str&gt;</req>
+        </bounds>
+        <assocconst>
+            <name>TABLE</name>
+            <type>&amp;'static [(u8, Option&lt;u8&gt;)]</type>
+        </assocconst>
+        <assocfn>
+            <name>first</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&amp;Self</type>
+                </param>
+                <param>
+                    <name>f</name>
+                    <type>&amp;F</type>
+                </param>
+            </params>
+            <ret>Result&lt;u8, Box&lt;dyn std::error::Error&gt;&gt;</ret>
+        </assocfn>
+    </trait>
+}
+
+struct S(String);
+
+impl AsRef<str> for S {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<F: Fn(&[Option<u8>; 2]) -> u8> Escaped<Vec<u8>, F> for S {
+    const TABLE: &'static [(u8, Option<u8>)] = &[(1, None)];
+
+    fn first(&self, f: &F) -> Result<u8, Box<dyn std::error::Error>> {
+        Ok(f(&[Some(self.0.len() as u8), <Self as Escaped<Vec<u8>, F>>::TABLE[0].1]))
+    }
+}
+
+fn main() {
+    let f = |xs: &[Option<u8>; 2]| xs[0].unwrap_or(0);
+    assert_eq!(Escaped::<Vec<u8>, _>::first(&S("ab".into()), &f).unwrap(), 2);
+}
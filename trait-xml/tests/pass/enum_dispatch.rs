@@ -0,0 +1,95 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait>
+        <vis>pub</vis>
+        <name>Counter</name>
+        <bounds>
+            <type>
+                <name>T</name>
+                <type-bound>Copy</type-bound>
+            </type>
+        </bounds>
+        <assocfn>
+            <name>add</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+                <param>
+                    <name>by</name>
+                    <type>T</type>
+                </param>
+            </params>
+        </assocfn>
+        <assocfn>
+            <name>get</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>T</ret>
+        </assocfn>
+        <assocfn>
+            <name>into_name</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>Self</type>
+                </param>
+            </params>
+            <ret>&'static str</ret>
+        </assocfn>
+        <enum-dispatch>
+            <name>AnyCounter</name>
+            <type>Simple</type>
+            <type>Doubling</type>
+        </enum-dispatch>
+    </trait>
+}
+
+pub struct Simple(u32);
+pub struct Doubling(u32);
+
+impl Counter<u32> for Simple {
+    fn add(&mut self, by: u32) {
+        self.0 += by;
+    }
+
+    fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn into_name(self) -> &'static str {
+        "simple"
+    }
+}
+
+impl Counter<u32> for Doubling {
+    fn add(&mut self, by: u32) {
+        self.0 += by * 2;
+    }
+
+    fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn into_name(self) -> &'static str {
+        "doubling"
+    }
+}
+
+fn main() {
+    let mut counters: Vec<AnyCounter> = vec![Simple(0).into(), Doubling(0).into()];
+    for counter in &mut counters {
+        counter.add(3);
+    }
+    assert_eq!(counters.iter().map(Counter::get).collect::<Vec<_>>(), [3, 6]);
+    assert_eq!(
+        counters.into_iter().map(Counter::into_name).collect::<Vec<_>>(),
+        ["simple", "doubling"],
+    );
+}
@@ -0,0 +1,63 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>Greet</name>
+        <example>
+            <test>greet_works</test>
+            struct World;
+            impl Greet for World {
+                fn name(&self) -> String {
+                    "world".to_string()
+                }
+            }
+            assert_eq!(World.greeting(), "hello world");
+        </example>
+        <example>
+            fn greet_everyone(everyone: &[&dyn Greet]) -> Vec<String> {
+                everyone.iter().map(|g| g.greeting()).collect()
+            }
+        </example>
+        <assocfn>
+            <name>name</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>String</ret>
+        </assocfn>
+        <assocfn>
+            <name>greeting</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>String</ret>
+            <example>
+                <test>greeting_uses_name</test>
+                struct Bob;
+                impl Greet for Bob {
+                    fn name(&self) -> String {
+                        "Bob".to_string()
+                    }
+                }
+                assert_eq!(Bob.greeting(), "hello Bob");
+            </example>
+            <rust>format!("hello {}", self.name())</rust>
+        </assocfn>
+    </trait>
+}
+
+struct World;
+
+impl Greet for World {
+    fn name(&self) -> String {
+        "world".to_string()
+    }
+}
+
+fn main() {
+    assert_eq!(World.greeting(), "hello world");
+}
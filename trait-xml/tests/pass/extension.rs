@@ -0,0 +1,67 @@
+trait_xml::trait_xml! {
+    <trait>
+        <name>NextOr</name>
+        <bounds>
+            <type>
+                <name>T</name>
+                <type-bound>Copy</type-bound>
+            </type>
+        </bounds>
+        <extension>
+            <for>I: Iterator<Item = T></for>
+        </extension>
+        <assocconst>
+            <name>EXTENDED</name>
+            <type>bool</type>
+            <default-value>true</default-value>
+        </assocconst>
+        <assocfn>
+            <name>next_or</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+                <param>
+                    <name>default</name>
+                    <type>T</type>
+                </param>
+            </params>
+            <ret>T</ret>
+            <rust>
+                self.next().unwrap_or(default)
+            </rust>
+        </assocfn>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Total</name>
+        <extension>
+            <for>Vec<u32></for>
+        </extension>
+        <assocfn>
+            <name>total</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>u32</ret>
+            <rust>
+                self.iter().sum()
+            </rust>
+        </assocfn>
+    </trait>
+}
+
+fn main() {
+    let mut chars = "a".chars();
+    assert_eq!(chars.next_or('z'), 'a');
+    assert_eq!(chars.next_or('z'), 'z');
+    assert!(<std::str::Chars<'_> as NextOr<char>>::EXTENDED);
+
+    assert_eq!(vec![1, 2, 3].total(), 6);
+}
@@ -0,0 +1,80 @@
+use std::ffi::c_void;
+
+trait_xml::trait_xml! {
+    <trait>
+        <vis>pub</vis>
+        <name>Device</name>
+        <assocconst>
+            <name>VERSION</name>
+            <type>u32</type>
+            <default-value>1</default-value>
+        </assocconst>
+        <assocfn>
+            <name>id</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>u64</ret>
+        </assocfn>
+        <assocfn>
+            <name>write</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+                <param>
+                    <name>data</name>
+                    <type>*const u8</type>
+                </param>
+                <param>
+                    <name>len</name>
+                    <type>usize</type>
+                </param>
+            </params>
+        </assocfn>
+        <ffi-vtable>
+            <wrapper>DeviceHandle</wrapper>
+            <name>DeviceVTable</name>
+        </ffi-vtable>
+    </trait>
+}
+
+struct Buffer(Vec<u8>);
+
+impl Device for Buffer {
+    fn id(&self) -> u64 {
+        7
+    }
+
+    fn write(&mut self, data: *const u8, len: usize) {
+        self.0
+            .extend_from_slice(unsafe { std::slice::from_raw_parts(data, len) });
+    }
+}
+
+// What a plugin would export
+extern "C" fn plugin_vtable() -> DeviceVTable {
+    DeviceVTable::vtable_for::<Buffer>()
+}
+
+fn main() {
+    assert_eq!(std::mem::size_of::<DeviceVTable>(), 2 * std::mem::size_of::<usize>());
+
+    let mut buffer = Buffer(Vec::new());
+    let this = &mut buffer as *mut Buffer as *mut c_void;
+    let vtable = plugin_vtable();
+    assert_eq!(unsafe { (vtable.id)(this) }, 7);
+
+    let mut handle = unsafe { DeviceHandle::from_raw(this, vtable) };
+    handle.write([1, 2].as_ptr(), 2);
+    assert_eq!(handle.id(), 7);
+    assert_eq!(<DeviceHandle as Device>::VERSION, 1);
+
+    let mut handle = DeviceHandle::new(&mut buffer);
+    handle.write([3].as_ptr(), 1);
+    assert_eq!(buffer.0, [1, 2, 3]);
+}
@@ -0,0 +1,76 @@
+#![recursion_limit = "256"]
+
+use std::fmt::Debug;
+
+trait_xml::trait_json! {{
+    "name": Buffer,
+    "vis": pub(crate),
+    "gparams": [
+        { "lifetime": 'a },
+        { "name": T, "bounds": [Clone, Debug, 'a] },
+        { "const": N, "type": usize },
+    ],
+    "reqs": [Default],
+    "where": [{ "type": Vec<T>, "bounds": [Debug] }],
+    "assoctype": [{ "name": Iter, "bounds": [Iterator<Item = T>] }],
+    "assocconst": [
+        { "name": CAPACITY, "type": usize, "default": N },
+        { "type": u8, "name": TAG },
+    ],
+    "assocfn": [
+        {
+            "name": push,
+            "params": [{ "name": self, "type": &mut Self }, { "name": item, "type": T }],
+        },
+        {
+            "rust": {
+                item = &*item;
+                item.clone().into()
+            },
+            "ret": U,
+            "name": convert,
+            "gparams": [{ "name": U }],
+            "where": [{ "type": T, "bounds": [Into<U>] }, { "lifetime": 'a, "bounds": ['a] }],
+            "params": [
+                { "name": self, "type": &Self },
+                { "name": item, "type": &T, "mut": true },
+            ],
+        },
+        {
+            "name": raw,
+            "unsafe": true,
+            "extern": "C",
+            "ret": usize,
+            "rust": { Self::CAPACITY },
+        },
+    ],
+}}
+
+trait_xml::trait_json! {{
+    "unsafe": true,
+    "name": Marker,
+}}
+
+#[derive(Default)]
+struct Stack(Vec<u8>);
+
+impl<'a> Buffer<'a, u8, 4> for Stack {
+    type Iter = std::vec::IntoIter<u8>;
+
+    const TAG: u8 = 7;
+
+    fn push(&mut self, item: u8) {
+        self.0.push(item);
+    }
+}
+
+unsafe impl Marker for Stack {}
+
+fn main() {
+    let mut s = Stack::default();
+    s.push(3);
+    assert_eq!(s.0, [3]);
+    assert_eq!(Buffer::<u8, 4>::convert::<u32>(&s, &5), 5u32);
+    assert_eq!(unsafe { <Stack as Buffer<u8, 4>>::raw() }, 4);
+    assert_eq!(<Stack as Buffer<u8, 4>>::TAG, 7);
+}
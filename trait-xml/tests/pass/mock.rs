@@ -0,0 +1,105 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Store</name>
+        <assocconst>
+            <name>CAPACITY</name>
+            <type>usize</type>
+            <default-value>16</default-value>
+        </assocconst>
+        <assocfn>
+            <name>get</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>key</name>
+                    <type>&str</type>
+                </param>
+            </params>
+            <ret>Option<u32></ret>
+        </assocfn>
+        <assocfn>
+            <name>put</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+                <param>
+                    <name>key</name>
+                    <type>String</type>
+                </param>
+                <param>
+                    <name>value</name>
+                    <type>u32</type>
+                </param>
+            </params>
+        </assocfn>
+        <assocfn>
+            <name>same</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>other</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>bool</ret>
+        </assocfn>
+        <mock>
+            <name>MockStore</name>
+        </mock>
+    </trait>
+}
+
+fn main() {
+    let mut mock = MockStore::default();
+    mock.get
+        .returns(Some(1))
+        .expect(Box::new(|key: &str| Some(key.len() as u32)));
+    assert_eq!(mock.get("a"), Some(1));
+    assert_eq!(mock.get("abc"), Some(3));
+    assert_eq!(
+        mock.get.calls(),
+        vec![vec!["\"a\"".to_string()], vec!["\"abc\"".to_string()]]
+    );
+
+    mock.put.expect(Box::new(|_, _| ()));
+    mock.put("a".to_string(), 2);
+    assert_eq!(mock.put.times_called(), 1);
+
+    let other = MockStore::default();
+    mock.same.returns(true);
+    assert!(mock.same(&other));
+    assert_eq!(MockStore::CAPACITY, 16);
+
+    // Expectations can use the mock they belong to
+    let shared = std::rc::Rc::new(MockStore::default());
+    let weak = std::rc::Rc::downgrade(&shared);
+    shared.get.expect(Box::new(move |key: &str| {
+        let mock = weak.upgrade().unwrap();
+        match key {
+            "again" => {
+                mock.get.returns(Some(7));
+                mock.get("inner").map(|inner| inner + 1)
+            }
+            _ => {
+                mock.get.expect(Box::new(|_| None));
+                Some(2)
+            }
+        }
+    }));
+    shared.get.returns(Some(1));
+    assert_eq!(shared.get("first"), Some(1));
+    assert_eq!(shared.get("again"), Some(8));
+    assert_eq!(shared.get("other"), Some(2));
+    assert_eq!(shared.get("last"), None);
+    assert_eq!(shared.get.times_called(), 5);
+}
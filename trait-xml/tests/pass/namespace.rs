@@ -0,0 +1,154 @@
+#![recursion_limit = "256"]
+
+macro_rules! acme {
+    (
+        @tag {
+            context: [trait],
+            ns: acme,
+            tag: note,
+            attrs: [],
+            body: [$($body:tt)*],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($args)*
+                attrs: [[doc = stringify!($($body)*)]],
+                items: [],
+            }
+        }
+    };
+    (
+        @tag {
+            context: [trait],
+            ns: acme,
+            tag: marker,
+            attrs: [name = $name:ident],
+            body: [],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($args)*
+                attrs: [],
+                items: [pub struct $name;],
+            }
+        }
+    };
+    (
+        @tag {
+            context: [assocfn $fname:ident],
+            ns: acme,
+            tag: tagged,
+            attrs: [const = $const:ident],
+            body: [],
+            callback: [
+                name: $callback:path,
+                rule: [$($rule:tt)+],
+                args: [$($args:tt)*],
+            ],
+        }
+    ) => {
+        $callback! {
+            $($rule)+ {
+                $($args)*
+                attrs: [[must_use]],
+                items: [pub const $const: &str = stringify!($fname);],
+            }
+        }
+    };
+}
+
+mod probes {
+    macro_rules! probe {
+        (
+            @tag {
+                context: [$($context:tt)+],
+                ns: probe,
+                tag: count,
+                attrs: [name = $name:ident],
+                body: [],
+                callback: [
+                    name: $callback:path,
+                    rule: [$($rule:tt)+],
+                    args: [$($args:tt)*],
+                ],
+            }
+        ) => {
+            $callback! {
+                $($rule)+ {
+                    $($args)*
+                    attrs: [],
+                    items: [pub const $name: &str = stringify!($($context)+);],
+                }
+            }
+        };
+    }
+
+    pub(crate) use probe;
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Sized2</name>
+        <namespace prefix=acme handler=acme/>
+        <acme:note>Has a size.</acme:note>
+        <acme:marker name=Marker/>
+        <assocfn>
+            <name>size</name>
+            <params>
+                <param><name>self</name><type>&Self</type></param>
+            </params>
+            <ret>usize</ret>
+            <acme:tagged const=SIZE_FN/>
+            <rust>core::mem::size_of_val(self)</rust>
+        </assocfn>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Counted</name>
+        <namespace prefix={acme} handler={acme}/>
+        <namespace prefix=probe handler=probes::probe/>
+        <probe:count name=COUNTED/>
+        <cfg>
+            <when>any()</when>
+            <assocfn>
+                <name>gone</name>
+                <acme:tagged const=GONE_FN/>
+            </assocfn>
+        </cfg>
+        <assocfn>
+            <name>count</name>
+            <ret>u32</ret>
+            <acme:tagged const=COUNT_FN/>
+            <probe:count name=COUNT_PROBE/>
+            <rust>1</rust>
+        </assocfn>
+    </trait>
+}
+
+impl Sized2 for u64 {}
+impl Counted for u64 {}
+
+fn main() {
+    let _ = Marker;
+    assert_eq!(7u64.size(), 8);
+    assert_eq!(u64::count(), 1);
+    assert_eq!(SIZE_FN, "size");
+    assert_eq!(COUNT_FN, "count");
+    assert_eq!(GONE_FN, "gone");
+    assert_eq!(COUNTED, "trait");
+    assert_eq!(COUNT_PROBE, "assocfn count");
+}
@@ -0,0 +1,62 @@
+#![recursion_limit = "256"]
+#![deny(unused_variables)]
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Encode</name>
+        <assocfn>
+            <name>tag</name>
+            <params>
+                <param>
+                    <attr>allow(unused_variables)</attr>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>u8</ret>
+            <rust>0</rust>
+        </assocfn>
+        <assocfn>
+            <name>encode</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <name>buf</name>
+                    <type>Vec<u8></type>
+                    <mut/>
+                </param>
+                <param>
+                    <name>extra</name>
+                    <attr>allow(unused_variables)</attr>
+                    <attr>allow(unused_mut)</attr>
+                    <mut/>
+                    <type>u8</type>
+                </param>
+            </params>
+            <ret>Vec<u8></ret>
+            <rust>
+                buf.push(self.tag());
+                buf
+            </rust>
+        </assocfn>
+        <delegate>
+            <for>Wrapper</for>
+            <field>0</field>
+            <type>Unit</type>
+        </delegate>
+    </trait>
+}
+
+struct Unit;
+
+impl Encode for Unit {}
+
+struct Wrapper(Unit);
+
+fn main() {
+    assert_eq!(Unit.encode(vec![1], 2), vec![1, 0]);
+    assert_eq!(Wrapper(Unit).encode(vec![], 2), vec![0]);
+}
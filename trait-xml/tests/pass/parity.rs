@@ -0,0 +1,55 @@
+// Every spec here is also run through `build::to_rust` by tests/build.rs, which expects all of
+// them to be accepted there too. tests/fail/parity.rs is the same for rejected ones.
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>EmptySections</name>
+        <bounds/>
+        <where/>
+        <assocfn>
+            <name>nothing</name>
+            <gparams/>
+            <params/>
+            <where/>
+        </assocfn>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <vis>pub(crate)</vis>
+        <name>EmptyBounds</name>
+        <bounds></bounds>
+        <assoctype>
+            <name>Item</name>
+            <bounds></bounds>
+        </assoctype>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <vis>pub(in crate)</vis>
+        <name>Clauses</name>
+        <bounds>
+            <lifetime>
+                <name>'a</name>
+            </lifetime>
+            <lifetime>
+                <name>'b</name>
+            </lifetime>
+        </bounds>
+        <where>
+            <type-clause>
+                <type>Self</type>
+                <type-bound>Sized</type-bound>
+            </type-clause>
+            <lifetime-clause>
+                <lifetime>'a</lifetime>
+                <lifetime-bound>'b</lifetime-bound>
+            </lifetime-clause>
+        </where>
+    </trait>
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+macro_rules! getter {
+    ($name:ident: $ty:ty) => {
+        fn $name(&self) -> $ty;
+    };
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Point</name>
+        <rust>
+            getter!(x: f64);
+        </rust>
+        <assocfn>
+            <name>norm</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+            </params>
+            <ret>f64</ret>
+            <rust>self.x().hypot(self.y())</rust>
+        </assocfn>
+        <rust>
+            getter!(y: f64);
+
+            const ORIGIN: (f64, f64) = (0.0, 0.0);
+        </rust>
+    </trait>
+}
+
+struct P(f64, f64);
+
+impl Point for P {
+    fn x(&self) -> f64 {
+        self.0
+    }
+
+    fn y(&self) -> f64 {
+        self.1
+    }
+}
+
+fn main() {
+    assert_eq!(P(3.0, 4.0).norm(), 5.0);
+    assert_eq!(P::ORIGIN, (0.0, 0.0));
+}
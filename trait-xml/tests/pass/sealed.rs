@@ -0,0 +1,48 @@
+mod shapes {
+    trait_xml::trait_xml! {
+        <trait>
+            <vis>pub</vis>
+            <name>Shape</name>
+            <sealed/>
+            <bounds>
+                <req>Clone</req>
+            </bounds>
+            <sealed-for>
+                <type>Square</type>
+                <type>Vec<Square></type>
+            </sealed-for>
+            <assocfn>
+                <name>area</name>
+                <params>
+                    <param>
+                        <name>self</name>
+                        <type>&Self</type>
+                    </param>
+                </params>
+                <ret>f64</ret>
+            </assocfn>
+        </trait>
+    }
+
+    #[derive(Clone)]
+    pub struct Square(pub f64);
+
+    impl Shape for Square {
+        fn area(&self) -> f64 {
+            self.0 * self.0
+        }
+    }
+
+    impl Shape for Vec<Square> {
+        fn area(&self) -> f64 {
+            self.iter().map(Shape::area).sum()
+        }
+    }
+}
+
+use shapes::Shape;
+
+fn main() {
+    assert_eq!(shapes::Square(2.0).area(), 4.0);
+    assert_eq!(vec![shapes::Square(1.0), shapes::Square(2.0)].area(), 5.0);
+}
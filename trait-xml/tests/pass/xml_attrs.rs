@@ -0,0 +1,82 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <trait unsafe>
+        <name>Raw</name>
+        <assocfn unsafe extern="C">
+            <name>bump</name>
+            <params>
+                <param mut>
+                    <name>x</name>
+                    <type>u8</type>
+                </param>
+            </params>
+            <ret>u8</ret>
+            <rust>
+                x += 1;
+                x
+            </rust>
+        </assocfn>
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait name=Shape vis=pub(crate)>
+        <assocfn name=area>
+            <params>
+                <param name=self type={&Self}/>
+            </params>
+            <ret>u32</ret>
+        </assocfn>
+        <assocfn name=scale>
+            <params>
+                <param name=self type={&mut Self}/>
+                <param name=by type=u32/>
+            </params>
+        </assocfn>
+        <mock name=MockShape/>
+        <dyn-companion name=DynShape/>
+        <enum-dispatch name=AnyShape>
+            <type>Square</type>
+        </enum-dispatch>
+        <ffi-vtable name=ShapeVTable wrapper=ShapeHandle/>
+    </trait>
+}
+
+struct S;
+
+unsafe impl Raw for S {}
+
+pub(crate) struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+
+    fn scale(&mut self, by: u32) {
+        self.0 *= by;
+    }
+}
+
+fn main() {
+    let f: unsafe extern "C" fn(u8) -> u8 = <S as Raw>::bump;
+    assert_eq!(unsafe { f(1) }, 2);
+
+    let mut shape = AnyShape::from(Square(2));
+    Shape::scale(&mut shape, 2);
+    assert_eq!(Shape::area(&shape), 16);
+
+    let boxed: Box<dyn DynShape> = Box::new(Square(3));
+    assert_eq!(boxed.area(), 9);
+
+    let mut square = Square(1);
+    let vtable = ShapeVTable::vtable_for::<Square>();
+    let this = &mut square as *mut Square as *mut std::ffi::c_void;
+    assert_eq!(unsafe { (vtable.area)(this) }, 1);
+    let _ = ShapeHandle::new(&mut square);
+
+    let mock = MockShape::default();
+    mock.area.returns(4);
+    assert_eq!(Shape::area(&mock), 4);
+}
@@ -0,0 +1,106 @@
+#![recursion_limit = "256"]
+
+trait_xml::trait_xml! {
+    <!-- a trait with comments everywhere tags can go -->
+    <trait>
+        <!-- the name -->
+        <name>Commented</name>
+        <bounds>
+            <!-- one type parameter -->
+            <type>
+                <!-- called T -->
+                <name>T</name>
+            </type>
+            <!-- has to be cloneable -->
+            <req>Clone</req>
+        </bounds>
+        <where>
+            <!-- and so does T -->
+            <type-clause>
+                <type>T</type>
+                <type-bound>Clone</type-bound>
+            </type-clause>
+        </where>
+        <assocfn>
+            <!-- the only method -->
+            <name>pick</name>
+            <params>
+                <param>
+                    <!-- the receiver -->
+                    <name>self</name>
+                    <type>&Self</type>
+                </param>
+                <param>
+                    <!-- what to pick if the flag is off -->
+                    <name>other</name>
+                    <type>T</type>
+                </param>
+                <!-- nothing after this -->
+            </params>
+            <ret>T</ret>
+        </assocfn>
+        <!-- that is everything -->
+    </trait>
+}
+
+trait_xml::trait_xml! {
+    <trait>
+        <name>Meter</name>
+        <assocfn>
+            <name>read</name>
+            <params>
+                <param>
+                    <name>self</name>
+                    <type>&mut Self</type>
+                </param>
+            </params>
+            <ret>u32</ret>
+        </assocfn>
+        <mock>
+            <!-- generated test double -->
+            <name>MockMeter</name>
+            <!-- nothing else -->
+        </mock>
+        <dyn-companion>
+            <!-- erased version -->
+            <name>DynMeter</name>
+            <!-- and Box<dyn DynMeter> implements Meter -->
+            <boxed/>
+        </dyn-companion>
+        <ffi-vtable>
+            <!-- for plugins -->
+            <name>MeterVTable</name>
+            <!-- the safe side -->
+            <wrapper>MeterHandle</wrapper>
+        </ffi-vtable>
+        <descriptor>
+            <!-- for the registry -->
+            <name>METER</name>
+        </descriptor>
+    </trait>
+}
+
+#[derive(Clone)]
+struct S(u8);
+
+impl Commented<u8> for S {
+    fn pick(&self, other: u8) -> u8 {
+        self.0.max(other)
+    }
+}
+
+fn main() {
+    assert_eq!(S(3).pick(5), 5);
+
+    let mut mock = MockMeter::default();
+    mock.read.returns(3);
+    assert_eq!(Meter::read(&mut mock), 3);
+
+    let boxed: Box<dyn DynMeter> = Box::new(MockMeter::default());
+    let _: fn(&mut Box<dyn DynMeter>) -> u32 = <Box<dyn DynMeter> as Meter>::read;
+    drop(boxed);
+
+    let _ = MeterVTable::vtable_for::<MockMeter>();
+    let _ = MeterHandle::new(&mut mock);
+    assert_eq!(METER.name, "Meter");
+}
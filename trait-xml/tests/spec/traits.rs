@@ -0,0 +1,54 @@
+// Generated from tests/spec/sealed.xml by trait_xml::build - edit that instead.
+/// ```
+/// assert_eq!(7u8.value(), 7);
+/// ```
+pub trait Digit: __sealed::Sealed {
+    /// ```
+    /// let digits: Vec<u8> = vec![1, 2];
+    /// assert_eq!(digits.iter().map(|d| d.value()).sum::<u8>(), 3);
+    /// ```
+    fn value(&self) -> u8;
+}
+
+mod __sealed {
+    pub trait Sealed {}
+}
+
+impl __sealed::Sealed for u8 {}
+
+#[cfg(test)]
+mod digit_value {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn example() {
+        assert_eq!(7u8.value(), 7);
+    }
+}
+
+// Generated from tests/spec/shape.xml by trait_xml::build - edit that instead.
+pub trait Shape<'a, T: Copy + 'a, const N: usize>: Clone + std::fmt::Debug
+where
+    Vec<T>: Default,
+{
+    type Points: Iterator<Item = (T, T)>;
+
+    const SIDES: usize = N;
+
+    fn points(&self) -> Self::Points;
+
+    fn scale<F>(&mut self, #[allow(unused_variables)] by: F)
+    where
+        F: Into<f64> + Copy,
+    {
+        let _ = &self;
+    }
+
+    unsafe extern "C" fn double(mut x: u8) -> u8 {
+        if x < 128 {
+            x *= 2;
+        }
+        x
+    }
+}